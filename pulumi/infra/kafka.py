@@ -136,13 +136,21 @@ class Kafka(pulumi.ComponentResource):
             self.confluent_environment = None
             # This list must match the set of all topics specified in
             # grapl-infrastructure/pulumi/confluent_cloud/index.ts
+            #
+            # The *-failed topics must be created there too, along with ACLs
+            # allowing generator-dispatcher, graph-merger and
+            # analyzer-dispatcher to write to raw-logs-failed,
+            # identified-graphs-failed and merged-graphs-failed respectively.
             topics = [
                 "raw-logs",
                 "raw-logs-retry",
+                "raw-logs-failed",
                 "generated-graphs",
                 "identified-graphs",
+                "identified-graphs-failed",
                 "merged-graphs",
                 "merged-graphs-retry",
+                "merged-graphs-failed",
                 "analyzer-executions",
                 "engagements",
             ]
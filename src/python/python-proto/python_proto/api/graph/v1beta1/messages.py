@@ -213,6 +213,42 @@ class ImmutableStrProp(SerDe[proto.ImmutableStrProp]):
         return proto_immutable_str_prop
 
 
+@dataclasses.dataclass(frozen=True)
+class MutableStrProp(SerDe[proto.MutableStrProp]):
+    prop: str
+    _proto_cls = proto.MutableStrProp
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_mutable_str_prop: proto.MutableStrProp,
+    ) -> MutableStrProp:
+        return MutableStrProp(prop=proto_mutable_str_prop.prop)
+
+    def into_proto(self) -> proto.MutableStrProp:
+        proto_mutable_str_prop = proto.MutableStrProp()
+        proto_mutable_str_prop.prop = self.prop
+        return proto_mutable_str_prop
+
+
+@dataclasses.dataclass(frozen=True)
+class MutableF64Prop(SerDe[proto.MutableF64Prop]):
+    prop: float
+    _proto_cls = proto.MutableF64Prop
+
+    @classmethod
+    def from_proto(
+        cls,
+        proto_mutable_f64_prop: proto.MutableF64Prop,
+    ) -> MutableF64Prop:
+        return MutableF64Prop(prop=proto_mutable_f64_prop.prop)
+
+    def into_proto(self) -> proto.MutableF64Prop:
+        proto_mutable_f64_prop = proto.MutableF64Prop()
+        proto_mutable_f64_prop.prop = self.prop
+        return proto_mutable_f64_prop
+
+
 @dataclasses.dataclass(frozen=True)
 class NodeProperty(SerDe[proto.NodeProperty]):
     property_: (
@@ -223,6 +259,8 @@ class NodeProperty(SerDe[proto.NodeProperty]):
         | DecrementOnlyIntProp
         | ImmutableIntProp
         | ImmutableStrProp
+        | MutableStrProp
+        | MutableF64Prop
     )
     _proto_cls = proto.NodeProperty
 
@@ -266,6 +304,14 @@ class NodeProperty(SerDe[proto.NodeProperty]):
             return NodeProperty(
                 property_=ImmutableStrProp.from_proto(proto_node_property.immutable_str)
             )
+        elif proto_node_property.HasField("mutable_str"):
+            return NodeProperty(
+                property_=MutableStrProp.from_proto(proto_node_property.mutable_str)
+            )
+        elif proto_node_property.HasField("mutable_f64"):
+            return NodeProperty(
+                property_=MutableF64Prop.from_proto(proto_node_property.mutable_f64)
+            )
         else:
             raise Exception("Encountered unknown type")
 
@@ -299,6 +345,14 @@ class NodeProperty(SerDe[proto.NodeProperty]):
             proto_node_property.immutable_str.CopyFrom(
                 cast(proto.ImmutableStrProp, self.property_.into_proto())
             )
+        elif type(self.property_) is MutableStrProp:
+            proto_node_property.mutable_str.CopyFrom(
+                cast(proto.MutableStrProp, self.property_.into_proto())
+            )
+        elif type(self.property_) is MutableF64Prop:
+            proto_node_property.mutable_f64.CopyFrom(
+                cast(proto.MutableF64Prop, self.property_.into_proto())
+            )
         else:
             raise Exception("Encountered unknown type")
         return proto_node_property
@@ -23,6 +23,8 @@ from python_proto.api.graph.v1beta1.messages import (
     MergedEdgeList,
     MergedGraph,
     MergedNode,
+    MutableF64Prop,
+    MutableStrProp,
     NodeDescription,
     NodeProperty,
     Session,
@@ -243,6 +245,18 @@ def immutable_str_props(
     return st.builds(ImmutableStrProp, prop=props)
 
 
+def mutable_str_props(
+    props: st.SearchStrategy[str] = st.text(),
+) -> st.SearchStrategy[MutableStrProp]:
+    return st.builds(MutableStrProp, prop=props)
+
+
+def mutable_f64_props(
+    props: st.SearchStrategy[float] = st.floats(allow_nan=False),
+) -> st.SearchStrategy[MutableF64Prop]:
+    return st.builds(MutableF64Prop, prop=props)
+
+
 def node_properties(
     properties: st.SearchStrategy[
         (
@@ -253,6 +267,8 @@ def node_properties(
             | DecrementOnlyIntProp
             | ImmutableIntProp
             | ImmutableStrProp
+            | MutableStrProp
+            | MutableF64Prop
         )
     ] = st.one_of(
         increment_only_uint_props(),
@@ -262,6 +278,8 @@ def node_properties(
         decrement_only_int_props(),
         immutable_int_props(),
         immutable_str_props(),
+        mutable_str_props(),
+        mutable_f64_props(),
     )
 ) -> st.SearchStrategy[NodeProperty]:
     return st.builds(NodeProperty, property_=properties)
@@ -23,6 +23,8 @@ from python_proto.tests.strategies import (
     merged_edges,
     merged_graphs,
     merged_nodes,
+    mutable_f64_props,
+    mutable_str_props,
     node_descriptions,
     node_properties,
     sessions,
@@ -106,6 +108,14 @@ def test_merged_node_encode_decode() -> None:
     check_encode_decode_invariant(merged_nodes())
 
 
+def test_mutable_f64_prop_encode_decode() -> None:
+    check_encode_decode_invariant(mutable_f64_props())
+
+
+def test_mutable_str_prop_encode_decode() -> None:
+    check_encode_decode_invariant(mutable_str_props())
+
+
 def test_node_description_encode_decode() -> None:
     check_encode_decode_invariant(node_descriptions())
 
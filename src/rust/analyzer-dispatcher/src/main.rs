@@ -13,6 +13,7 @@ use futures::{
 use kafka::{
     config::{
         ConsumerConfig,
+        DeadLetterProducerConfig,
         RetryProducerConfig,
     },
     CommitError,
@@ -83,6 +84,7 @@ struct AnalyzerDispatcherConfigParams {
 struct AnalyzerDispatcherConfig {
     pub kafka_config: ConsumerConfig,
     pub kafka_retry_producer_config: RetryProducerConfig,
+    pub kafka_dead_letter_producer_config: DeadLetterProducerConfig,
     pub params: AnalyzerDispatcherConfigParams,
 }
 
@@ -91,6 +93,7 @@ impl AnalyzerDispatcherConfig {
         Self {
             kafka_config: ConsumerConfig::parse(),
             kafka_retry_producer_config: RetryProducerConfig::parse(),
+            kafka_dead_letter_producer_config: DeadLetterProducerConfig::parse(),
             params: AnalyzerDispatcherConfigParams::parse(),
         }
     }
@@ -138,8 +141,10 @@ impl AnalyzerDispatcher {
         plugin_work_queue_client: PluginWorkQueueServiceClient,
     ) -> Result<Self, ConfigurationError> {
         let merged_graphs_consumer: Consumer<MergedGraph> = Consumer::new(config.kafka_config)?;
-        let merged_graphs_retry_producer: RetryProducer<MergedGraph> =
-            RetryProducer::new(config.kafka_retry_producer_config)?;
+        let merged_graphs_retry_producer: RetryProducer<MergedGraph> = RetryProducer::new(
+            config.kafka_retry_producer_config,
+            config.kafka_dead_letter_producer_config,
+        )?;
         let client_config = PluginRegistryClientConfig::parse();
 
         let plugin_registry_client =
@@ -252,6 +257,7 @@ impl AnalyzerDispatcher {
                                         retry_message(
                                             &merged_graphs_retry_producer,
                                             envelope,
+                                            "analyzer IDs cache miss".to_string(),
                                         )
                                             .instrument(span)
                                             .await?;
@@ -271,6 +277,7 @@ impl AnalyzerDispatcher {
                                         retry_message(
                                             &merged_graphs_retry_producer,
                                             envelope,
+                                            format!("analyzer IDs cache error {reason}"),
                                         )
                                             .instrument(span)
                                             .await?;
@@ -317,9 +324,12 @@ impl AnalyzerDispatcher {
 async fn retry_message(
     merged_graphs_retry_producer: &RetryProducer<MergedGraph>,
     envelope: Envelope<MergedGraph>,
+    error_reason: String,
 ) -> Result<(), ProducerError> {
     // TODO: be a little smarter about handling ProducerError here
-    merged_graphs_retry_producer.send(envelope).await
+    merged_graphs_retry_producer
+        .send(envelope, error_reason)
+        .await
 }
 
 #[tracing::instrument(skip(plugin_work_queue_client, analyzer_ids, envelope), err)]
@@ -115,4 +115,21 @@ impl EventSourceDbClient {
         .await?;
         Ok(row)
     }
+
+    #[instrument(skip(self), err)]
+    pub async fn delete_event_sources_for_tenant(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM event_sources
+            WHERE tenant_id = $1
+            "#,
+            tenant_id,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
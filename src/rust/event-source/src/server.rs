@@ -111,4 +111,15 @@ impl EventSourceApi for EventSourceApiImpl {
         };
         Ok(native::GetEventSourceResponse { event_source })
     }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn delete_event_sources_for_tenant(
+        &self,
+        request: native::DeleteEventSourcesForTenantRequest,
+    ) -> Result<native::DeleteEventSourcesForTenantResponse, Self::Error> {
+        self.db_client
+            .delete_event_sources_for_tenant(request.tenant_id)
+            .await?;
+        Ok(native::DeleteEventSourcesForTenantResponse {})
+    }
 }
@@ -72,3 +72,50 @@ async fn test_create_update_get() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[test_log::test(tokio::test)]
+async fn test_delete_event_sources_for_tenant() -> eyre::Result<()> {
+    let client_config = EventSourceClientConfig::parse();
+    let mut client = EventSourceServiceClient::connect_with_config(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let other_tenant_id = uuid::Uuid::new_v4();
+
+    let mut event_source_ids = vec![];
+    for tenant_id in [tenant_id, tenant_id, other_tenant_id] {
+        let request = es_api::CreateEventSourceRequest {
+            display_name: "Name".to_owned(),
+            description: "Description".to_owned(),
+            tenant_id,
+        };
+        event_source_ids.push(client.create_event_source(request).await?.event_source_id);
+    }
+
+    client
+        .delete_event_sources_for_tenant(es_api::DeleteEventSourcesForTenantRequest { tenant_id })
+        .await?;
+
+    // Only the tenant's own event sources are gone
+    for event_source_id in &event_source_ids[..2] {
+        let get_response = client
+            .get_event_source(es_api::GetEventSourceRequest {
+                event_source_id: *event_source_id,
+            })
+            .await;
+        assert!(get_response.is_err(), "{get_response:?}");
+    }
+
+    let get_response = client
+        .get_event_source(es_api::GetEventSourceRequest {
+            event_source_id: event_source_ids[2],
+        })
+        .await?;
+    assert_eq!(get_response.event_source.tenant_id, other_tenant_id);
+
+    // Deleting them again is a no-op
+    client
+        .delete_event_sources_for_tenant(es_api::DeleteEventSourcesForTenantRequest { tenant_id })
+        .await?;
+
+    Ok(())
+}
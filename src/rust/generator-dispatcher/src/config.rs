@@ -1,6 +1,7 @@
 use clap::Parser;
 use kafka::config::{
     ConsumerConfig,
+    DeadLetterProducerConfig,
     RetryProducerConfig,
 };
 
@@ -26,6 +27,7 @@ pub struct GeneratorDispatcherConfigParams {
 pub struct GeneratorDispatcherConfig {
     pub kafka_config: ConsumerConfig,
     pub kafka_retry_producer_config: RetryProducerConfig,
+    pub kafka_dead_letter_producer_config: DeadLetterProducerConfig,
     pub params: GeneratorDispatcherConfigParams,
 }
 
@@ -34,6 +36,7 @@ impl GeneratorDispatcherConfig {
         Self {
             kafka_config: ConsumerConfig::parse(),
             kafka_retry_producer_config: RetryProducerConfig::parse(),
+            kafka_dead_letter_producer_config: DeadLetterProducerConfig::parse(),
             params: GeneratorDispatcherConfigParams::parse(),
         }
     }
@@ -97,8 +97,10 @@ impl GeneratorDispatcher {
         plugin_work_queue_client: PluginWorkQueueServiceClient,
     ) -> Result<Self, ConfigurationError> {
         let raw_logs_consumer: Consumer<RawLog> = Consumer::new(config.kafka_config)?;
-        let raw_logs_retry_producer: RetryProducer<RawLog> =
-            RetryProducer::new(config.kafka_retry_producer_config)?;
+        let raw_logs_retry_producer: RetryProducer<RawLog> = RetryProducer::new(
+            config.kafka_retry_producer_config,
+            config.kafka_dead_letter_producer_config,
+        )?;
         let client_config = PluginRegistryClientConfig::parse();
         let plugin_registry_client =
             PluginRegistryServiceClient::connect_with_config(client_config).await?;
@@ -212,7 +214,8 @@ impl GeneratorDispatcher {
 
                                         retry_message(
                                             &raw_logs_retry_producer,
-                                            envelope
+                                            envelope,
+                                            "generator IDs cache miss".to_string(),
                                         ).instrument(span).await?;
 
                                         Ok(())
@@ -229,7 +232,8 @@ impl GeneratorDispatcher {
 
                                         retry_message(
                                             &raw_logs_retry_producer,
-                                            envelope
+                                            envelope,
+                                            format!("generator IDs cache error {reason}"),
                                         )
                                             .instrument(span)
                                             .await?;
@@ -276,9 +280,10 @@ impl GeneratorDispatcher {
 async fn retry_message(
     raw_logs_retry_producer: &RetryProducer<RawLog>,
     envelope: Envelope<RawLog>,
+    error_reason: String,
 ) -> Result<(), ProducerError> {
     // TODO: be a little smarter about handling ProducerError here
-    raw_logs_retry_producer.send(envelope).await
+    raw_logs_retry_producer.send(envelope, error_reason).await
 }
 
 #[tracing::instrument(skip(plugin_work_queue_client, generator_ids, envelope), err)]
@@ -11,18 +11,17 @@ use grapl_tracing::setup_tracing;
 use kafka::{
     config::{
         ConsumerConfig,
+        DeadLetterProducerConfig,
         ProducerConfig,
     },
+    DeadLetterProducer,
     StreamProcessor,
     StreamProcessorError,
 };
 use rusoto_dynamodb::DynamoDbClient;
-use rust_proto::graplinc::grapl::{
-    api::graph::v1beta1::{
-        IdentifiedGraph,
-        MergedGraph,
-    },
-    pipeline::v1beta1::Envelope,
+use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+    IdentifiedGraph,
+    MergedGraph,
 };
 use tokio::sync::Mutex;
 use tracing::{
@@ -33,6 +32,7 @@ use tracing::{
 use crate::{
     reverse_resolver::ReverseEdgeResolver,
     service::{
+        self,
         GraphMerger,
         GraphMergerError,
     },
@@ -62,11 +62,13 @@ async fn main() -> Result<(), GraphMergerError> {
 
     let consumer_config = ConsumerConfig::parse();
     let producer_config = ProducerConfig::parse();
+    let dead_letter_producer_config = DeadLetterProducerConfig::parse();
 
     handler(
         Arc::new(Mutex::new(graph_merger)),
         consumer_config,
         producer_config,
+        dead_letter_producer_config,
     )
     .await
 }
@@ -76,6 +78,7 @@ async fn handler(
     graph_merger: Arc<Mutex<GraphMerger>>,
     consumer_config: ConsumerConfig,
     producer_config: ProducerConfig,
+    dead_letter_producer_config: DeadLetterProducerConfig,
 ) -> Result<(), GraphMergerError> {
     tracing::info!(
         message = "configuring kafka stream processor",
@@ -83,80 +86,31 @@ async fn handler(
         consumer_group_name = %consumer_config.consumer_group_name,
         consumer_topic = %consumer_config.topic,
         producer_topic = %producer_config.topic,
+        failed_topic = ?dead_letter_producer_config.topic,
     );
 
     // TODO: also construct a stream processor for retries
 
     let stream_processor: StreamProcessor<IdentifiedGraph, MergedGraph> =
         StreamProcessor::new(consumer_config, producer_config)?;
+    let dead_letter_producer: DeadLetterProducer<IdentifiedGraph> =
+        DeadLetterProducer::new(dead_letter_producer_config)?;
 
     tracing::info!(message = "kafka stream processor configured successfully",);
 
     let stream = stream_processor.stream::<_, _, StreamProcessorError>(move |event| {
         let graph_merger = graph_merger.clone();
+        let dead_letter_producer = dead_letter_producer.clone();
         async move {
             let (span, envelope) = event?;
             let handler_span = span.clone();
             let _guard = span.enter();
-            let tenant_id = envelope.tenant_id();
-            let trace_id = envelope.trace_id();
-            let event_source_id = envelope.event_source_id();
 
             tracing::debug!("received kafka message");
 
-            match graph_merger
-                .lock()
-                .await
-                .handle_event(envelope.inner_message())
+            service::merge_or_dead_letter(&graph_merger, &dead_letter_producer, envelope)
                 .instrument(handler_span)
                 .await
-            {
-                Ok(merged_graph) => Ok(Some(Envelope::new(
-                    tenant_id,
-                    trace_id,
-                    event_source_id,
-                    merged_graph,
-                ))),
-                Err(e) => match e {
-                    Ok((_, e)) => {
-                        match e {
-                            GraphMergerError::Unexpected(ref reason) => {
-                                tracing::error!(
-                                    message = "unexpected error",
-                                    reason = %reason,
-                                    error = %e,
-                                );
-                                // TODO: write message to failed topic here
-                                Err(StreamProcessorError::from(e))
-                            }
-                            _ => {
-                                tracing::error!(
-                                    mesage = "unexpected error",
-                                    error = %e,
-                                );
-                                // TODO: write message to failed topic here
-                                Err(StreamProcessorError::from(e))
-                            }
-                        }
-                    }
-                    Err(e) => match e {
-                        GraphMergerError::Unexpected(reason) => {
-                            tracing::warn!(
-                                message = "unexpected error",
-                                reason = %reason,
-                            );
-                            Ok(None)
-                        }
-                        _ => {
-                            tracing::error!(
-                                message = "unknown error",
-                                error = %e,
-                            );
-                            Err(StreamProcessorError::from(e))
-                        }
-                    },
-                },
-            }
         }
         .into_stream()
         .filter_map(|res| async move { res.transpose() })
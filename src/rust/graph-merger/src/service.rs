@@ -9,10 +9,18 @@ use std::{
 
 use dgraph_tonic::Client as DgraphClient;
 use grapl_tracing::SetupTracingError;
-use rust_proto::graplinc::grapl::api::graph::v1beta1::{
-    IdentifiedGraph,
-    MergedGraph,
+use kafka::{
+    DeadLetterProducer,
+    StreamProcessorError,
 };
+use rust_proto::graplinc::grapl::{
+    api::graph::v1beta1::{
+        IdentifiedGraph,
+        MergedGraph,
+    },
+    pipeline::v1beta1::Envelope,
+};
+use tokio::sync::Mutex;
 
 use crate::{
     reverse_resolver::ReverseEdgeResolver,
@@ -22,7 +30,7 @@ use crate::{
 #[non_exhaustive]
 #[derive(thiserror::Error, Debug)]
 pub enum GraphMergerError {
-    #[error("unexpected error")]
+    #[error("unexpected error {0}")]
     Unexpected(String),
 
     #[error("error processing event {0}")]
@@ -71,7 +79,7 @@ impl GraphMerger {
     pub async fn handle_event(
         &mut self,
         subgraph: IdentifiedGraph,
-    ) -> Result<MergedGraph, Result<(MergedGraph, GraphMergerError), GraphMergerError>> {
+    ) -> Result<MergedGraph, GraphMergerError> {
         if subgraph.is_empty() {
             tracing::warn!("Attempted to merge empty subgraph. Short circuiting.");
             return Ok(MergedGraph::default());
@@ -89,8 +97,7 @@ impl GraphMerger {
         let reverse = self
             .reverse_edge_resolver
             .resolve_reverse_edges(uncached_edges.clone())
-            .await
-            .map_err(Err)?;
+            .await?;
 
         uncached_edges.extend_from_slice(&reverse[..]);
 
@@ -117,6 +124,42 @@ impl GraphMerger {
     }
 }
 
+/// Merges the envelope's identified graph, returning an envelope of the merged
+/// graph to publish. If the graph can't be merged the envelope is sent to the
+/// failed topic, with the error as its reason, and nothing is returned.
+pub async fn merge_or_dead_letter(
+    graph_merger: &Mutex<GraphMerger>,
+    dead_letter_producer: &DeadLetterProducer<IdentifiedGraph>,
+    envelope: Envelope<IdentifiedGraph>,
+) -> Result<Option<Envelope<MergedGraph>>, StreamProcessorError> {
+    let tenant_id = envelope.tenant_id();
+    let trace_id = envelope.trace_id();
+    let event_source_id = envelope.event_source_id();
+
+    let result = graph_merger
+        .lock()
+        .await
+        .handle_event(envelope.clone().inner_message())
+        .await;
+
+    match result {
+        Ok(merged_graph) => Ok(Some(Envelope::new(
+            tenant_id,
+            trace_id,
+            event_source_id,
+            merged_graph,
+        ))),
+        Err(e) => {
+            tracing::error!(
+                message = "failed to merge identified graph, sending it to the failed topic",
+                error = %e,
+            );
+            dead_letter_producer.send(envelope, e.to_string()).await?;
+            Ok(None)
+        }
+    }
+}
+
 pub fn time_based_key_fn(_event: &[u8]) -> String {
     let cur_ms = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(n) => n.as_millis(),
@@ -50,6 +50,8 @@ fn escape_prop(node_property: &NodeProperty) -> Escaped {
         Property::DecrementOnlyUintProp(prop) => escape_quote(&prop.to_string()),
         Property::ImmutableUintProp(prop) => escape_quote(&prop.to_string()),
         Property::ImmutableStrProp(prop) => escape_quote(prop.as_inner()),
+        Property::MutableStrProp(prop) => escape_quote(prop.as_inner()),
+        Property::MutableF64Prop(prop) => escape_quote(&prop.to_string()),
     }
 }
 
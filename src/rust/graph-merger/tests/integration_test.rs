@@ -1,29 +1,60 @@
 #![cfg(feature = "integration_tests")]
 
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    time::Duration,
+};
 
 use bytes::Bytes;
+use dgraph_tonic::Client as DgraphClient;
 use e2e_tests::test_utils::context::{
     E2eTestContext,
     SetupResult,
 };
+use futures::{
+    pin_mut,
+    StreamExt,
+};
+use graph_merger::{
+    reverse_resolver::ReverseEdgeResolver,
+    service::{
+        merge_or_dead_letter,
+        GraphMerger,
+    },
+};
+use grapl_config::env_helpers::FromEnv;
 use kafka::{
-    config::ConsumerConfig,
+    config::{
+        ConsumerConfig,
+        DeadLetterProducerConfig,
+    },
     test_utils::topic_scanner::KafkaTopicScanner,
+    BytesConsumer,
+    DeadLetterProducer,
 };
-use rust_proto::graplinc::grapl::{
-    api::{
-        graph::v1beta1::{
-            ImmutableUintProp,
-            MergedGraph,
-            MergedNode,
-            Property,
+use rusoto_dynamodb::DynamoDbClient;
+use rust_proto::{
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                IdentifiedGraph,
+                IdentifiedNode,
+                ImmutableUintProp,
+                MergedGraph,
+                MergedNode,
+                Property,
+            },
+            pipeline_ingress::v1beta1::PublishRawLogRequest,
+        },
+        pipeline::v1beta1::{
+            Envelope,
+            FailedEnvelope,
         },
-        pipeline_ingress::v1beta1::PublishRawLogRequest,
     },
-    pipeline::v1beta1::Envelope,
+    SerDe,
 };
 use test_context::test_context;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 fn find_node<'a>(
@@ -39,6 +70,8 @@ fn find_node<'a>(
 }
 
 const CONSUMER_TOPIC: &'static str = "merged-graphs";
+const ORIGIN_TOPIC: &'static str = "identified-graphs";
+const FAILED_TOPIC: &'static str = "identified-graphs-failed";
 
 #[test_context(E2eTestContext)]
 #[tokio::test]
@@ -168,3 +201,78 @@ async fn test_sysmon_event_produces_merged_graph(ctx: &mut E2eTestContext) -> ey
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_unmergeable_graph_is_dead_lettered() -> eyre::Result<()> {
+    // Resolving reverse edges against a schema table which doesn't exist
+    // fails, so any graph with an edge in it can't be merged.
+    std::env::set_var(
+        "GRAPL_SCHEMA_TABLE",
+        format!("missing-schema-table-{}", Uuid::new_v4()),
+    );
+    let mg_alpha = grapl_config::mg_alphas()
+        .pop()
+        .expect("Dgraph Alpha not specified.");
+    let graph_merger = Mutex::new(GraphMerger::new(
+        DgraphClient::new(mg_alpha).expect("Failed to create dgraph client."),
+        ReverseEdgeResolver::new(DynamoDbClient::from_env(), 1000),
+    ));
+
+    let consumer_config = ConsumerConfig::with_topic(FAILED_TOPIC);
+    let dead_letter_producer = DeadLetterProducer::new(DeadLetterProducerConfig {
+        bootstrap_servers: consumer_config.bootstrap_servers.clone(),
+        sasl_username: consumer_config.sasl_username.clone(),
+        sasl_password: consumer_config.sasl_password.clone(),
+        topic: Some(FAILED_TOPIC.to_owned()),
+        origin_topic: ORIGIN_TOPIC.to_owned(),
+    })?;
+
+    let mut identified_graph = IdentifiedGraph::new();
+    for node_key in ["parent-process", "child-process"] {
+        identified_graph.add_node(IdentifiedNode {
+            properties: HashMap::new(),
+            node_key: node_key.to_owned(),
+            node_type: "Process".to_owned(),
+        });
+    }
+    identified_graph.add_edge("children", "parent-process", "child-process");
+
+    let tenant_id = Uuid::new_v4();
+    let envelope = Envelope::new(tenant_id, Uuid::new_v4(), Uuid::new_v4(), identified_graph);
+
+    let merged =
+        merge_or_dead_letter(&graph_merger, &dead_letter_producer, envelope.clone()).await?;
+    assert!(merged.is_none());
+
+    // read the failed topic from the start with a consumer group of our own
+    let consumer = BytesConsumer::from_earliest(ConsumerConfig {
+        consumer_group_name: format!("{}-{}", consumer_config.consumer_group_name, Uuid::new_v4()),
+        ..consumer_config
+    })?;
+    let failed_envelope = tokio::time::timeout(Duration::from_secs(60), async {
+        let stream = consumer.stream();
+        pin_mut!(stream);
+        while let Some(message) = stream.next().await {
+            let message = message.expect("error consuming message from kafka");
+            match FailedEnvelope::deserialize(message) {
+                Ok(failed_envelope) if failed_envelope.tenant_id() == tenant_id => {
+                    return failed_envelope;
+                }
+                _ => continue,
+            }
+        }
+        panic!("failed topic stream ended");
+    })
+    .await?;
+
+    assert_eq!(failed_envelope.origin_topic(), ORIGIN_TOPIC);
+    assert!(failed_envelope
+        .error_reason()
+        .starts_with("unexpected error"));
+    assert_eq!(
+        Envelope::<IdentifiedGraph>::deserialize(failed_envelope.envelope())?,
+        envelope
+    );
+
+    Ok(())
+}
@@ -1,10 +1,39 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    str::FromStr,
+};
 
 use rust_proto::client_factory::services::{
     GraphSchemaManagerClientConfig,
     UidAllocatorClientConfig,
 };
 
+/// What to do when a new edge would give a node a second edge of a name that
+/// the schema declares as ToOne, e.g. a second `parent` for a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToOneEdgePolicy {
+    /// Delete the existing edge (and its reverse), then create the new one
+    Replace,
+    /// Fail the write, leaving the existing edge in place
+    Reject,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("invalid ToOne edge policy {0:?}, expected `replace` or `reject`")]
+pub struct ToOneEdgePolicyError(String);
+
+impl FromStr for ToOneEdgePolicy {
+    type Err = ToOneEdgePolicyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "replace" => Ok(ToOneEdgePolicy::Replace),
+            "reject" => Ok(ToOneEdgePolicy::Reject),
+            _ => Err(ToOneEdgePolicyError(value.to_owned())),
+        }
+    }
+}
+
 #[derive(clap::Parser, Debug, Clone)]
 pub struct GraphDbConfig {
     #[clap(long, env, value_delimiter = ',')]
@@ -18,6 +47,17 @@ pub struct GraphDbConfig {
     pub graph_db_auth_password: String,
 }
 
+impl GraphDbConfig {
+    pub async fn connect(&self) -> Result<scylla::Session, Box<dyn std::error::Error>> {
+        let mut scylla_config = scylla::SessionConfig::new();
+        scylla_config.add_known_nodes_addr(&self.graph_db_addresses[..]);
+        scylla_config.auth_username = Some(self.graph_db_auth_username.to_owned());
+        scylla_config.auth_password = Some(self.graph_db_auth_password.to_owned());
+
+        Ok(scylla::Session::connect(scylla_config).await?)
+    }
+}
+
 #[derive(clap::Parser, Debug, Clone)]
 pub struct GraphMutationServiceConfig {
     #[clap(env)]
@@ -32,4 +72,31 @@ pub struct GraphMutationServiceConfig {
 
     #[clap(flatten)]
     pub graph_db_config: GraphDbConfig,
+
+    #[clap(
+        long,
+        env = "GRAPH_MUTATION_TO_ONE_EDGE_POLICY",
+        default_value = "replace"
+    )]
+    /// What to do with a node's existing ToOne edge when another edge of the
+    /// same name is created from it, either `replace` or `reject`
+    pub to_one_edge_policy: ToOneEdgePolicy,
+
+    #[clap(
+        long,
+        env = "GRAPH_MUTATION_PROPERTY_SCHEMA_CACHE_TTL_MS",
+        default_value = "60000"
+    )]
+    /// How long a property's declared type is cached for, and so how long a
+    /// redeployed schema may take to be enforced
+    pub property_schema_cache_ttl_ms: u64,
+
+    #[clap(
+        long,
+        env = "GRAPH_MUTATION_EDGE_SCHEMA_CACHE_TTL_MS",
+        default_value = "60000"
+    )]
+    /// How long an edge's reverse and cardinalities are cached for, and so how
+    /// long a redeployed schema may take to be enforced
+    pub edge_schema_cache_ttl_ms: u64,
 }
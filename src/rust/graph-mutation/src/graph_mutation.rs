@@ -1,21 +1,49 @@
-use std::sync::Arc;
+use std::{
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+    sync::Arc,
+};
 
+use futures::{
+    future::try_join_all,
+    stream,
+    StreamExt,
+    TryStreamExt,
+};
 use rust_proto::{
     graplinc::grapl::{
         api::{
             graph::v1beta1::Property,
             graph_mutation::v1beta1::{
                 messages::{
+                    BatchCreateEdge,
+                    BatchCreateNode,
+                    ClearPropertyRequest,
+                    ClearPropertyResponse,
                     CreateEdgeRequest,
                     CreateEdgeResponse,
                     CreateNodeRequest,
                     CreateNodeResponse,
+                    DeleteEdgeRequest,
+                    DeleteEdgeResponse,
+                    DeleteNodeRequest,
+                    DeleteNodeResponse,
+                    MutateGraphRequest,
+                    MutateGraphResponse,
                     MutationRedundancy,
+                    NodeRef,
                     SetNodePropertyRequest,
                     SetNodePropertyResponse,
                 },
                 server::GraphMutationApi,
             },
+            graph_schema_manager::v1beta1::messages::{
+                EdgeCardinality,
+                GetEdgeSchemaResponse,
+                PropertyType,
+            },
             uid_allocator::v1beta1::client::UidAllocatorServiceClientError,
         },
         common::v1beta1::types::{
@@ -28,27 +56,57 @@ use rust_proto::{
     protocol::status::Status,
 };
 use scylla::{
+    batch::{
+        Batch,
+        BatchType,
+    },
+    cql_to_rust::{
+        FromRow,
+        FromRowError,
+    },
+    frame::value::{
+        SerializeValuesError,
+        SerializedValues,
+        Value,
+        ValueList,
+    },
     query::Query,
+    transport::query_result::MaybeFirstRowTypedError,
     CachingSession,
 };
+use scylla_table_names::{
+    tenant_keyspace_name,
+    EDGES_TABLE_NAME,
+    IMM_I_64_INDEX_TABLE_NAME,
+    IMM_I_64_TABLE_NAME,
+    IMM_STRING_INDEX_TABLE_NAME,
+    IMM_STRING_TABLE_NAME,
+    IMM_U_64_INDEX_TABLE_NAME,
+    IMM_U_64_TABLE_NAME,
+    MAX_I_64_TABLE_NAME,
+    MAX_U_64_TABLE_NAME,
+    MIN_I_64_TABLE_NAME,
+    MIN_U_64_TABLE_NAME,
+    MUT_F_64_TABLE_NAME,
+    MUT_STRING_TABLE_NAME,
+    NODE_TYPE_TABLE_NAME,
+};
 use uid_allocator::client::CachingUidAllocatorServiceClient as UidAllocatorClient;
 
 use crate::{
+    config::ToOneEdgePolicy,
+    property_schema_resolver::{
+        PropertySchemaResolver,
+        PropertySchemaResolverError,
+    },
     reverse_edge_resolver::{
         ReverseEdgeResolver,
         ReverseEdgeResolverError,
     },
-    table_names::{
-        tenant_keyspace_name,
-        IMM_I_64_TABLE_NAME,
-        IMM_STRING_TABLE_NAME,
-        IMM_U_64_TABLE_NAME,
-        MAX_I_64_TABLE_NAME,
-        MAX_U_64_TABLE_NAME,
-        MIN_I_64_TABLE_NAME,
-        MIN_U_64_TABLE_NAME,
+    write_dropper::{
+        PropertyWrite,
+        WriteDropper,
     },
-    write_dropper::WriteDropper,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -59,10 +117,66 @@ pub enum GraphMutationManagerError {
     ZeroUid,
     #[error("Scylla Error: {0}")]
     ScyllaError(#[from] scylla::transport::errors::QueryError),
+    #[error("Too many rows: {0}")]
+    MaybeFirstRowTypedError(#[from] MaybeFirstRowTypedError),
+    #[error("Row was invalid {0}")]
+    FromRowError(#[from] FromRowError),
+    #[error("SerializeValuesError {0}")]
+    SerializeValuesError(#[from] SerializeValuesError),
+    #[error("Invalid destination_uid '{0}' stored in the edges table")]
+    InvalidStoredUid(i64),
+    #[error("node {source_uid:?} already has a '{edge_name}' edge, to {existing_uid:?}, and may only have one")]
+    ToOneEdgeConflict {
+        source_uid: Uid,
+        edge_name: EdgeName,
+        existing_uid: Uid,
+    },
     #[error("ReverseEdgeResolverError: {0}")]
     ReverseEdgeResolverError(#[from] ReverseEdgeResolverError),
+    #[error("PropertySchemaResolverError: {0}")]
+    PropertySchemaResolverError(#[from] PropertySchemaResolverError),
+    #[error("property '{property_name}' of node type '{node_type}' is declared as {declared:?}, got {written:?}")]
+    PropertyTypeMismatch {
+        node_type: NodeType,
+        property_name: PropertyName,
+        declared: PropertyType,
+        written: PropertyType,
+    },
+    #[error("batch has {count} mutations, more than the limit of {max}")]
+    TooManyMutations { count: usize, max: usize },
+    #[error("node ref {index} is out of range of the batch's {created} created nodes")]
+    InvalidNodeRef { index: u32, created: usize },
+    #[error("node ref {index} is to a created node of type '{created}', not '{referenced}'")]
+    CreatedNodeTypeMismatch {
+        index: u32,
+        created: NodeType,
+        referenced: NodeType,
+    },
+    #[error("node {uid:?} is of type '{stored}', not '{requested}'")]
+    NodeTypeMismatch {
+        uid: Uid,
+        stored: String,
+        requested: NodeType,
+    },
+    #[error(
+        "batch gives node {node:?} more than one '{edge_name}' edge, and it may only have one"
+    )]
+    ConflictingToOneEdges { node: NodeRef, edge_name: EdgeName },
 }
 
+/// The most node creations, property updates and edge creations a single
+/// MutateGraph request may hold.
+const MAX_MUTATIONS_PER_REQUEST: usize = 10_000;
+/// How many of a MutateGraph request's writes are in flight at once.
+const MAX_CONCURRENT_BATCH_WRITES: usize = 32;
+/// How many node type rows go into a single Scylla batch statement, which
+/// keeps batches well under Scylla's batch size warning threshold.
+const NODE_TYPE_BATCH_SIZE: usize = 100;
+/// How many property updates or edges go into a single Scylla batch
+/// statement. Each takes up to two statements, e.g. an immutable property and
+/// its index entry, or an edge and its reverse.
+const MUTATION_BATCH_SIZE: usize = 50;
+
 impl From<GraphMutationManagerError> for Status {
     fn from(e: GraphMutationManagerError) -> Self {
         match e {
@@ -79,6 +193,24 @@ impl From<GraphMutationManagerError> for Status {
                 Status::internal(format!("UidAllocatorClient error: {e:?}"))
             }
             GraphMutationManagerError::ZeroUid => Status::failed_precondition("Allocated Zero Uid"),
+            // Writes that don't match the tenant's schema are the caller's fault
+            e @ GraphMutationManagerError::ReverseEdgeResolverError(
+                ReverseEdgeResolverError::UndeclaredEdge { .. },
+            ) => Status::invalid_argument(e.to_string()),
+            e @ GraphMutationManagerError::PropertySchemaResolverError(
+                PropertySchemaResolverError::UndeclaredProperty { .. },
+            ) => Status::invalid_argument(e.to_string()),
+            e @ GraphMutationManagerError::ToOneEdgeConflict { .. } => {
+                Status::failed_precondition(e.to_string())
+            }
+            e @ (GraphMutationManagerError::PropertyTypeMismatch { .. }
+            | GraphMutationManagerError::TooManyMutations { .. }
+            | GraphMutationManagerError::InvalidNodeRef { .. }
+            | GraphMutationManagerError::CreatedNodeTypeMismatch { .. }
+            | GraphMutationManagerError::ConflictingToOneEdges { .. }
+            | GraphMutationManagerError::NodeTypeMismatch { .. }) => {
+                Status::invalid_argument(e.to_string())
+            }
             e => Status::internal(e.to_string()),
         }
     }
@@ -88,6 +220,8 @@ pub struct GraphMutationManager {
     scylla_client: Arc<CachingSession>,
     uid_allocator_client: UidAllocatorClient,
     reverse_edge_resolver: ReverseEdgeResolver,
+    property_schema_resolver: PropertySchemaResolver,
+    to_one_edge_policy: ToOneEdgePolicy,
     write_dropper: WriteDropper,
 }
 
@@ -96,16 +230,74 @@ impl GraphMutationManager {
         scylla_client: Arc<CachingSession>,
         uid_allocator_client: UidAllocatorClient,
         reverse_edge_resolver: ReverseEdgeResolver,
+        property_schema_resolver: PropertySchemaResolver,
+        to_one_edge_policy: ToOneEdgePolicy,
         max_write_drop_size: usize,
     ) -> Self {
         Self {
             scylla_client,
             uid_allocator_client,
             reverse_edge_resolver,
+            property_schema_resolver,
+            to_one_edge_policy,
             write_dropper: WriteDropper::new(max_write_drop_size),
         }
     }
 
+    /// Writes an immutable property along with its entry in the matching
+    /// property index table, so that the node can be found by the property's
+    /// value without knowing its uid.
+    async fn insert_indexed_property<V>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        property_value: V,
+        table_name: &str,
+        index_table_name: &str,
+    ) -> Result<(), GraphMutationManagerError>
+    where
+        V: Value,
+    {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut batch: scylla::batch::Batch = Default::default();
+        batch.statements.reserve(2);
+        batch.append_statement(Query::new(format!(
+            r"
+            INSERT INTO {tenant_ks}.{table_name}
+            (uid, populated_field, value)
+            VALUES (?, ?, ?)
+            "
+        )));
+        batch.append_statement(Query::new(format!(
+            r"
+            INSERT INTO {tenant_ks}.{index_table_name}
+            (node_type, populated_field, value, uid)
+            VALUES (?, ?, ?, ?)
+            "
+        )));
+        batch.set_is_idempotent(true);
+
+        self.scylla_client
+            .session
+            .batch(
+                &batch,
+                (
+                    (uid.as_i64(), &property_name.value, &property_value),
+                    (
+                        &node_type.value,
+                        &property_name.value,
+                        &property_value,
+                        uid.as_i64(),
+                    ),
+                ),
+            )
+            .await?;
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self), err)]
     async fn upsert_max_u64(
         &self,
@@ -114,10 +306,11 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: u64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_max_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -150,10 +343,11 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: u64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_min_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -187,27 +381,25 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: u64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_imm_u64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
                     let property_value = property_value as i64;
-                    let tenant_ks = tenant_keyspace_name(tenant_id);
-                    let query = Query::new(format!(
-                        r"
-                        INSERT INTO {tenant_ks}.{IMM_U_64_TABLE_NAME} 
-                        (uid, populated_field, value)
-                        VALUES (?, ?, ?)
-                    "
-                    ));
-
-                    self.scylla_client
-                        .execute(query, &(uid.as_i64(), property_name.value, property_value))
-                        .await?;
-                    Ok(())
+                    self.insert_indexed_property(
+                        tenant_id,
+                        uid,
+                        &node_type,
+                        &property_name,
+                        property_value,
+                        IMM_U_64_TABLE_NAME,
+                        IMM_U_64_INDEX_TABLE_NAME,
+                    )
+                    .await
                 },
             )
             .await
@@ -221,10 +413,11 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: i64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_max_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -256,10 +449,11 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: i64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_min_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 property_value,
@@ -292,26 +486,24 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: i64,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_imm_i64(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
-                    let tenant_ks = tenant_keyspace_name(tenant_id);
-                    let query = Query::new(format!(
-                        r"
-                        INSERT INTO {tenant_ks}.{IMM_I_64_TABLE_NAME} 
-                        (uid, populated_field, value)
-                        VALUES (?, ?, ?)
-                    "
-                    ));
-
-                    self.scylla_client
-                        .execute(query, &(uid.as_i64(), property_name.value, property_value))
-                        .await?;
-                    Ok(())
+                    self.insert_indexed_property(
+                        tenant_id,
+                        uid,
+                        &node_type,
+                        &property_name,
+                        property_value,
+                        IMM_I_64_TABLE_NAME,
+                        IMM_I_64_INDEX_TABLE_NAME,
+                    )
+                    .await
                 },
             )
             .await
@@ -323,13 +515,13 @@ impl GraphMutationManager {
         tenant_id: uuid::Uuid,
         uid: Uid,
         node_type: NodeType,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_node_type(tenant_id, uid, || async move {
                 let tenant_ks = tenant_keyspace_name(tenant_id);
                 let query = Query::new(format!(
                     r"
-                        INSERT INTO {tenant_ks}.node_type (uid, node_type)
+                        INSERT INTO {tenant_ks}.{NODE_TYPE_TABLE_NAME} (uid, node_type)
                         VALUES (?, ?)
                     "
                 ));
@@ -350,31 +542,59 @@ impl GraphMutationManager {
         node_type: NodeType,
         property_name: PropertyName,
         property_value: String,
-    ) -> Result<(), GraphMutationManagerError> {
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
         self.write_dropper
             .check_imm_string(
                 tenant_id,
+                uid,
                 node_type.clone(),
                 property_name.clone(),
                 || async move {
-                    let tenant_ks = tenant_keyspace_name(tenant_id);
-                    let query = Query::new(format!(
-                        r"
-                        INSERT INTO {tenant_ks}.{IMM_STRING_TABLE_NAME} 
-                        (uid, populated_field, value)
-                        VALUES (?, ?, ?)
-                    "
-                    ));
-
-                    self.scylla_client
-                        .execute(query, &(uid.as_i64(), property_name.value, property_value))
-                        .await?;
-                    Ok(())
+                    self.insert_indexed_property(
+                        tenant_id,
+                        uid,
+                        &node_type,
+                        &property_name,
+                        property_value,
+                        IMM_STRING_TABLE_NAME,
+                        IMM_STRING_INDEX_TABLE_NAME,
+                    )
+                    .await
                 },
             )
             .await
     }
 
+    /// Sets a last-write-wins property. These don't go through the write
+    /// dropper, since another writer may have replaced a value since this
+    /// instance last wrote it, so writing it again isn't redundant.
+    #[tracing::instrument(skip(self, property_value), err)]
+    async fn upsert_mutable_property<V>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: PropertyName,
+        property_value: V,
+        table_name: &str,
+    ) -> Result<MutationRedundancy, GraphMutationManagerError>
+    where
+        V: Value,
+    {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let query = Query::new(format!(
+            r"
+            INSERT INTO {tenant_ks}.{table_name}
+            (uid, populated_field, value)
+            VALUES (?, ?, ?)
+            "
+        ));
+
+        self.scylla_client
+            .execute(query, &(uid.as_i64(), property_name.value, property_value))
+            .await?;
+        Ok(MutationRedundancy::Maybe)
+    }
+
     #[tracing::instrument(skip(self), err)]
     async fn upsert_edges(
         &self,
@@ -382,8 +602,13 @@ impl GraphMutationManager {
         from_uid: Uid,
         to_uid: Uid,
         f_edge_name: EdgeName,
-        r_edge_name: EdgeName,
-    ) -> Result<(), GraphMutationManagerError> {
+        edge_schema: GetEdgeSchemaResponse,
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
+        let GetEdgeSchemaResponse {
+            reverse_edge_name: r_edge_name,
+            cardinality,
+            reverse_cardinality,
+        } = edge_schema;
         self.write_dropper
             .check_edges(
                 tenant_id,
@@ -392,13 +617,24 @@ impl GraphMutationManager {
                 f_edge_name,
                 r_edge_name,
                 |f_edge_name, r_edge_name| async move {
+                    self.enforce_to_one(tenant_id, from_uid, to_uid, &f_edge_name, &cardinality)
+                        .await?;
+                    self.enforce_to_one(
+                        tenant_id,
+                        to_uid,
+                        from_uid,
+                        &r_edge_name,
+                        &reverse_cardinality,
+                    )
+                    .await?;
+
                     // todo: Batch statements are currently not supported by the Scylla rust client
                     //       https://github.com/scylladb/scylla-rust-driver/issues/469
                     let tenant_ks = tenant_keyspace_name(tenant_id);
 
                     let f_statement = format!(
                         r"
-                        INSERT INTO {tenant_ks}.edges (
+                        INSERT INTO {tenant_ks}.{EDGES_TABLE_NAME} (
                             source_uid,
                             destination_uid,
                             f_edge_name,
@@ -440,68 +676,310 @@ impl GraphMutationManager {
             )
             .await
     }
-}
 
-#[async_trait::async_trait]
-impl GraphMutationApi for GraphMutationManager {
-    type Error = GraphMutationManagerError;
+    /// Writes the node type of each of a batch's newly allocated nodes, in as
+    /// few Scylla batches as possible. The uids were only just allocated, so
+    /// there's nothing for the write dropper to drop.
+    #[tracing::instrument(skip(self, create_nodes), err)]
+    async fn insert_node_types(
+        &self,
+        tenant_id: uuid::Uuid,
+        created_uids: &[Uid],
+        create_nodes: &[BatchCreateNode],
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let statement = format!(
+            r"
+            INSERT INTO {tenant_ks}.{NODE_TYPE_TABLE_NAME} (uid, node_type)
+            VALUES (?, ?)
+            "
+        );
 
-    /// Create Node allocates a new node in the graph, returning the uid of the new node.
-    #[tracing::instrument(skip(self), err)]
-    async fn create_node(
+        let rows: Vec<(i64, &str)> = created_uids
+            .iter()
+            .zip(create_nodes)
+            .map(|(uid, create_node)| (uid.as_i64(), create_node.node_type.value.as_str()))
+            .collect();
+        for chunk in rows.chunks(NODE_TYPE_BATCH_SIZE) {
+            // The rows span many partitions, so a logged batch would only add
+            // overhead; a failed batch is safe to retry
+            let mut batch = Batch::new(BatchType::Unlogged);
+            batch.statements.reserve(chunk.len());
+            for _ in chunk {
+                batch.append_statement(Query::new(statement.clone()));
+            }
+            batch.set_is_idempotent(true);
+
+            self.scylla_client
+                .session
+                .batch(&batch, chunk.to_vec())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Writes a MutateGraph request's property updates, `MUTATION_BATCH_SIZE`
+    /// at a time in Scylla batches, with up to `MAX_CONCURRENT_BATCH_WRITES`
+    /// batches in flight. Updates that the write dropper knows are redundant
+    /// are dropped, and the rest are recorded with it once their batch has
+    /// been written.
+    #[tracing::instrument(skip(self, set_node_properties), err)]
+    async fn batch_upsert_properties(
         &self,
-        request: CreateNodeRequest,
-    ) -> Result<CreateNodeResponse, Self::Error> {
-        let uid = self
-            .uid_allocator_client
-            .allocate_id(request.tenant_id)
+        tenant_id: uuid::Uuid,
+        set_node_properties: Vec<(Uid, NodeType, PropertyName, Property)>,
+    ) -> Result<Vec<MutationRedundancy>, GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut redundancies = Vec::with_capacity(set_node_properties.len());
+        let mut pending = vec![];
+        for (uid, node_type, property_name, property) in set_node_properties {
+            let (write, statements) =
+                property_statements(&tenant_ks, uid, &node_type, &property_name, property)?;
+            if let Some(write) = write {
+                if self.write_dropper.is_redundant_property_write(
+                    tenant_id,
+                    uid,
+                    node_type.clone(),
+                    property_name.clone(),
+                    write,
+                ) {
+                    redundancies.push(MutationRedundancy::True);
+                    continue;
+                }
+            }
+            redundancies.push(MutationRedundancy::Maybe);
+            pending.push(PendingProperty {
+                uid,
+                node_type,
+                property_name,
+                write,
+                statements,
+            });
+        }
+
+        let batches: Vec<_> = pending
+            .chunks(MUTATION_BATCH_SIZE)
+            .map(|chunk| self.write_property_batch(tenant_id, chunk))
+            .collect();
+        stream::iter(batches)
+            .buffer_unordered(MAX_CONCURRENT_BATCH_WRITES)
+            .try_collect::<Vec<()>>()
             .await?;
-        let uid = Uid::from_u64(uid).ok_or_else(|| GraphMutationManagerError::ZeroUid)?;
 
-        self.set_node_type(request.tenant_id, uid, request.node_type)
+        Ok(redundancies)
+    }
+
+    async fn write_property_batch(
+        &self,
+        tenant_id: uuid::Uuid,
+        chunk: &[PendingProperty],
+    ) -> Result<(), GraphMutationManagerError> {
+        // Mutable properties are last-write-wins without an explicit
+        // timestamp, so retrying them could undo a later write
+        let is_idempotent = chunk.iter().all(|pending| pending.write.is_some());
+        let statements = chunk
+            .iter()
+            .flat_map(|pending| pending.statements.iter())
+            .collect();
+        self.execute_batch(statements, is_idempotent).await?;
+
+        for pending in chunk {
+            if let Some(write) = pending.write {
+                self.write_dropper.record_property_write(
+                    tenant_id,
+                    pending.uid,
+                    pending.node_type.clone(),
+                    pending.property_name.clone(),
+                    write,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a MutateGraph request's edges, along with their reverses, like
+    /// `batch_upsert_properties`. Each batch's ToOne edges are enforced just
+    /// before it's written; `check_to_one_edges` has already made sure that no
+    /// two of the request's edges conflict with each other.
+    #[tracing::instrument(skip(self, create_edges), err)]
+    async fn batch_upsert_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        create_edges: Vec<(Uid, Uid, EdgeName, GetEdgeSchemaResponse)>,
+    ) -> Result<Vec<MutationRedundancy>, GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut redundancies = Vec::with_capacity(create_edges.len());
+        let mut pending = vec![];
+        for (from_uid, to_uid, f_edge_name, edge_schema) in create_edges {
+            if self.write_dropper.is_redundant_edges_write(
+                tenant_id,
+                from_uid,
+                to_uid,
+                &f_edge_name,
+            ) {
+                redundancies.push(MutationRedundancy::True);
+                continue;
+            }
+            redundancies.push(MutationRedundancy::Maybe);
+            let statements = edge_statements(
+                &tenant_ks,
+                from_uid,
+                to_uid,
+                &f_edge_name,
+                &edge_schema.reverse_edge_name,
+            )?;
+            pending.push(PendingEdge {
+                from_uid,
+                to_uid,
+                f_edge_name,
+                edge_schema,
+                statements,
+            });
+        }
+
+        let batches: Vec<_> = pending
+            .chunks(MUTATION_BATCH_SIZE)
+            .map(|chunk| self.write_edge_batch(tenant_id, chunk))
+            .collect();
+        stream::iter(batches)
+            .buffer_unordered(MAX_CONCURRENT_BATCH_WRITES)
+            .try_collect::<Vec<()>>()
             .await?;
 
-        Ok(CreateNodeResponse { uid })
+        Ok(redundancies)
     }
 
-    /// SetNodeProperty will update the property of the node with the given uid.
-    /// If the node does not exist it will be created.
-    #[tracing::instrument(skip(self), err)]
-    async fn set_node_property(
+    async fn write_edge_batch(
         &self,
-        request: SetNodePropertyRequest,
-    ) -> Result<SetNodePropertyResponse, Self::Error> {
-        let SetNodePropertyRequest {
+        tenant_id: uuid::Uuid,
+        chunk: &[PendingEdge],
+    ) -> Result<(), GraphMutationManagerError> {
+        try_join_all(
+            chunk
+                .iter()
+                .map(|pending| self.enforce_to_one_both_ways(tenant_id, pending)),
+        )
+        .await?;
+
+        let statements = chunk
+            .iter()
+            .flat_map(|pending| pending.statements.iter())
+            .collect();
+        self.execute_batch(statements, true).await?;
+
+        for pending in chunk {
+            self.write_dropper.record_edges_write(
+                tenant_id,
+                pending.from_uid,
+                pending.to_uid,
+                &pending.f_edge_name,
+                &pending.edge_schema.reverse_edge_name,
+            );
+        }
+        Ok(())
+    }
+
+    async fn enforce_to_one_both_ways(
+        &self,
+        tenant_id: uuid::Uuid,
+        pending: &PendingEdge,
+    ) -> Result<(), GraphMutationManagerError> {
+        self.enforce_to_one(
             tenant_id,
-            uid,
-            node_type,
-            property_name,
-            property,
-        } = request;
-        match property.property {
+            pending.from_uid,
+            pending.to_uid,
+            &pending.f_edge_name,
+            &pending.edge_schema.cardinality,
+        )
+        .await?;
+        self.enforce_to_one(
+            tenant_id,
+            pending.to_uid,
+            pending.from_uid,
+            &pending.edge_schema.reverse_edge_name,
+            &pending.edge_schema.reverse_cardinality,
+        )
+        .await
+    }
+
+    /// Executes the statements in a single logged batch, so that a property
+    /// and its index entry, or an edge and its reverse, are written together.
+    async fn execute_batch(
+        &self,
+        statements: Vec<&BatchStatement>,
+        is_idempotent: bool,
+    ) -> Result<(), GraphMutationManagerError> {
+        let mut batch = Batch::new(BatchType::Logged);
+        let mut values = vec![];
+        for statement in statements {
+            batch.append_statement(Query::new(statement.statement.clone()));
+            values.push(statement.values.clone());
+        }
+        batch.set_is_idempotent(is_idempotent);
+
+        self.scylla_client.session.batch(&batch, values).await?;
+        Ok(())
+    }
+
+    /// Checks that a property is declared in the tenant's schema, with the
+    /// same type as the property being written.
+    async fn validate_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        property: &Property,
+    ) -> Result<(), GraphMutationManagerError> {
+        let declared = self
+            .property_schema_resolver
+            .resolve_property_type(tenant_id, node_type.clone(), property_name.clone())
+            .await?;
+        let written = property_type_of(property);
+        if declared != written {
+            return Err(GraphMutationManagerError::PropertyTypeMismatch {
+                node_type: node_type.clone(),
+                property_name: property_name.clone(),
+                declared,
+                written,
+            });
+        }
+        Ok(())
+    }
+
+    async fn upsert_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        property: Property,
+    ) -> Result<MutationRedundancy, GraphMutationManagerError> {
+        match property {
             Property::IncrementOnlyUintProp(property) => {
                 self.upsert_max_u64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::DecrementOnlyUintProp(property) => {
                 self.upsert_min_u64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::ImmutableUintProp(property) => {
                 self.upsert_immutable_u64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::IncrementOnlyIntProp(property) => {
                 self.upsert_max_i64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::DecrementOnlyIntProp(property) => {
                 self.upsert_min_i64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::ImmutableIntProp(property) => {
                 self.upsert_immutable_i64(tenant_id, uid, node_type, property_name, property.prop)
-                    .await?;
+                    .await
             }
             Property::ImmutableStrProp(property) => {
                 self.upsert_immutable_string(
@@ -511,15 +989,840 @@ impl GraphMutationApi for GraphMutationManager {
                     property_name,
                     property.prop,
                 )
-                .await?;
+                .await
+            }
+            Property::MutableStrProp(property) => {
+                self.upsert_mutable_property(
+                    tenant_id,
+                    uid,
+                    property_name,
+                    property.prop,
+                    MUT_STRING_TABLE_NAME,
+                )
+                .await
+            }
+            Property::MutableF64Prop(property) => {
+                self.upsert_mutable_property(
+                    tenant_id,
+                    uid,
+                    property_name,
+                    property.prop,
+                    MUT_F_64_TABLE_NAME,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Removes a property from a node, along with its index entry if it's
+    /// indexed. Clearing a property that isn't set does nothing.
+    async fn clear_stored_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        property_type: PropertyType,
+    ) -> Result<(), GraphMutationManagerError> {
+        match property_type {
+            PropertyType::ImmutableString => {
+                self.delete_indexed_property::<String>(
+                    tenant_id,
+                    uid,
+                    node_type,
+                    property_name,
+                    IMM_STRING_TABLE_NAME,
+                    IMM_STRING_INDEX_TABLE_NAME,
+                )
+                .await?
+            }
+            PropertyType::ImmutableI64 => {
+                self.delete_indexed_property::<i64>(
+                    tenant_id,
+                    uid,
+                    node_type,
+                    property_name,
+                    IMM_I_64_TABLE_NAME,
+                    IMM_I_64_INDEX_TABLE_NAME,
+                )
+                .await?
+            }
+            PropertyType::ImmutableU64 => {
+                self.delete_indexed_property::<i64>(
+                    tenant_id,
+                    uid,
+                    node_type,
+                    property_name,
+                    IMM_U_64_TABLE_NAME,
+                    IMM_U_64_INDEX_TABLE_NAME,
+                )
+                .await?
             }
+            PropertyType::MaxI64 => {
+                self.delete_ordered_property(tenant_id, uid, property_name, MAX_I_64_TABLE_NAME)
+                    .await?
+            }
+            PropertyType::MinI64 => {
+                self.delete_ordered_property(tenant_id, uid, property_name, MIN_I_64_TABLE_NAME)
+                    .await?
+            }
+            PropertyType::MaxU64 => {
+                self.delete_ordered_property(tenant_id, uid, property_name, MAX_U_64_TABLE_NAME)
+                    .await?
+            }
+            PropertyType::MinU64 => {
+                self.delete_ordered_property(tenant_id, uid, property_name, MIN_U_64_TABLE_NAME)
+                    .await?
+            }
+            PropertyType::MutableString => {
+                self.delete_mutable_property(tenant_id, uid, property_name, MUT_STRING_TABLE_NAME)
+                    .await?
+            }
+            PropertyType::MutableF64 => {
+                self.delete_mutable_property(tenant_id, uid, property_name, MUT_F_64_TABLE_NAME)
+                    .await?
+            }
+        }
+
+        self.write_dropper.invalidate_property(
+            tenant_id,
+            uid,
+            node_type.clone(),
+            property_name.clone(),
+        );
+        Ok(())
+    }
+
+    /// Deletes an immutable property along with its entry in the matching
+    /// property index table. The index entry is keyed by the property's
+    /// value, so the value has to be read first.
+    async fn delete_indexed_property<V>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        table_name: &str,
+        index_table_name: &str,
+    ) -> Result<(), GraphMutationManagerError>
+    where
+        (V,): FromRow,
+        V: Value,
+    {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            SELECT value
+            FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self
+            .scylla_client
+            .execute(query, &(uid.as_i64(), &property_name.value))
+            .await?;
+        let property_value = match query_result.maybe_first_row_typed::<(V,)>()? {
+            Some((property_value,)) => property_value,
+            None => return Ok(()),
+        };
+
+        let mut batch: scylla::batch::Batch = Default::default();
+        batch.statements.reserve(2);
+        batch.append_statement(Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            "
+        )));
+        batch.append_statement(Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{index_table_name}
+            WHERE
+                node_type = ? AND
+                populated_field = ? AND
+                value = ? AND
+                uid = ?
+            "
+        )));
+        batch.set_is_idempotent(true);
+
+        self.scylla_client
+            .session
+            .batch(
+                &batch,
+                (
+                    (uid.as_i64(), &property_name.value),
+                    (
+                        &node_type.value,
+                        &property_name.value,
+                        &property_value,
+                        uid.as_i64(),
+                    ),
+                ),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes a last-write-wins property. Unlike the ordered properties, a
+    /// delete at the current time is exactly what's wanted: it replaces every
+    /// earlier write, and is replaced by every later one.
+    async fn delete_mutable_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+        table_name: &str,
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+
+        self.scylla_client
+            .execute(query, &(uid.as_i64(), &property_name.value))
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes an increment-only or decrement-only property.
+    ///
+    /// Those are written with their value as the write timestamp, so that the
+    /// greatest (or least) value wins. A delete at the current time would
+    /// shadow every value below it forever, so the delete is made at the
+    /// existing value's timestamp instead: the property keeps its ordering,
+    /// and is set again by any value that would have replaced the cleared one.
+    ///
+    /// Scylla resolves a tie between a write and a delete in favor of the
+    /// delete, so writing the cleared value again is silently lost, as is any
+    /// value that wouldn't have replaced it. The write is still reported as
+    /// `MutationRedundancy::Maybe`.
+    async fn delete_ordered_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+        table_name: &str,
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            SELECT WRITETIME(value)
+            FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self
+            .scylla_client
+            .execute(query, &(uid.as_i64(), &property_name.value))
+            .await?;
+        let write_timestamp = match query_result.maybe_first_row_typed::<(i64,)>()? {
+            Some((write_timestamp,)) => write_timestamp,
+            None => return Ok(()),
         };
 
+        let mut query = Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{table_name}
+            WHERE
+                uid = ? AND
+                populated_field = ?
+            "
+        ));
+        query.set_timestamp(Some(write_timestamp));
+        query.set_is_idempotent(true);
+
+        self.scylla_client
+            .execute(query, &(uid.as_i64(), &property_name.value))
+            .await?;
+        Ok(())
+    }
+
+    /// The names of the properties the node has in the given property table.
+    async fn populated_fields(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        table_name: &str,
+    ) -> Result<Vec<PropertyName>, GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            SELECT populated_field
+            FROM {tenant_ks}.{table_name}
+            WHERE uid = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self.scylla_client.execute(query, &(uid.as_i64(),)).await?;
+
+        let mut property_names = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(String,)>() {
+            let (populated_field,) = row?;
+            // The name was validated when it was written
+            property_names.push(PropertyName {
+                value: populated_field,
+            });
+        }
+        Ok(property_names)
+    }
+
+    /// Deletes an edge along with its reverse edge.
+    #[tracing::instrument(skip(self), err)]
+    async fn delete_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        from_uid: Uid,
+        to_uid: Uid,
+        f_edge_name: EdgeName,
+        r_edge_name: EdgeName,
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let statement = format!(
+            r"
+            DELETE FROM {tenant_ks}.{EDGES_TABLE_NAME}
+            WHERE
+                source_uid = ? AND
+                f_edge_name = ? AND
+                destination_uid = ?
+            "
+        );
+
+        let mut batch: scylla::batch::Batch = Default::default();
+        batch.statements.reserve(2);
+        batch.append_statement(Query::from(statement.clone()));
+        batch.append_statement(Query::from(statement));
+        batch.set_is_idempotent(true);
+
+        self.scylla_client
+            .session
+            .batch(
+                &batch,
+                (
+                    (from_uid.as_i64(), &f_edge_name.value, to_uid.as_i64()),
+                    (to_uid.as_i64(), &r_edge_name.value, from_uid.as_i64()),
+                ),
+            )
+            .await?;
+
+        self.write_dropper.invalidate_edges(
+            tenant_id,
+            from_uid,
+            to_uid,
+            &f_edge_name,
+            &r_edge_name,
+        );
+        Ok(())
+    }
+
+    /// Deletes every edge out of the node, along with each of their reverses.
+    /// Since both directions of every edge are stored, that's every edge to or
+    /// from the node.
+    async fn delete_all_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) -> Result<(), GraphMutationManagerError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            SELECT f_edge_name, destination_uid, r_edge_name
+            FROM {tenant_ks}.{EDGES_TABLE_NAME}
+            WHERE source_uid = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self.scylla_client.execute(query, &(uid.as_i64(),)).await?;
+
+        let mut edges = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(String, i64, String)>() {
+            let (f_edge_name, destination_uid, r_edge_name) = row?;
+            let destination_uid = Uid::from_i64(destination_uid)
+                .ok_or(GraphMutationManagerError::InvalidStoredUid(destination_uid))?;
+            // The names were validated when the edge was written
+            edges.push((
+                destination_uid,
+                EdgeName { value: f_edge_name },
+                EdgeName { value: r_edge_name },
+            ));
+        }
+
+        stream::iter(edges.into_iter().map(Ok))
+            .try_for_each_concurrent(
+                MAX_CONCURRENT_BATCH_WRITES,
+                |(destination_uid, f_edge_name, r_edge_name)| {
+                    self.delete_edges(tenant_id, uid, destination_uid, f_edge_name, r_edge_name)
+                },
+            )
+            .await
+    }
+
+    /// Makes room for a new `edge_name` edge from `source_uid` to
+    /// `destination_uid`, if the edge is ToOne and the source already has one
+    /// to another node, by replacing or rejecting it as configured.
+    ///
+    /// This isn't atomic with the write that follows it, so two conflicting
+    /// edges created at the same time may both be written.
+    async fn enforce_to_one(
+        &self,
+        tenant_id: uuid::Uuid,
+        source_uid: Uid,
+        destination_uid: Uid,
+        edge_name: &EdgeName,
+        cardinality: &EdgeCardinality,
+    ) -> Result<(), GraphMutationManagerError> {
+        if *cardinality != EdgeCardinality::ToOne {
+            return Ok(());
+        }
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let mut query = Query::new(format!(
+            r"
+            SELECT destination_uid, r_edge_name
+            FROM {tenant_ks}.{EDGES_TABLE_NAME}
+            WHERE
+                source_uid = ? AND
+                f_edge_name = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self
+            .scylla_client
+            .execute(query, &(source_uid.as_i64(), &edge_name.value))
+            .await?;
+
+        for row in query_result.rows_typed_or_empty::<(i64, String)>() {
+            let (existing_uid, r_edge_name) = row?;
+            let existing_uid = Uid::from_i64(existing_uid)
+                .ok_or(GraphMutationManagerError::InvalidStoredUid(existing_uid))?;
+            if existing_uid == destination_uid {
+                continue;
+            }
+            match self.to_one_edge_policy {
+                ToOneEdgePolicy::Reject => {
+                    return Err(GraphMutationManagerError::ToOneEdgeConflict {
+                        source_uid,
+                        edge_name: edge_name.clone(),
+                        existing_uid,
+                    })
+                }
+                ToOneEdgePolicy::Replace => {
+                    self.delete_edges(
+                        tenant_id,
+                        source_uid,
+                        existing_uid,
+                        edge_name.clone(),
+                        EdgeName { value: r_edge_name },
+                    )
+                    .await?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks that a node ref to one of the batch's created nodes is in range,
+/// and, if the referring item names a node type, that it's the created node's.
+fn check_node_ref(
+    create_nodes: &[BatchCreateNode],
+    node_ref: &NodeRef,
+    node_type: Option<&NodeType>,
+) -> Result<(), GraphMutationManagerError> {
+    let index = match node_ref {
+        NodeRef::Uid(_) => return Ok(()),
+        NodeRef::CreatedNodeIndex(index) => *index,
+    };
+    let created =
+        create_nodes
+            .get(index as usize)
+            .ok_or(GraphMutationManagerError::InvalidNodeRef {
+                index,
+                created: create_nodes.len(),
+            })?;
+    match node_type {
+        Some(referenced) if *referenced != created.node_type => {
+            Err(GraphMutationManagerError::CreatedNodeTypeMismatch {
+                index,
+                created: created.node_type.clone(),
+                referenced: referenced.clone(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that none of a batch's edges would give a node a second edge of a
+/// name that the schema declares as ToOne, in either direction. Each edge would
+/// pass `enforce_to_one` before the other was written, so both would be
+/// written.
+fn check_to_one_edges(
+    create_edges: &[BatchCreateEdge],
+    edge_schemas: &[GetEdgeSchemaResponse],
+) -> Result<(), GraphMutationManagerError> {
+    let mut to_one_edges: HashMap<(&NodeRef, &EdgeName), &NodeRef> = HashMap::new();
+    for (create_edge, edge_schema) in create_edges.iter().zip(edge_schemas) {
+        for (node, edge_name, destination, cardinality) in [
+            (
+                &create_edge.from,
+                &create_edge.edge_name,
+                &create_edge.to,
+                &edge_schema.cardinality,
+            ),
+            (
+                &create_edge.to,
+                &edge_schema.reverse_edge_name,
+                &create_edge.from,
+                &edge_schema.reverse_cardinality,
+            ),
+        ] {
+            if *cardinality != EdgeCardinality::ToOne {
+                continue;
+            }
+            match to_one_edges.entry((node, edge_name)) {
+                // The same edge twice isn't a conflict
+                Entry::Occupied(entry) if *entry.get() != destination => {
+                    return Err(GraphMutationManagerError::ConflictingToOneEdges {
+                        node: node.clone(),
+                        edge_name: edge_name.clone(),
+                    })
+                }
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(destination);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolves an already checked node ref to a uid.
+fn resolve_node_ref(created_uids: &[Uid], node_ref: &NodeRef) -> Uid {
+    match node_ref {
+        NodeRef::Uid(uid) => *uid,
+        NodeRef::CreatedNodeIndex(index) => created_uids[*index as usize],
+    }
+}
+
+/// A statement to execute as part of a batch, along with its values.
+struct BatchStatement {
+    statement: String,
+    values: SerializedValues,
+}
+
+impl BatchStatement {
+    fn new(statement: String, values: impl ValueList) -> Result<Self, SerializeValuesError> {
+        Ok(Self {
+            statement,
+            values: values.serialized()?.into_owned(),
+        })
+    }
+}
+
+/// A MutateGraph property update waiting to be written in a batch.
+struct PendingProperty {
+    uid: Uid,
+    node_type: NodeType,
+    property_name: PropertyName,
+    write: Option<PropertyWrite>,
+    statements: Vec<BatchStatement>,
+}
+
+/// A MutateGraph edge waiting to be written in a batch, along with its
+/// schema.
+struct PendingEdge {
+    from_uid: Uid,
+    to_uid: Uid,
+    f_edge_name: EdgeName,
+    edge_schema: GetEdgeSchemaResponse,
+    statements: Vec<BatchStatement>,
+}
+
+/// The statements that write a property, along with how the write dropper
+/// may drop the write if it's of a type that can be dropped. These match the
+/// writes of `upsert_property`.
+fn property_statements(
+    tenant_ks: &str,
+    uid: Uid,
+    node_type: &NodeType,
+    property_name: &PropertyName,
+    property: Property,
+) -> Result<(Option<PropertyWrite>, Vec<BatchStatement>), SerializeValuesError> {
+    let uid = uid.as_i64();
+    let property_name = property_name.value.as_str();
+    // Ordered properties use their value as their write timestamp, so that the
+    // greatest (or least) value written wins
+    let ordered = |table_name: &str, value: i64, timestamp: i64| {
+        BatchStatement::new(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{table_name}
+                (uid, populated_field, value)
+                VALUES (?, ?, ?)
+                USING TIMESTAMP ?
+                "
+            ),
+            (uid, property_name, value, timestamp),
+        )
+    };
+    Ok(match property {
+        Property::IncrementOnlyUintProp(property) => {
+            let value = property.prop as i64;
+            (
+                Some(PropertyWrite::MaxU64(property.prop)),
+                vec![ordered(MAX_U_64_TABLE_NAME, value, value)?],
+            )
+        }
+        Property::DecrementOnlyUintProp(property) => {
+            let value = property.prop as i64;
+            (
+                Some(PropertyWrite::MinU64(property.prop)),
+                vec![ordered(MIN_U_64_TABLE_NAME, value, -value)?],
+            )
+        }
+        Property::ImmutableUintProp(property) => (
+            Some(PropertyWrite::ImmU64),
+            indexed_property_statements(
+                tenant_ks,
+                uid,
+                node_type,
+                property_name,
+                property.prop as i64,
+                IMM_U_64_TABLE_NAME,
+                IMM_U_64_INDEX_TABLE_NAME,
+            )?,
+        ),
+        Property::IncrementOnlyIntProp(property) => (
+            Some(PropertyWrite::MaxI64(property.prop)),
+            vec![ordered(MAX_I_64_TABLE_NAME, property.prop, property.prop)?],
+        ),
+        Property::DecrementOnlyIntProp(property) => (
+            Some(PropertyWrite::MinI64(property.prop)),
+            vec![ordered(MIN_I_64_TABLE_NAME, property.prop, -property.prop)?],
+        ),
+        Property::ImmutableIntProp(property) => (
+            Some(PropertyWrite::ImmI64),
+            indexed_property_statements(
+                tenant_ks,
+                uid,
+                node_type,
+                property_name,
+                property.prop,
+                IMM_I_64_TABLE_NAME,
+                IMM_I_64_INDEX_TABLE_NAME,
+            )?,
+        ),
+        Property::ImmutableStrProp(property) => (
+            Some(PropertyWrite::ImmString),
+            indexed_property_statements(
+                tenant_ks,
+                uid,
+                node_type,
+                property_name,
+                property.prop,
+                IMM_STRING_TABLE_NAME,
+                IMM_STRING_INDEX_TABLE_NAME,
+            )?,
+        ),
+        Property::MutableStrProp(property) => (
+            None,
+            vec![mutable_property_statement(
+                tenant_ks,
+                uid,
+                property_name,
+                property.prop,
+                MUT_STRING_TABLE_NAME,
+            )?],
+        ),
+        Property::MutableF64Prop(property) => (
+            None,
+            vec![mutable_property_statement(
+                tenant_ks,
+                uid,
+                property_name,
+                property.prop,
+                MUT_F_64_TABLE_NAME,
+            )?],
+        ),
+    })
+}
+
+/// The statements that write an immutable property and its index entry.
+fn indexed_property_statements<V: Value + Clone>(
+    tenant_ks: &str,
+    uid: i64,
+    node_type: &NodeType,
+    property_name: &str,
+    value: V,
+    table_name: &str,
+    index_table_name: &str,
+) -> Result<Vec<BatchStatement>, SerializeValuesError> {
+    Ok(vec![
+        BatchStatement::new(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{table_name}
+                (uid, populated_field, value)
+                VALUES (?, ?, ?)
+                "
+            ),
+            (uid, property_name, value.clone()),
+        )?,
+        BatchStatement::new(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{index_table_name}
+                (node_type, populated_field, value, uid)
+                VALUES (?, ?, ?, ?)
+                "
+            ),
+            (node_type.value.as_str(), property_name, value, uid),
+        )?,
+    ])
+}
+
+/// The statement that writes a mutable property.
+fn mutable_property_statement<V: Value>(
+    tenant_ks: &str,
+    uid: i64,
+    property_name: &str,
+    value: V,
+    table_name: &str,
+) -> Result<BatchStatement, SerializeValuesError> {
+    BatchStatement::new(
+        format!(
+            r"
+            INSERT INTO {tenant_ks}.{table_name}
+            (uid, populated_field, value)
+            VALUES (?, ?, ?)
+            "
+        ),
+        (uid, property_name, value),
+    )
+}
+
+/// The statements that write an edge and its reverse.
+fn edge_statements(
+    tenant_ks: &str,
+    from_uid: Uid,
+    to_uid: Uid,
+    f_edge_name: &EdgeName,
+    r_edge_name: &EdgeName,
+) -> Result<Vec<BatchStatement>, SerializeValuesError> {
+    let statement = format!(
+        r"
+        INSERT INTO {tenant_ks}.{EDGES_TABLE_NAME} (
+            source_uid,
+            destination_uid,
+            f_edge_name,
+            r_edge_name
+        )
+        VALUES (?, ?, ?, ?)
+        ",
+    );
+    Ok(vec![
+        BatchStatement::new(
+            statement.clone(),
+            (
+                from_uid.as_i64(),
+                to_uid.as_i64(),
+                &f_edge_name.value,
+                &r_edge_name.value,
+            ),
+        )?,
+        BatchStatement::new(
+            statement,
+            (
+                to_uid.as_i64(),
+                from_uid.as_i64(),
+                &r_edge_name.value,
+                &f_edge_name.value,
+            ),
+        )?,
+    ])
+}
+
+/// The schema property type that a written property has to be declared as.
+fn property_type_of(property: &Property) -> PropertyType {
+    match property {
+        Property::IncrementOnlyUintProp(_) => PropertyType::MaxU64,
+        Property::DecrementOnlyUintProp(_) => PropertyType::MinU64,
+        Property::ImmutableUintProp(_) => PropertyType::ImmutableU64,
+        Property::IncrementOnlyIntProp(_) => PropertyType::MaxI64,
+        Property::DecrementOnlyIntProp(_) => PropertyType::MinI64,
+        Property::ImmutableIntProp(_) => PropertyType::ImmutableI64,
+        Property::ImmutableStrProp(_) => PropertyType::ImmutableString,
+        Property::MutableStrProp(_) => PropertyType::MutableString,
+        Property::MutableF64Prop(_) => PropertyType::MutableF64,
+    }
+}
+
+#[async_trait::async_trait]
+impl GraphMutationApi for GraphMutationManager {
+    type Error = GraphMutationManagerError;
+
+    /// Create Node allocates a new node in the graph, returning the uid of the new node.
+    #[tracing::instrument(skip(self), err)]
+    async fn create_node(
+        &self,
+        request: CreateNodeRequest,
+    ) -> Result<CreateNodeResponse, Self::Error> {
+        let uid = self
+            .uid_allocator_client
+            .allocate_id(request.tenant_id)
+            .await?;
+        let uid = Uid::from_u64(uid).ok_or_else(|| GraphMutationManagerError::ZeroUid)?;
+
+        self.set_node_type(request.tenant_id, uid, request.node_type)
+            .await?;
+
+        Ok(CreateNodeResponse { uid })
+    }
+
+    /// SetNodeProperty will update the property of the node with the given uid.
+    /// If the node does not exist it will be created.
+    #[tracing::instrument(skip(self), err)]
+    async fn set_node_property(
+        &self,
+        request: SetNodePropertyRequest,
+    ) -> Result<SetNodePropertyResponse, Self::Error> {
+        let SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+            property,
+        } = request;
+
+        self.validate_property(tenant_id, &node_type, &property_name, &property.property)
+            .await?;
+        let mutation_redundancy = self
+            .upsert_property(tenant_id, uid, node_type, property_name, property.property)
+            .await?;
+
         Ok(SetNodePropertyResponse {
-            // todo: At this point we can't tell if the update was redundant
-            //       but it is always safe (albeit suboptimal) to assume that
-            //       it was not.
-            mutation_redundancy: MutationRedundancy::Maybe,
+            mutation_redundancy,
         })
     }
 
@@ -538,19 +1841,279 @@ impl GraphMutationApi for GraphMutationManager {
             source_node_type,
         } = request;
 
-        let reverse_edge_name = self
+        let edge_schema = self
             .reverse_edge_resolver
-            .resolve_reverse_edge(tenant_id, source_node_type.clone(), edge_name.clone())
+            .resolve_edge_schema(tenant_id, source_node_type.clone(), edge_name.clone())
             .await?;
 
-        self.upsert_edges(tenant_id, from_uid, to_uid, edge_name, reverse_edge_name)
+        let mutation_redundancy = self
+            .upsert_edges(tenant_id, from_uid, to_uid, edge_name, edge_schema)
             .await?;
 
         Ok(CreateEdgeResponse {
-            // todo: At this point we don't track if the update was redundant
-            //       but it is always safe (albeit suboptimal) to assume that
-            //       it was not.
-            mutation_redundancy: MutationRedundancy::Maybe,
+            mutation_redundancy,
+        })
+    }
+
+    /// DeleteNode deletes a node, along with all of its properties and every
+    /// edge to or from it. The node's type is deleted last, so that a delete
+    /// that fails part way through can be retried.
+    ///
+    /// The request's node type has to match the stored one, since it keys the
+    /// property index entries that are deleted along with the properties.
+    #[tracing::instrument(skip(self), err)]
+    async fn delete_node(
+        &self,
+        request: DeleteNodeRequest,
+    ) -> Result<DeleteNodeResponse, Self::Error> {
+        let DeleteNodeRequest {
+            tenant_id,
+            uid,
+            node_type,
+        } = request;
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = Query::new(format!(
+            r"
+            SELECT node_type
+            FROM {tenant_ks}.{NODE_TYPE_TABLE_NAME}
+            WHERE uid = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        let query_result = self.scylla_client.execute(query, &(uid.as_i64(),)).await?;
+        match query_result.maybe_first_row_typed::<(String,)>()? {
+            // Either the node never existed, or it's already been deleted
+            None => return Ok(DeleteNodeResponse {}),
+            Some((stored,)) if stored != node_type.value => {
+                return Err(GraphMutationManagerError::NodeTypeMismatch {
+                    uid,
+                    stored,
+                    requested: node_type,
+                })
+            }
+            Some(_) => {}
+        }
+
+        for (property_type, table_name) in [
+            (PropertyType::ImmutableString, IMM_STRING_TABLE_NAME),
+            (PropertyType::ImmutableI64, IMM_I_64_TABLE_NAME),
+            (PropertyType::MaxI64, MAX_I_64_TABLE_NAME),
+            (PropertyType::MinI64, MIN_I_64_TABLE_NAME),
+            (PropertyType::ImmutableU64, IMM_U_64_TABLE_NAME),
+            (PropertyType::MaxU64, MAX_U_64_TABLE_NAME),
+            (PropertyType::MinU64, MIN_U_64_TABLE_NAME),
+            (PropertyType::MutableString, MUT_STRING_TABLE_NAME),
+            (PropertyType::MutableF64, MUT_F_64_TABLE_NAME),
+        ] {
+            let property_names = self.populated_fields(tenant_id, uid, table_name).await?;
+            stream::iter(property_names.into_iter().map(Ok))
+                .try_for_each_concurrent(MAX_CONCURRENT_BATCH_WRITES, |property_name| {
+                    let node_type = &node_type;
+                    async move {
+                        self.clear_stored_property(
+                            tenant_id,
+                            uid,
+                            node_type,
+                            &property_name,
+                            property_type,
+                        )
+                        .await
+                    }
+                })
+                .await?;
+        }
+
+        self.delete_all_edges(tenant_id, uid).await?;
+
+        let mut query = Query::new(format!(
+            r"
+            DELETE FROM {tenant_ks}.{NODE_TYPE_TABLE_NAME}
+            WHERE uid = ?
+            "
+        ));
+        query.set_is_idempotent(true);
+        self.scylla_client.execute(query, &(uid.as_i64(),)).await?;
+        self.write_dropper.invalidate_node_type(tenant_id, uid);
+
+        Ok(DeleteNodeResponse {})
+    }
+
+    /// DeleteEdge deletes an edge, along with its reverse edge.
+    #[tracing::instrument(skip(self), err)]
+    async fn delete_edge(
+        &self,
+        request: DeleteEdgeRequest,
+    ) -> Result<DeleteEdgeResponse, Self::Error> {
+        let DeleteEdgeRequest {
+            edge_name,
+            tenant_id,
+            from_uid,
+            to_uid,
+            source_node_type,
+        } = request;
+
+        let reverse_edge_name = self
+            .reverse_edge_resolver
+            .resolve_reverse_edge(tenant_id, source_node_type, edge_name.clone())
+            .await?;
+
+        self.delete_edges(tenant_id, from_uid, to_uid, edge_name, reverse_edge_name)
+            .await?;
+
+        Ok(DeleteEdgeResponse {})
+    }
+
+    /// ClearProperty removes a property from a node. The property's declared
+    /// type says which table it's stored in.
+    #[tracing::instrument(skip(self), err)]
+    async fn clear_property(
+        &self,
+        request: ClearPropertyRequest,
+    ) -> Result<ClearPropertyResponse, Self::Error> {
+        let ClearPropertyRequest {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        } = request;
+
+        let property_type = self
+            .property_schema_resolver
+            .resolve_property_type(tenant_id, node_type.clone(), property_name.clone())
+            .await?;
+
+        self.clear_stored_property(tenant_id, uid, &node_type, &property_name, property_type)
+            .await?;
+
+        Ok(ClearPropertyResponse {})
+    }
+
+    /// MutateGraph applies a batch of node creations, property updates and
+    /// edge creations, which may refer to the nodes the batch creates.
+    ///
+    /// The whole batch is validated before anything is written, so an invalid
+    /// batch writes nothing. The writes themselves aren't atomic though: if one
+    /// fails, the others may still have been made, and the batch should be
+    /// retried.
+    #[tracing::instrument(
+        skip(self, request),
+        fields(
+            tenant_id = %request.tenant_id,
+            create_nodes = request.create_nodes.len(),
+            set_node_properties = request.set_node_properties.len(),
+            create_edges = request.create_edges.len(),
+        ),
+        err
+    )]
+    async fn mutate_graph(
+        &self,
+        request: MutateGraphRequest,
+    ) -> Result<MutateGraphResponse, Self::Error> {
+        let MutateGraphRequest {
+            tenant_id,
+            create_nodes,
+            set_node_properties,
+            create_edges,
+        } = request;
+
+        let count = create_nodes.len() + set_node_properties.len() + create_edges.len();
+        if count > MAX_MUTATIONS_PER_REQUEST {
+            return Err(GraphMutationManagerError::TooManyMutations {
+                count,
+                max: MAX_MUTATIONS_PER_REQUEST,
+            });
+        }
+
+        for set_node_property in set_node_properties.iter() {
+            check_node_ref(
+                &create_nodes,
+                &set_node_property.node,
+                Some(&set_node_property.node_type),
+            )?;
+        }
+        for create_edge in create_edges.iter() {
+            check_node_ref(
+                &create_nodes,
+                &create_edge.from,
+                Some(&create_edge.source_node_type),
+            )?;
+            check_node_ref(&create_nodes, &create_edge.to, None)?;
+        }
+
+        // The futures are collected up front, since a stream holding the
+        // iterator and its closure across the await isn't provably Send
+        let validations: Vec<_> = set_node_properties
+            .iter()
+            .map(|set_node_property| {
+                self.validate_property(
+                    tenant_id,
+                    &set_node_property.node_type,
+                    &set_node_property.property_name,
+                    &set_node_property.property.property,
+                )
+            })
+            .collect();
+        stream::iter(validations)
+            .buffer_unordered(MAX_CONCURRENT_BATCH_WRITES)
+            .try_collect::<Vec<()>>()
+            .await?;
+        let edge_schema_lookups: Vec<_> = create_edges
+            .iter()
+            .map(|create_edge| {
+                self.reverse_edge_resolver.resolve_edge_schema(
+                    tenant_id,
+                    create_edge.source_node_type.clone(),
+                    create_edge.edge_name.clone(),
+                )
+            })
+            .collect();
+        let edge_schemas: Vec<GetEdgeSchemaResponse> = stream::iter(edge_schema_lookups)
+            .buffered(MAX_CONCURRENT_BATCH_WRITES)
+            .try_collect()
+            .await?;
+        check_to_one_edges(&create_edges, &edge_schemas)?;
+
+        let mut created_uids = Vec::with_capacity(create_nodes.len());
+        for _ in create_nodes.iter() {
+            let uid = self.uid_allocator_client.allocate_id(tenant_id).await?;
+            created_uids.push(Uid::from_u64(uid).ok_or(GraphMutationManagerError::ZeroUid)?);
+        }
+        self.insert_node_types(tenant_id, &created_uids, &create_nodes)
+            .await?;
+
+        let set_node_properties = set_node_properties
+            .into_iter()
+            .map(|set_node_property| {
+                (
+                    resolve_node_ref(&created_uids, &set_node_property.node),
+                    set_node_property.node_type,
+                    set_node_property.property_name,
+                    set_node_property.property.property,
+                )
+            })
+            .collect();
+        let create_edges = create_edges
+            .into_iter()
+            .zip(edge_schemas)
+            .map(|(create_edge, edge_schema)| {
+                (
+                    resolve_node_ref(&created_uids, &create_edge.from),
+                    resolve_node_ref(&created_uids, &create_edge.to),
+                    create_edge.edge_name,
+                    edge_schema,
+                )
+            })
+            .collect();
+        let (set_node_property_redundancies, create_edge_redundancies) = futures::try_join!(
+            self.batch_upsert_properties(tenant_id, set_node_properties),
+            self.batch_upsert_edges(tenant_id, create_edges),
+        )?;
+
+        Ok(MutateGraphResponse {
+            created_uids,
+            set_node_property_redundancies,
+            create_edge_redundancies,
         })
     }
 }
@@ -1,5 +1,5 @@
 pub mod config;
 pub mod graph_mutation;
+pub mod property_schema_resolver;
 pub mod reverse_edge_resolver;
-pub mod table_names;
 pub mod write_dropper;
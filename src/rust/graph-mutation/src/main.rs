@@ -8,6 +8,7 @@ use clap::Parser;
 use graph_mutation::{
     config::GraphMutationServiceConfig,
     graph_mutation::GraphMutationManager,
+    property_schema_resolver::PropertySchemaResolver,
     reverse_edge_resolver::ReverseEdgeResolver,
 };
 use rust_proto::{
@@ -31,13 +32,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _guard = grapl_tracing::setup_tracing(SERVICE_NAME);
 
     let config = GraphMutationServiceConfig::parse();
-    let mut scylla_config = scylla::SessionConfig::new();
-    scylla_config.add_known_nodes_addr(&config.graph_db_config.graph_db_addresses[..]);
-    scylla_config.auth_username = Some(config.graph_db_config.graph_db_auth_username.to_owned());
-    scylla_config.auth_password = Some(config.graph_db_config.graph_db_auth_password.to_owned());
-
     let scylla_client = Arc::new(CachingSession::from(
-        scylla::Session::connect(scylla_config).await?,
+        config.graph_db_config.connect().await?,
         10_000,
     ));
     let graph_schema_manager_client =
@@ -49,7 +45,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let graph_mutation_service = GraphMutationManager::new(
         scylla_client,
         uid_allocator_client,
-        ReverseEdgeResolver::new(graph_schema_manager_client, 10_000),
+        ReverseEdgeResolver::new(
+            graph_schema_manager_client.clone(),
+            10_000,
+            Duration::from_millis(config.edge_schema_cache_ttl_ms),
+        ),
+        PropertySchemaResolver::new(
+            graph_schema_manager_client,
+            10_000,
+            Duration::from_millis(config.property_schema_cache_ttl_ms),
+        ),
+        config.to_one_edge_policy,
         1_000_000,
     );
     exec_service(config.graph_mutation_bind_address, graph_mutation_service).await
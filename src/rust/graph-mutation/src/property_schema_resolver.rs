@@ -0,0 +1,107 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use rust_proto::{
+    graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::{
+            client::{
+                GraphSchemaManagerClient,
+                GraphSchemaManagerClientError,
+            },
+            messages::{
+                GetPropertySchemaRequest,
+                PropertyType,
+            },
+        },
+        common::v1beta1::types::{
+            NodeType,
+            PropertyName,
+        },
+    },
+    protocol::status::Code,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum PropertySchemaResolverError {
+    #[error("couldn't resolve property schema from Graph Schema Manager: {0}")]
+    GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
+    #[error("property '{property_name}' is not declared on node type '{node_type}'")]
+    UndeclaredProperty {
+        node_type: NodeType,
+        property_name: PropertyName,
+    },
+}
+
+/// Looks up the declared type of a node type's property in the tenant's
+/// deployed schema.
+///
+/// A new version of a schema may redeclare a property with another type, so
+/// lookups are only cached for `time_to_live`, after which writes are
+/// validated against whichever version is deployed by then. Undeclared
+/// properties aren't cached, so that they're picked up as soon as a schema
+/// declaring them is deployed.
+#[derive(Clone)]
+pub struct PropertySchemaResolver {
+    schema_client: GraphSchemaManagerClient,
+    property_type_cache:
+        dashmap::DashMap<(uuid::Uuid, NodeType, PropertyName), (PropertyType, Instant)>,
+    time_to_live: Duration,
+}
+
+impl PropertySchemaResolver {
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        cache_size: usize,
+        time_to_live: Duration,
+    ) -> Self {
+        let property_type_cache = dashmap::DashMap::with_capacity(cache_size);
+        Self {
+            schema_client,
+            property_type_cache,
+            time_to_live,
+        }
+    }
+
+    pub async fn resolve_property_type(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+        property_name: PropertyName,
+    ) -> Result<PropertyType, PropertySchemaResolverError> {
+        let key = (tenant_id, node_type, property_name);
+        if let Some(entry) = self.property_type_cache.get(&key) {
+            let (property_type, cached_at) = *entry;
+            if cached_at.elapsed() < self.time_to_live {
+                return Ok(property_type);
+            }
+        }
+
+        let (tenant_id, node_type, property_name) = key.clone();
+        let mut schema_client = self.schema_client.clone();
+        let response = match schema_client
+            .get_property_schema(GetPropertySchemaRequest {
+                tenant_id,
+                node_type: node_type.clone(),
+                property_name: property_name.clone(),
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                return Err(PropertySchemaResolverError::UndeclaredProperty {
+                    node_type,
+                    property_name,
+                })
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.property_type_cache
+            .insert(key, (response.property_type, Instant::now()));
+        Ok(response.property_type)
+    }
+}
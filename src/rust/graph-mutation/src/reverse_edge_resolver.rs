@@ -1,38 +1,65 @@
-use rust_proto::graplinc::grapl::{
-    api::graph_schema_manager::v1beta1::{
-        client::{
-            GraphSchemaManagerClient,
-            GraphSchemaManagerClientError,
+use std::time::Duration;
+
+use moka::future::{
+    Cache,
+    CacheBuilder,
+};
+use rust_proto::{
+    graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::{
+            client::{
+                GraphSchemaManagerClient,
+                GraphSchemaManagerClientError,
+            },
+            messages::{
+                GetEdgeSchemaRequest,
+                GetEdgeSchemaResponse,
+            },
         },
-        messages::{
-            GetEdgeSchemaRequest,
-            GetEdgeSchemaResponse,
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
         },
     },
-    common::v1beta1::types::{
-        EdgeName,
-        NodeType,
-    },
+    protocol::status::Code,
 };
 
 #[derive(thiserror::Error, Debug)]
 pub enum ReverseEdgeResolverError {
     #[error("couldn't resolve reverse edge from Graph Schema Manager: {0}")]
     GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
+    #[error("edge '{edge_name}' is not declared on node type '{node_type}'")]
+    UndeclaredEdge {
+        node_type: NodeType,
+        edge_name: EdgeName,
+    },
 }
 
+/// Looks up an edge's reverse, and the cardinality of each direction, in the
+/// tenant's deployed schema.
+///
+/// A new version of a schema may redeclare an edge with another cardinality,
+/// so lookups are only cached for `time_to_live`, after which ToOne edges are
+/// enforced against whichever version is deployed by then. Undeclared edges
+/// aren't cached, so that they're picked up as soon as a schema declaring them
+/// is deployed.
 #[derive(Clone)]
 pub struct ReverseEdgeResolver {
     schema_client: GraphSchemaManagerClient,
-    r_edge_cache: dashmap::DashMap<(uuid::Uuid, EdgeName, NodeType), GetEdgeSchemaResponse>,
+    r_edge_cache: Cache<(uuid::Uuid, EdgeName, NodeType), GetEdgeSchemaResponse>,
 }
 
 impl ReverseEdgeResolver {
-    pub fn new(schema_client: GraphSchemaManagerClient, cache_size: usize) -> Self {
-        let r_edge_cache = dashmap::DashMap::with_capacity(cache_size);
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        cache_size: u64,
+        time_to_live: Duration,
+    ) -> Self {
         Self {
             schema_client,
-            r_edge_cache,
+            r_edge_cache: CacheBuilder::new(cache_size)
+                .time_to_live(time_to_live)
+                .build(),
         }
     }
 
@@ -42,27 +69,48 @@ impl ReverseEdgeResolver {
         node_type: NodeType,
         edge_name: EdgeName,
     ) -> Result<EdgeName, ReverseEdgeResolverError> {
-        match self
-            .r_edge_cache
-            .entry((tenant_id, edge_name.clone(), node_type.clone()))
+        let edge_schema = self
+            .resolve_edge_schema(tenant_id, node_type, edge_name)
+            .await?;
+        Ok(edge_schema.reverse_edge_name)
+    }
+
+    /// Resolves the edge's reverse along with the cardinality of each
+    /// direction.
+    pub async fn resolve_edge_schema(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+        edge_name: EdgeName,
+    ) -> Result<GetEdgeSchemaResponse, ReverseEdgeResolverError> {
+        let key = (tenant_id, edge_name, node_type);
+        if let Some(response) = self.r_edge_cache.get(&key) {
+            return Ok(response);
+        }
+
+        let (tenant_id, edge_name, node_type) = key.clone();
+        let mut schema_client = self.schema_client.clone();
+        let response = match schema_client
+            .get_edge_schema(GetEdgeSchemaRequest {
+                tenant_id,
+                node_type: node_type.clone(),
+                edge_name: edge_name.clone(),
+            })
+            .await
         {
-            dashmap::mapref::entry::Entry::Occupied(entry) => {
-                Ok(entry.get().reverse_edge_name.clone())
+            Ok(response) => response,
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                return Err(ReverseEdgeResolverError::UndeclaredEdge {
+                    node_type,
+                    edge_name,
+                })
             }
-            dashmap::mapref::entry::Entry::Vacant(entry) => {
-                let mut schema_client = self.schema_client.clone();
-                let response = schema_client
-                    .get_edge_schema(GetEdgeSchemaRequest {
-                        tenant_id,
-                        node_type: node_type.clone(),
-                        edge_name: edge_name.clone(),
-                    })
-                    .await?;
+            Err(e) => return Err(e.into()),
+        };
 
-                let reverse_name = response.reverse_edge_name.clone();
-                entry.insert(response);
-                Ok(reverse_name)
-            }
-        }
+        self.r_edge_cache.insert(key, response.clone()).await;
+        Ok(response)
     }
 }
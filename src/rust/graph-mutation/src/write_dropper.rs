@@ -5,12 +5,14 @@ use blake2::{
     Blake2b,
     Digest,
 };
-use dashmap::mapref::entry::Entry;
-use rust_proto::graplinc::grapl::common::v1beta1::types::{
-    EdgeName,
-    NodeType,
-    PropertyName,
-    Uid,
+use rust_proto::graplinc::grapl::{
+    api::graph_mutation::v1beta1::messages::MutationRedundancy,
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        PropertyName,
+        Uid,
+    },
 };
 
 type Blake2b16 = Blake2b<U16>;
@@ -36,6 +38,7 @@ struct NodeTypeKey {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct PropertyKey {
     tenant_id: uuid::Uuid,
+    uid: Uid,
     node_type: NodeType,
     property_name: PropertyName,
 }
@@ -55,6 +58,30 @@ fn edge_key(
     hasher.finalize().into()
 }
 
+/// A property write that the write dropper may drop, along with the value
+/// written for properties that only ever grow or shrink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyWrite {
+    MaxI64(i64),
+    MinI64(i64),
+    ImmI64,
+    MaxU64(u64),
+    MinU64(u64),
+    ImmU64,
+    ImmString,
+}
+
+/// Drops writes that are known to be redundant with ones this instance has
+/// already made, reporting `MutationRedundancy::True` for those. Every other
+/// write is made, and reported as `MutationRedundancy::Maybe`, since another
+/// instance may have already made it.
+///
+/// Deletes only invalidate the instance that makes them. Another instance that
+/// made a write before the delete will go on dropping it as redundant, so the
+/// data stays deleted. Deleting data therefore needs a single writer: the
+/// deletes, and every write that may follow them, have to go through the same
+/// instance. nomad/grapl-graph-db.nomad runs exactly one graph-mutation
+/// instance for this reason, and replaces it without overlapping the two.
 pub struct WriteDropper {
     max_i64: dashmap::DashMap<PropertyKey, i64>,
     min_i64: dashmap::DashMap<PropertyKey, i64>,
@@ -87,196 +114,150 @@ impl WriteDropper {
     pub async fn check_max_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
-        match self.max_i64.entry(PropertyKey {
+        let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
-        }) {
-            Entry::Vacant(entry) => {
-                callback().await?;
-                handle_full!(self, max_i64);
-                entry.insert(value);
-            }
-            Entry::Occupied(mut entry) => {
-                if value > *entry.get() {
-                    callback().await?;
-                    handle_full!(self, max_i64);
-                    entry.insert(value);
-                }
-            }
-        }
-        Ok(())
+        };
+        self.check_property(key, PropertyWrite::MaxI64(value), callback)
+            .await
     }
 
     pub async fn check_min_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: i64,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
-        match self.min_i64.entry(PropertyKey {
+        let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
-        }) {
-            Entry::Vacant(entry) => {
-                callback().await?;
-                handle_full!(self, min_i64);
-                entry.insert(value);
-            }
-            Entry::Occupied(mut entry) => {
-                if value < *entry.get() {
-                    callback().await?;
-                    handle_full!(self, min_i64);
-                    entry.insert(value);
-                }
-            }
-        }
-        Ok(())
+        };
+        self.check_property(key, PropertyWrite::MinI64(value), callback)
+            .await
     }
     pub async fn check_imm_i64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
-        if !self.imm_i64.contains(&key) {
-            callback().await?;
-            handle_full!(self, imm_i64);
-            self.imm_i64.insert(key);
-        }
-        Ok(())
+        self.check_property(key, PropertyWrite::ImmI64, callback)
+            .await
     }
 
     pub async fn check_max_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
-        match self.max_u64.entry(PropertyKey {
+        let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
-        }) {
-            Entry::Vacant(entry) => {
-                callback().await?;
-                handle_full!(self, max_u64);
-                entry.insert(value);
-            }
-            Entry::Occupied(mut entry) => {
-                if value > *entry.get() {
-                    callback().await?;
-                    handle_full!(self, max_u64);
-                    entry.insert(value);
-                }
-            }
-        }
-        Ok(())
+        };
+        self.check_property(key, PropertyWrite::MaxU64(value), callback)
+            .await
     }
 
     pub async fn check_min_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         value: u64,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
-        match self.min_u64.entry(PropertyKey {
+        let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
-        }) {
-            Entry::Vacant(entry) => {
-                callback().await?;
-                handle_full!(self, min_u64);
-                entry.insert(value);
-            }
-            Entry::Occupied(mut entry) => {
-                if value < *entry.get() {
-                    callback().await?;
-                    handle_full!(self, min_u64);
-                    entry.insert(value);
-                }
-            }
-        }
-        Ok(())
+        };
+        self.check_property(key, PropertyWrite::MinU64(value), callback)
+            .await
     }
     pub async fn check_imm_u64<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
-        if !self.imm_u64.contains(&key) {
-            callback().await?;
-            handle_full!(self, imm_u64);
-            self.imm_u64.insert(key);
-        }
-        Ok(())
+        self.check_property(key, PropertyWrite::ImmU64, callback)
+            .await
     }
 
     pub async fn check_imm_string<T, E, Fut>(
         &self,
         tenant_id: uuid::Uuid,
+        uid: Uid,
         node_type: NodeType,
         property_name: PropertyName,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
         let key = PropertyKey {
             tenant_id,
+            uid,
             node_type,
             property_name,
         };
-        if !self.imm_string.contains(&key) {
-            callback().await?;
-            handle_full!(self, imm_string);
-            self.imm_string.insert(key);
-        }
-        Ok(())
+        self.check_property(key, PropertyWrite::ImmString, callback)
+            .await
     }
 
     pub async fn check_node_type<T, E, Fut>(
@@ -284,17 +265,18 @@ impl WriteDropper {
         tenant_id: uuid::Uuid,
         uid: Uid,
         callback: impl FnOnce() -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
         let key = NodeTypeKey { tenant_id, uid };
-        if !self.node_type.contains(&key) {
-            callback().await?;
-            handle_full!(self, node_type);
-            self.node_type.insert(key);
+        if self.node_type.contains(&key) {
+            return Ok(MutationRedundancy::True);
         }
-        Ok(())
+        callback().await?;
+        handle_full!(self, node_type);
+        self.node_type.insert(key);
+        Ok(MutationRedundancy::Maybe)
     }
 
     pub async fn check_edges<T, E, Fut>(
@@ -305,22 +287,273 @@ impl WriteDropper {
         f_edge_name: EdgeName,
         r_edge_name: EdgeName,
         callback: impl FnOnce(EdgeName, EdgeName) -> Fut,
-    ) -> Result<(), E>
+    ) -> Result<MutationRedundancy, E>
     where
         Fut: Future<Output = Result<T, E>>,
     {
-        let fkey = edge_key(tenant_id, source_uid, dest_uid, &f_edge_name);
-
         // We always insert both the forward and reverse edges in a batch insert
-        if !self.edges.contains(&fkey) {
-            handle_full!(self, edges);
-            let rkey = edge_key(tenant_id, dest_uid, source_uid, &r_edge_name);
+        if self.is_redundant_edges_write(tenant_id, source_uid, dest_uid, &f_edge_name) {
+            return Ok(MutationRedundancy::True);
+        }
+
+        callback(f_edge_name.clone(), r_edge_name.clone()).await?;
+
+        self.record_edges_write(tenant_id, source_uid, dest_uid, &f_edge_name, &r_edge_name);
+        Ok(MutationRedundancy::Maybe)
+    }
+
+    /// Whether the property write is redundant with one this instance has
+    /// already made. Along with `record_property_write`, this is for writes
+    /// that can't be made in a `check_*` callback, e.g. because they're
+    /// batched with others.
+    pub fn is_redundant_property_write(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        write: PropertyWrite,
+    ) -> bool {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        self.is_redundant(&key, write)
+    }
 
-            callback(f_edge_name, r_edge_name).await?;
+    /// Records that a property write, checked with
+    /// `is_redundant_property_write`, has been made.
+    pub fn record_property_write(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+        write: PropertyWrite,
+    ) {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        self.record(key, write);
+    }
 
-            self.edges.insert(fkey);
-            self.edges.insert(rkey);
+    /// Whether the edge (and so its reverse) is redundant with one this
+    /// instance has already written.
+    pub fn is_redundant_edges_write(
+        &self,
+        tenant_id: uuid::Uuid,
+        source_uid: Uid,
+        dest_uid: Uid,
+        f_edge_name: &EdgeName,
+    ) -> bool {
+        self.edges
+            .contains(&edge_key(tenant_id, source_uid, dest_uid, f_edge_name))
+    }
+
+    /// Records that an edge and its reverse, checked with
+    /// `is_redundant_edges_write`, have been written.
+    pub fn record_edges_write(
+        &self,
+        tenant_id: uuid::Uuid,
+        source_uid: Uid,
+        dest_uid: Uid,
+        f_edge_name: &EdgeName,
+        r_edge_name: &EdgeName,
+    ) {
+        handle_full!(self, edges);
+        self.edges
+            .insert(edge_key(tenant_id, source_uid, dest_uid, f_edge_name));
+        self.edges
+            .insert(edge_key(tenant_id, dest_uid, source_uid, r_edge_name));
+    }
+
+    async fn check_property<T, E, Fut>(
+        &self,
+        key: PropertyKey,
+        write: PropertyWrite,
+        callback: impl FnOnce() -> Fut,
+    ) -> Result<MutationRedundancy, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if self.is_redundant(&key, write) {
+            return Ok(MutationRedundancy::True);
+        }
+        callback().await?;
+        self.record(key, write);
+        Ok(MutationRedundancy::Maybe)
+    }
+
+    fn is_redundant(&self, key: &PropertyKey, write: PropertyWrite) -> bool {
+        // No guard may outlive this check, or a concurrent write to the same
+        // shard would deadlock
+        match write {
+            PropertyWrite::MaxI64(value) => {
+                matches!(self.max_i64.get(key), Some(current) if value <= *current)
+            }
+            PropertyWrite::MinI64(value) => {
+                matches!(self.min_i64.get(key), Some(current) if value >= *current)
+            }
+            PropertyWrite::ImmI64 => self.imm_i64.contains(key),
+            PropertyWrite::MaxU64(value) => {
+                matches!(self.max_u64.get(key), Some(current) if value <= *current)
+            }
+            PropertyWrite::MinU64(value) => {
+                matches!(self.min_u64.get(key), Some(current) if value >= *current)
+            }
+            PropertyWrite::ImmU64 => self.imm_u64.contains(key),
+            PropertyWrite::ImmString => self.imm_string.contains(key),
         }
-        Ok(())
+    }
+
+    fn record(&self, key: PropertyKey, write: PropertyWrite) {
+        match write {
+            PropertyWrite::MaxI64(value) => {
+                handle_full!(self, max_i64);
+                self.max_i64
+                    .entry(key)
+                    .and_modify(|current| *current = (*current).max(value))
+                    .or_insert(value);
+            }
+            PropertyWrite::MinI64(value) => {
+                handle_full!(self, min_i64);
+                self.min_i64
+                    .entry(key)
+                    .and_modify(|current| *current = (*current).min(value))
+                    .or_insert(value);
+            }
+            PropertyWrite::ImmI64 => {
+                handle_full!(self, imm_i64);
+                self.imm_i64.insert(key);
+            }
+            PropertyWrite::MaxU64(value) => {
+                handle_full!(self, max_u64);
+                self.max_u64
+                    .entry(key)
+                    .and_modify(|current| *current = (*current).max(value))
+                    .or_insert(value);
+            }
+            PropertyWrite::MinU64(value) => {
+                handle_full!(self, min_u64);
+                self.min_u64
+                    .entry(key)
+                    .and_modify(|current| *current = (*current).min(value))
+                    .or_insert(value);
+            }
+            PropertyWrite::ImmU64 => {
+                handle_full!(self, imm_u64);
+                self.imm_u64.insert(key);
+            }
+            PropertyWrite::ImmString => {
+                handle_full!(self, imm_string);
+                self.imm_string.insert(key);
+            }
+        }
+    }
+
+    /// Forgets that the node's type was written, once it's been deleted. Only
+    /// this instance forgets it.
+    pub fn invalidate_node_type(&self, tenant_id: uuid::Uuid, uid: Uid) {
+        self.node_type.remove(&NodeTypeKey { tenant_id, uid });
+    }
+
+    /// Forgets every write of the property, whatever its type, once it's
+    /// been cleared. Only this instance forgets them.
+    pub fn invalidate_property(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        node_type: NodeType,
+        property_name: PropertyName,
+    ) {
+        let key = PropertyKey {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        };
+        self.max_i64.remove(&key);
+        self.min_i64.remove(&key);
+        self.imm_i64.remove(&key);
+        self.max_u64.remove(&key);
+        self.min_u64.remove(&key);
+        self.imm_u64.remove(&key);
+        self.imm_string.remove(&key);
+    }
+
+    /// Forgets that the edge and its reverse were written, once they've been
+    /// deleted. Only this instance forgets them.
+    pub fn invalidate_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        source_uid: Uid,
+        dest_uid: Uid,
+        f_edge_name: &EdgeName,
+        r_edge_name: &EdgeName,
+    ) {
+        self.edges
+            .remove(&edge_key(tenant_id, source_uid, dest_uid, f_edge_name));
+        self.edges
+            .remove(&edge_key(tenant_id, dest_uid, source_uid, r_edge_name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    use super::*;
+
+    async fn write_imm_string(
+        write_dropper: &WriteDropper,
+        writes: &AtomicUsize,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) {
+        write_dropper
+            .check_imm_string(
+                tenant_id,
+                uid,
+                NodeType {
+                    value: "Process".to_owned(),
+                },
+                PropertyName {
+                    value: "process_name".to_owned(),
+                },
+                || async {
+                    writes.fetch_add(1, Ordering::SeqCst);
+                    Ok::<(), ()>(())
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_property_writes_are_dropped_per_node() {
+        let write_dropper = WriteDropper::new(100);
+        let writes = AtomicUsize::new(0);
+        let tenant_id = uuid::Uuid::new_v4();
+        let first_uid = Uid::from_u64(1).unwrap();
+        let second_uid = Uid::from_u64(2).unwrap();
+
+        write_imm_string(&write_dropper, &writes, tenant_id, first_uid).await;
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+        // The same property on another node is a different write
+        write_imm_string(&write_dropper, &writes, tenant_id, second_uid).await;
+        assert_eq!(writes.load(Ordering::SeqCst), 2);
+
+        write_imm_string(&write_dropper, &writes, tenant_id, first_uid).await;
+        write_imm_string(&write_dropper, &writes, tenant_id, second_uid).await;
+        assert_eq!(writes.load(Ordering::SeqCst), 2);
     }
 }
@@ -0,0 +1,1346 @@
+#![cfg(feature = "integration_tests")]
+
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use clap::Parser;
+use graph_mutation::{
+    config::{
+        GraphDbConfig,
+        ToOneEdgePolicy,
+    },
+    graph_mutation::{
+        GraphMutationManager,
+        GraphMutationManagerError,
+    },
+    property_schema_resolver::PropertySchemaResolver,
+    reverse_edge_resolver::ReverseEdgeResolver,
+};
+use rust_proto::{
+    client_factory::services::{
+        GraphSchemaManagerClientConfig,
+        ScyllaProvisionerClientConfig,
+        UidAllocatorClientConfig,
+    },
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                ImmutableStrProp,
+                ImmutableUintProp,
+                IncrementOnlyUintProp,
+                MutableF64Prop,
+                MutableStrProp,
+                NodeProperty,
+                Property,
+            },
+            graph_mutation::v1beta1::{
+                messages as mutation,
+                server::GraphMutationApi,
+            },
+            graph_schema_manager::v1beta1::{
+                client::GraphSchemaManagerClient,
+                messages as graph_schema_manager_api,
+            },
+            scylla_provisioner::v1beta1::{
+                client::ScyllaProvisionerClient,
+                messages as scylla_provisioner_msgs,
+            },
+            uid_allocator::v1beta1::{
+                client::UidAllocatorServiceClient,
+                messages::CreateTenantKeyspaceRequest,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
+    },
+    protocol::{
+        service_client::ConnectWithConfig,
+        status::{
+            Code,
+            Status,
+        },
+    },
+};
+use scylla::{
+    frame::response::cql_to_rust::FromRow,
+    query::Query,
+    CachingSession,
+};
+use scylla_table_names::{
+    tenant_keyspace_name,
+    EDGES_TABLE_NAME,
+    IMM_STRING_INDEX_TABLE_NAME,
+    IMM_STRING_TABLE_NAME,
+    MAX_U_64_TABLE_NAME,
+    MUT_F_64_TABLE_NAME,
+    MUT_STRING_TABLE_NAME,
+    NODE_TYPE_TABLE_NAME,
+};
+use uid_allocator::client::CachingUidAllocatorServiceClient;
+
+/// Short, so that tests can wait out a redeployed schema's stale property types
+/// and edge cardinalities
+const SCHEMA_CACHE_TTL: Duration = Duration::from_secs(1);
+
+fn get_example_graphql_schema() -> Result<Bytes, std::io::Error> {
+    // This path is created in rust/Dockerfile
+    let path = "/test-fixtures/example_schemas/example.graphql";
+    std::fs::read(path).map(Bytes::from)
+}
+
+async fn provision_tenant() -> eyre::Result<uuid::Uuid> {
+    let tenant_id = uuid::Uuid::new_v4();
+
+    let mut provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(ScyllaProvisionerClientConfig::parse())
+            .await?;
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let mut uid_allocator_client =
+        UidAllocatorServiceClient::connect_with_config(UidAllocatorClientConfig::parse()).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    let mut graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+            .await?;
+    graph_schema_manager_client
+        .deploy_schema(graph_schema_manager_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+        })
+        .await?;
+
+    Ok(tenant_id)
+}
+
+async fn scylla_client() -> eyre::Result<Arc<CachingSession>> {
+    let session = GraphDbConfig::parse()
+        .connect()
+        .await
+        .map_err(|e| eyre::eyre!("failed to connect to the graph db: {e}"))?;
+    Ok(Arc::new(CachingSession::from(session, 100)))
+}
+
+/// The service only runs with one policy, so we build the managers in-process
+/// to exercise both against the same cluster.
+async fn graph_mutation_manager(
+    scylla_client: Arc<CachingSession>,
+    to_one_edge_policy: ToOneEdgePolicy,
+) -> eyre::Result<GraphMutationManager> {
+    let graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+            .await?;
+    let uid_allocator_client = CachingUidAllocatorServiceClient::from_client_config(
+        UidAllocatorClientConfig::parse(),
+        100,
+    )
+    .await?;
+    Ok(GraphMutationManager::new(
+        scylla_client,
+        uid_allocator_client,
+        ReverseEdgeResolver::new(graph_schema_manager_client.clone(), 100, SCHEMA_CACHE_TTL),
+        PropertySchemaResolver::new(graph_schema_manager_client, 100, SCHEMA_CACHE_TTL),
+        to_one_edge_policy,
+        1_000,
+    ))
+}
+
+async fn create_node(
+    manager: &GraphMutationManager,
+    tenant_id: uuid::Uuid,
+    node_type: &str,
+) -> eyre::Result<Uid> {
+    let mutation::CreateNodeResponse { uid } = manager
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: NodeType {
+                value: node_type.to_owned(),
+            },
+        })
+        .await?;
+    Ok(uid)
+}
+
+async fn create_edge(
+    manager: &GraphMutationManager,
+    tenant_id: uuid::Uuid,
+    from_uid: Uid,
+    edge_name: &str,
+    to_uid: Uid,
+    source_node_type: &str,
+) -> Result<(), GraphMutationManagerError> {
+    manager
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: EdgeName {
+                value: edge_name.to_owned(),
+            },
+            tenant_id,
+            from_uid,
+            to_uid,
+            source_node_type: NodeType {
+                value: source_node_type.to_owned(),
+            },
+        })
+        .await?;
+    Ok(())
+}
+
+async fn set_node_property(
+    manager: &GraphMutationManager,
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    node_type: &str,
+    property_name: &str,
+    property: Property,
+) -> Result<(), GraphMutationManagerError> {
+    manager
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: NodeType {
+                value: node_type.to_owned(),
+            },
+            property_name: PropertyName {
+                value: property_name.to_owned(),
+            },
+            property: NodeProperty { property },
+        })
+        .await?;
+    Ok(())
+}
+
+async fn edge_destinations(
+    scylla_client: &CachingSession,
+    tenant_id: uuid::Uuid,
+    source_uid: Uid,
+    edge_name: &str,
+) -> eyre::Result<Vec<Uid>> {
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+    let query = Query::new(format!(
+        r"
+        SELECT destination_uid
+        FROM {tenant_ks}.{EDGES_TABLE_NAME}
+        WHERE
+            source_uid = ? AND
+            f_edge_name = ?
+        "
+    ));
+    let query_result = scylla_client
+        .execute(query, &(source_uid.as_i64(), edge_name))
+        .await?;
+
+    let mut destinations = vec![];
+    for row in query_result.rows_typed_or_empty::<(i64,)>() {
+        let (destination_uid,) = row?;
+        destinations.push(Uid::from_i64(destination_uid).unwrap());
+    }
+    Ok(destinations)
+}
+
+async fn node_type(
+    scylla_client: &CachingSession,
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+) -> eyre::Result<Option<String>> {
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+    let query = Query::new(format!(
+        r"
+        SELECT node_type
+        FROM {tenant_ks}.{NODE_TYPE_TABLE_NAME}
+        WHERE uid = ?
+        "
+    ));
+    let query_result = scylla_client.execute(query, &(uid.as_i64(),)).await?;
+    Ok(query_result
+        .maybe_first_row_typed::<(String,)>()?
+        .map(|(node_type,)| node_type))
+}
+
+async fn stored_property<V>(
+    scylla_client: &CachingSession,
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    table_name: &str,
+    property_name: &str,
+) -> eyre::Result<Option<V>>
+where
+    (V,): FromRow,
+{
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+    let query = Query::new(format!(
+        r"
+        SELECT value
+        FROM {tenant_ks}.{table_name}
+        WHERE
+            uid = ? AND
+            populated_field = ?
+        "
+    ));
+    let query_result = scylla_client
+        .execute(query, &(uid.as_i64(), property_name))
+        .await?;
+    Ok(query_result
+        .maybe_first_row_typed::<(V,)>()?
+        .map(|(value,)| value))
+}
+
+async fn indexed_string_uids(
+    scylla_client: &CachingSession,
+    tenant_id: uuid::Uuid,
+    node_type: &str,
+    property_name: &str,
+    value: &str,
+) -> eyre::Result<Vec<Uid>> {
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+    let query = Query::new(format!(
+        r"
+        SELECT uid
+        FROM {tenant_ks}.{IMM_STRING_INDEX_TABLE_NAME}
+        WHERE
+            node_type = ? AND
+            populated_field = ? AND
+            value = ?
+        "
+    ));
+    let query_result = scylla_client
+        .execute(query, &(node_type, property_name, value))
+        .await?;
+
+    let mut uids = vec![];
+    for row in query_result.rows_typed_or_empty::<(i64,)>() {
+        let (uid,) = row?;
+        uids.push(Uid::from_i64(uid).unwrap());
+    }
+    Ok(uids)
+}
+
+#[test_log::test(tokio::test)]
+async fn test_to_one_edge_reject() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Reject).await?;
+
+    let first_parent = create_node(&manager, tenant_id, "Process").await?;
+    let second_parent = create_node(&manager, tenant_id, "Process").await?;
+    let child = create_node(&manager, tenant_id, "Process").await?;
+    let first_binary = create_node(&manager, tenant_id, "File").await?;
+    let second_binary = create_node(&manager, tenant_id, "File").await?;
+
+    // `children` is ToMany, but its reverse `parent` is ToOne
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "children",
+        child,
+        "Process",
+    )
+    .await?;
+    // Re-creating the same edge is not a conflict
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "children",
+        child,
+        "Process",
+    )
+    .await?;
+    let result = create_edge(
+        &manager,
+        tenant_id,
+        second_parent,
+        "children",
+        child,
+        "Process",
+    )
+    .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::ToOneEdgeConflict { .. })
+        ),
+        "expected a conflict on the reverse edge, got {result:?}"
+    );
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, child, "parent").await?,
+        vec![first_parent]
+    );
+    assert!(
+        edge_destinations(&scylla_client, tenant_id, second_parent, "children")
+            .await?
+            .is_empty()
+    );
+
+    // `binary_file` is ToOne in the forward direction
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "binary_file",
+        first_binary,
+        "Process",
+    )
+    .await?;
+    let result = create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "binary_file",
+        second_binary,
+        "Process",
+    )
+    .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::ToOneEdgeConflict { .. })
+        ),
+        "expected a conflict on the forward edge, got {result:?}"
+    );
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, first_parent, "binary_file").await?,
+        vec![first_binary]
+    );
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_to_one_edge_replace() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Replace).await?;
+
+    let first_parent = create_node(&manager, tenant_id, "Process").await?;
+    let second_parent = create_node(&manager, tenant_id, "Process").await?;
+    let child = create_node(&manager, tenant_id, "Process").await?;
+    let first_binary = create_node(&manager, tenant_id, "File").await?;
+    let second_binary = create_node(&manager, tenant_id, "File").await?;
+
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "children",
+        child,
+        "Process",
+    )
+    .await?;
+    create_edge(
+        &manager,
+        tenant_id,
+        second_parent,
+        "children",
+        child,
+        "Process",
+    )
+    .await?;
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, child, "parent").await?,
+        vec![second_parent]
+    );
+    assert!(
+        edge_destinations(&scylla_client, tenant_id, first_parent, "children")
+            .await?
+            .is_empty()
+    );
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, second_parent, "children").await?,
+        vec![child]
+    );
+
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "binary_file",
+        first_binary,
+        "Process",
+    )
+    .await?;
+    create_edge(
+        &manager,
+        tenant_id,
+        first_parent,
+        "binary_file",
+        second_binary,
+        "Process",
+    )
+    .await?;
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, first_parent, "binary_file").await?,
+        vec![second_binary]
+    );
+    assert!(edge_destinations(
+        &scylla_client,
+        tenant_id,
+        first_binary,
+        "executed_as_processes"
+    )
+    .await?
+    .is_empty());
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_redeployed_schema_is_enforced() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let manager = graph_mutation_manager(scylla_client().await?, ToOneEdgePolicy::Replace).await?;
+    let process = create_node(&manager, tenant_id, "Process").await?;
+
+    let as_string = || {
+        Property::MutableStrProp(MutableStrProp {
+            prop: "alice".to_owned(),
+        })
+    };
+    let as_float = || Property::MutableF64Prop(MutableF64Prop { prop: 1.5 });
+
+    // The example schema declares `current_user` as a mutable string, which
+    // the resolver then caches
+    set_node_property(
+        &manager,
+        tenant_id,
+        process,
+        "Process",
+        "current_user",
+        as_string(),
+    )
+    .await?;
+
+    // Then a new version of the schema redeclares it as a float
+    let schema = String::from_utf8(get_example_graphql_schema()?.to_vec())?;
+    let redeclared = "current_user: Float @mutable";
+    let schema = schema.replace("current_user: String @mutable", redeclared);
+    assert!(schema.contains(redeclared));
+    let mut graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+            .await?;
+    graph_schema_manager_client
+        .deploy_schema(graph_schema_manager_api::DeploySchemaRequest {
+            tenant_id,
+            schema: Bytes::from(schema),
+            schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
+            schema_version: 1,
+        })
+        .await?;
+
+    // Once the cached type has expired, writes are validated against the new
+    // version
+    tokio::time::sleep(SCHEMA_CACHE_TTL).await;
+    set_node_property(
+        &manager,
+        tenant_id,
+        process,
+        "Process",
+        "current_user",
+        as_float(),
+    )
+    .await?;
+    let result = set_node_property(
+        &manager,
+        tenant_id,
+        process,
+        "Process",
+        "current_user",
+        as_string(),
+    )
+    .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::PropertyTypeMismatch { .. })
+        ),
+        "expected the redeclared type to be enforced, got {result:?}"
+    );
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_redeployed_edge_cardinality_is_enforced() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Reject).await?;
+
+    let process = create_node(&manager, tenant_id, "Process").await?;
+    let first_binary = create_node(&manager, tenant_id, "File").await?;
+    let second_binary = create_node(&manager, tenant_id, "File").await?;
+    let third_binary = create_node(&manager, tenant_id, "File").await?;
+
+    let to_one = "binary_file: File! @edge";
+    let to_many = "binary_file: [File!] @edge";
+    let example_schema = String::from_utf8(get_example_graphql_schema()?.to_vec())?;
+    assert!(example_schema.contains(to_one));
+    let mut graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+            .await?;
+
+    // The example schema declares `binary_file` as ToOne, which the resolver
+    // then caches
+    create_edge(
+        &manager,
+        tenant_id,
+        process,
+        "binary_file",
+        first_binary,
+        "Process",
+    )
+    .await?;
+
+    // Then a new version of the schema redeclares it as ToMany, so once the
+    // cached cardinality has expired a second binary is no longer a conflict
+    graph_schema_manager_client
+        .deploy_schema(graph_schema_manager_api::DeploySchemaRequest {
+            tenant_id,
+            schema: Bytes::from(example_schema.replace(to_one, to_many)),
+            schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
+            schema_version: 1,
+        })
+        .await?;
+    tokio::time::sleep(SCHEMA_CACHE_TTL).await;
+    create_edge(
+        &manager,
+        tenant_id,
+        process,
+        "binary_file",
+        second_binary,
+        "Process",
+    )
+    .await?;
+    let mut binaries = edge_destinations(&scylla_client, tenant_id, process, "binary_file").await?;
+    binaries.sort();
+    let mut expected = vec![first_binary, second_binary];
+    expected.sort();
+    assert_eq!(binaries, expected);
+
+    // And a version which declares it as ToOne again is enforced in turn
+    graph_schema_manager_client
+        .deploy_schema(graph_schema_manager_api::DeploySchemaRequest {
+            tenant_id,
+            schema: Bytes::from(example_schema),
+            schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
+            schema_version: 2,
+        })
+        .await?;
+    tokio::time::sleep(SCHEMA_CACHE_TTL).await;
+    let result = create_edge(
+        &manager,
+        tenant_id,
+        process,
+        "binary_file",
+        third_binary,
+        "Process",
+    )
+    .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::ToOneEdgeConflict { .. })
+        ),
+        "expected the redeclared cardinality to be enforced, got {result:?}"
+    );
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mutate_graph() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Reject).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let process_name = PropertyName::try_from("process_name")?;
+    let file_path = PropertyName::try_from("file_path")?;
+    let binary_file = EdgeName::try_from("binary_file")?;
+    let children = EdgeName::try_from("children")?;
+
+    let set_process_name = mutation::BatchSetNodeProperty {
+        node: mutation::NodeRef::CreatedNodeIndex(0),
+        node_type: process_node_type.clone(),
+        property_name: process_name.clone(),
+        property: NodeProperty {
+            property: Property::ImmutableStrProp(ImmutableStrProp {
+                prop: "chrome.exe".into(),
+            }),
+        },
+    };
+    let create_binary_file = mutation::BatchCreateEdge {
+        edge_name: binary_file.clone(),
+        from: mutation::NodeRef::CreatedNodeIndex(0),
+        to: mutation::NodeRef::CreatedNodeIndex(1),
+        source_node_type: process_node_type.clone(),
+    };
+
+    // A process and its binary, linked by batch-local refs
+    let response = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![
+                mutation::BatchCreateNode {
+                    node_type: process_node_type.clone(),
+                },
+                mutation::BatchCreateNode {
+                    node_type: file_node_type.clone(),
+                },
+            ],
+            set_node_properties: vec![
+                set_process_name.clone(),
+                mutation::BatchSetNodeProperty {
+                    node: mutation::NodeRef::CreatedNodeIndex(1),
+                    node_type: file_node_type.clone(),
+                    property_name: file_path.clone(),
+                    property: NodeProperty {
+                        property: Property::ImmutableStrProp(ImmutableStrProp {
+                            prop: "/usr/bin/chrome".into(),
+                        }),
+                    },
+                },
+            ],
+            create_edges: vec![create_binary_file.clone()],
+        })
+        .await?;
+    assert_eq!(response.created_uids.len(), 2);
+    assert_eq!(
+        response.set_node_property_redundancies,
+        vec![mutation::MutationRedundancy::Maybe; 2]
+    );
+    assert_eq!(
+        response.create_edge_redundancies,
+        vec![mutation::MutationRedundancy::Maybe]
+    );
+    let (process_uid, file_uid) = (response.created_uids[0], response.created_uids[1]);
+
+    assert_eq!(
+        node_type(&scylla_client, tenant_id, process_uid).await?,
+        Some("Process".to_owned())
+    );
+    assert_eq!(
+        node_type(&scylla_client, tenant_id, file_uid).await?,
+        Some("File".to_owned())
+    );
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            IMM_STRING_TABLE_NAME,
+            "process_name"
+        )
+        .await?,
+        Some("chrome.exe".to_owned())
+    );
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            file_uid,
+            IMM_STRING_TABLE_NAME,
+            "file_path"
+        )
+        .await?,
+        Some("/usr/bin/chrome".to_owned())
+    );
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, process_uid, "binary_file").await?,
+        vec![file_uid]
+    );
+    assert_eq!(
+        edge_destinations(&scylla_client, tenant_id, file_uid, "executed_as_processes").await?,
+        vec![process_uid]
+    );
+
+    // Repeating the writes against the now existing nodes drops them
+    let response = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![],
+            set_node_properties: vec![mutation::BatchSetNodeProperty {
+                node: mutation::NodeRef::Uid(process_uid),
+                ..set_process_name.clone()
+            }],
+            create_edges: vec![mutation::BatchCreateEdge {
+                from: mutation::NodeRef::Uid(process_uid),
+                to: mutation::NodeRef::Uid(file_uid),
+                ..create_binary_file.clone()
+            }],
+        })
+        .await?;
+    assert!(response.created_uids.is_empty());
+    assert_eq!(
+        response.set_node_property_redundancies,
+        vec![mutation::MutationRedundancy::True]
+    );
+    assert_eq!(
+        response.create_edge_redundancies,
+        vec![mutation::MutationRedundancy::True]
+    );
+
+    // A ref past the batch's created nodes is rejected before anything is
+    // created
+    let result = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![mutation::BatchCreateNode {
+                node_type: process_node_type.clone(),
+            }],
+            set_node_properties: vec![],
+            create_edges: vec![mutation::BatchCreateEdge {
+                to: mutation::NodeRef::CreatedNodeIndex(1),
+                ..create_binary_file.clone()
+            }],
+        })
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::InvalidNodeRef { .. })
+        ),
+        "expected an invalid node ref, got {result:?}"
+    );
+
+    // The same edge twice isn't a conflict
+    let response = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![
+                mutation::BatchCreateNode {
+                    node_type: process_node_type.clone(),
+                },
+                mutation::BatchCreateNode {
+                    node_type: file_node_type.clone(),
+                },
+            ],
+            set_node_properties: vec![],
+            create_edges: vec![create_binary_file.clone(), create_binary_file.clone()],
+        })
+        .await?;
+    assert_eq!(
+        edge_destinations(
+            &scylla_client,
+            tenant_id,
+            response.created_uids[0],
+            "binary_file"
+        )
+        .await?,
+        vec![response.created_uids[1]]
+    );
+
+    // Two different `binary_file` edges from one process would both pass the
+    // ToOne check before either was written, so the batch is rejected
+    let result = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![
+                mutation::BatchCreateNode {
+                    node_type: process_node_type.clone(),
+                },
+                mutation::BatchCreateNode {
+                    node_type: file_node_type.clone(),
+                },
+                mutation::BatchCreateNode {
+                    node_type: file_node_type.clone(),
+                },
+            ],
+            set_node_properties: vec![],
+            create_edges: vec![
+                create_binary_file.clone(),
+                mutation::BatchCreateEdge {
+                    to: mutation::NodeRef::CreatedNodeIndex(2),
+                    ..create_binary_file.clone()
+                },
+            ],
+        })
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::ConflictingToOneEdges { .. })
+        ),
+        "expected a conflict on the forward edge, got {result:?}"
+    );
+
+    // As would two `children` edges to one process, whose reverse `parent` is
+    // ToOne
+    let create_children = mutation::BatchCreateEdge {
+        edge_name: children.clone(),
+        from: mutation::NodeRef::CreatedNodeIndex(0),
+        to: mutation::NodeRef::CreatedNodeIndex(2),
+        source_node_type: process_node_type.clone(),
+    };
+    let result = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![
+                mutation::BatchCreateNode {
+                    node_type: process_node_type.clone(),
+                };
+                3
+            ],
+            set_node_properties: vec![],
+            create_edges: vec![
+                create_children.clone(),
+                mutation::BatchCreateEdge {
+                    from: mutation::NodeRef::CreatedNodeIndex(1),
+                    ..create_children.clone()
+                },
+            ],
+        })
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::ConflictingToOneEdges { .. })
+        ),
+        "expected a conflict on the reverse edge, got {result:?}"
+    );
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_delete_node_edge_and_property() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Reject).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let process_name = PropertyName::try_from("process_name")?;
+    let last_seen_at = PropertyName::try_from("last_seen_at")?;
+    let file_path = PropertyName::try_from("file_path")?;
+    let binary_file = EdgeName::try_from("binary_file")?;
+
+    let set_property =
+        |node_index, node_type: &NodeType, property_name: &PropertyName, property| {
+            mutation::BatchSetNodeProperty {
+                node: mutation::NodeRef::CreatedNodeIndex(node_index),
+                node_type: node_type.clone(),
+                property_name: property_name.clone(),
+                property: NodeProperty { property },
+            }
+        };
+    let set_last_seen_at = |uid, prop| mutation::SetNodePropertyRequest {
+        tenant_id,
+        uid,
+        node_type: process_node_type.clone(),
+        property_name: last_seen_at.clone(),
+        property: NodeProperty {
+            property: Property::IncrementOnlyUintProp(IncrementOnlyUintProp { prop }),
+        },
+    };
+    let clear_last_seen_at = |uid| mutation::ClearPropertyRequest {
+        tenant_id,
+        uid,
+        node_type: process_node_type.clone(),
+        property_name: last_seen_at.clone(),
+    };
+
+    let response = manager
+        .mutate_graph(mutation::MutateGraphRequest {
+            tenant_id,
+            create_nodes: vec![
+                mutation::BatchCreateNode {
+                    node_type: process_node_type.clone(),
+                },
+                mutation::BatchCreateNode {
+                    node_type: file_node_type.clone(),
+                },
+            ],
+            set_node_properties: vec![
+                set_property(
+                    0,
+                    &process_node_type,
+                    &process_name,
+                    Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: "chrome.exe".into(),
+                    }),
+                ),
+                set_property(
+                    0,
+                    &process_node_type,
+                    &last_seen_at,
+                    Property::IncrementOnlyUintProp(IncrementOnlyUintProp { prop: 100 }),
+                ),
+                set_property(
+                    1,
+                    &file_node_type,
+                    &file_path,
+                    Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: "/usr/bin/chrome".into(),
+                    }),
+                ),
+            ],
+            create_edges: vec![mutation::BatchCreateEdge {
+                edge_name: binary_file.clone(),
+                from: mutation::NodeRef::CreatedNodeIndex(0),
+                to: mutation::NodeRef::CreatedNodeIndex(1),
+                source_node_type: process_node_type.clone(),
+            }],
+        })
+        .await?;
+    let (process_uid, file_uid) = (response.created_uids[0], response.created_uids[1]);
+
+    manager
+        .clear_property(mutation::ClearPropertyRequest {
+            tenant_id,
+            uid: process_uid,
+            node_type: process_node_type.clone(),
+            property_name: process_name.clone(),
+        })
+        .await?;
+    manager
+        .clear_property(clear_last_seen_at(process_uid))
+        .await?;
+    manager
+        .delete_edge(mutation::DeleteEdgeRequest {
+            edge_name: binary_file.clone(),
+            tenant_id,
+            from_uid: process_uid,
+            to_uid: file_uid,
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // A cleared increment-only property is only set again by a greater value,
+    // and the write dropper has forgotten it, so the writes aren't dropped
+    for prop in [50, 200] {
+        let response = manager
+            .set_node_property(set_last_seen_at(process_uid, prop))
+            .await?;
+        assert_eq!(
+            response.mutation_redundancy,
+            mutation::MutationRedundancy::Maybe
+        );
+    }
+    assert_eq!(
+        stored_property::<i64>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            MAX_U_64_TABLE_NAME,
+            "last_seen_at"
+        )
+        .await?,
+        Some(200)
+    );
+
+    // The clear's tombstone is at the cleared value's timestamp, and wins the
+    // tie with a write of the same value, so that write is lost
+    manager
+        .clear_property(clear_last_seen_at(process_uid))
+        .await?;
+    let response = manager
+        .set_node_property(set_last_seen_at(process_uid, 200))
+        .await?;
+    assert_eq!(
+        response.mutation_redundancy,
+        mutation::MutationRedundancy::Maybe
+    );
+    assert_eq!(
+        stored_property::<i64>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            MAX_U_64_TABLE_NAME,
+            "last_seen_at"
+        )
+        .await?,
+        None
+    );
+
+    // The cleared property and its index entry are gone, as are both
+    // directions of the edge
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            IMM_STRING_TABLE_NAME,
+            "process_name"
+        )
+        .await?,
+        None
+    );
+    assert!(indexed_string_uids(
+        &scylla_client,
+        tenant_id,
+        "Process",
+        "process_name",
+        "chrome.exe"
+    )
+    .await?
+    .is_empty());
+    assert!(
+        edge_destinations(&scylla_client, tenant_id, process_uid, "binary_file")
+            .await?
+            .is_empty()
+    );
+    assert!(
+        edge_destinations(&scylla_client, tenant_id, file_uid, "executed_as_processes")
+            .await?
+            .is_empty()
+    );
+
+    // The node's stored type keys its index entries, so a delete of the
+    // wrong type is rejected, and deletes nothing
+    let result = manager
+        .delete_node(mutation::DeleteNodeRequest {
+            tenant_id,
+            uid: file_uid,
+            node_type: process_node_type.clone(),
+        })
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(GraphMutationManagerError::NodeTypeMismatch { .. })
+        ),
+        "expected a node type mismatch, got {result:?}"
+    );
+    assert_eq!(
+        indexed_string_uids(
+            &scylla_client,
+            tenant_id,
+            "File",
+            "file_path",
+            "/usr/bin/chrome"
+        )
+        .await?,
+        vec![file_uid]
+    );
+
+    manager
+        .delete_node(mutation::DeleteNodeRequest {
+            tenant_id,
+            uid: file_uid,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+    assert_eq!(node_type(&scylla_client, tenant_id, file_uid).await?, None);
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            file_uid,
+            IMM_STRING_TABLE_NAME,
+            "file_path"
+        )
+        .await?,
+        None
+    );
+    assert!(indexed_string_uids(
+        &scylla_client,
+        tenant_id,
+        "File",
+        "file_path",
+        "/usr/bin/chrome"
+    )
+    .await?
+    .is_empty());
+
+    // Deleting it again succeeds
+    manager
+        .delete_node(mutation::DeleteNodeRequest {
+            tenant_id,
+            uid: file_uid,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_schema_validated_writes() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client, ToOneEdgePolicy::Reject).await?;
+
+    let process_uid = create_node(&manager, tenant_id, "Process").await?;
+    let file_uid = create_node(&manager, tenant_id, "File").await?;
+
+    fn assert_invalid_argument(result: Result<(), GraphMutationManagerError>) {
+        match result.map_err(Status::from) {
+            Err(status) => assert_eq!(status.code(), Code::InvalidArgument, "{status}"),
+            Ok(()) => panic!("expected InvalidArgument, got Ok"),
+        }
+    }
+
+    // A property that the schema declares, with the declared type
+    set_node_property(
+        &manager,
+        tenant_id,
+        process_uid,
+        "Process",
+        "process_name",
+        Property::ImmutableStrProp(ImmutableStrProp {
+            prop: "chrome.exe".into(),
+        }),
+    )
+    .await?;
+
+    // A property that the schema doesn't declare on this node type
+    assert_invalid_argument(
+        set_node_property(
+            &manager,
+            tenant_id,
+            process_uid,
+            "Process",
+            "file_path",
+            Property::ImmutableStrProp(ImmutableStrProp {
+                prop: "/usr/bin/chrome".into(),
+            }),
+        )
+        .await,
+    );
+
+    // A declared property written with the wrong type
+    assert_invalid_argument(
+        set_node_property(
+            &manager,
+            tenant_id,
+            process_uid,
+            "Process",
+            "process_name",
+            Property::ImmutableUintProp(ImmutableUintProp { prop: 1234 }),
+        )
+        .await,
+    );
+
+    // An edge that the schema doesn't declare on this node type
+    assert_invalid_argument(
+        create_edge(
+            &manager,
+            tenant_id,
+            process_uid,
+            "opened_file",
+            file_uid,
+            "Process",
+        )
+        .await,
+    );
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mutable_properties() -> eyre::Result<()> {
+    let tenant_id = provision_tenant().await?;
+    let scylla_client = scylla_client().await?;
+    let manager = graph_mutation_manager(scylla_client.clone(), ToOneEdgePolicy::Reject).await?;
+
+    let process_uid = create_node(&manager, tenant_id, "Process").await?;
+    let file_uid = create_node(&manager, tenant_id, "File").await?;
+
+    // Setting "alice" again after "bob" must not be dropped as redundant
+    for user in ["alice", "bob", "alice"] {
+        set_node_property(
+            &manager,
+            tenant_id,
+            process_uid,
+            "Process",
+            "current_user",
+            Property::MutableStrProp(MutableStrProp { prop: user.into() }),
+        )
+        .await?;
+    }
+    for prop in [7.5, 0.25] {
+        set_node_property(
+            &manager,
+            tenant_id,
+            file_uid,
+            "File",
+            "entropy",
+            Property::MutableF64Prop(MutableF64Prop { prop }),
+        )
+        .await?;
+    }
+
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            MUT_STRING_TABLE_NAME,
+            "current_user"
+        )
+        .await?,
+        Some("alice".to_owned())
+    );
+    assert_eq!(
+        stored_property::<f64>(
+            &scylla_client,
+            tenant_id,
+            file_uid,
+            MUT_F_64_TABLE_NAME,
+            "entropy"
+        )
+        .await?,
+        Some(0.25)
+    );
+
+    manager
+        .clear_property(mutation::ClearPropertyRequest {
+            tenant_id,
+            uid: process_uid,
+            node_type: NodeType::try_from("Process").unwrap(),
+            property_name: PropertyName::try_from("current_user")?,
+        })
+        .await?;
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            MUT_STRING_TABLE_NAME,
+            "current_user"
+        )
+        .await?,
+        None
+    );
+
+    // A mutable property is set again by any write after the clear
+    set_node_property(
+        &manager,
+        tenant_id,
+        process_uid,
+        "Process",
+        "current_user",
+        Property::MutableStrProp(MutableStrProp {
+            prop: "alice".into(),
+        }),
+    )
+    .await?;
+    assert_eq!(
+        stored_property::<String>(
+            &scylla_client,
+            tenant_id,
+            process_uid,
+            MUT_STRING_TABLE_NAME,
+            "current_user"
+        )
+        .await?,
+        Some("alice".to_owned())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Benchmarks matching a query against a single high fan-out node (think
+//! `explorer.exe`) with varying traversal concurrency.
+//!
+//! These run against a live Scylla, graph-mutation, graph-schema-manager and
+//! uid-allocator, and read the same configuration from the environment as the
+//! integration tests:
+//!
+//! `cargo bench -p graph-query --features integration_tests`
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use clap::Parser;
+use criterion::{
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+};
+use graph_query::{
+    config::GraphDbConfig,
+    node_query::{
+        fetch_node_with_edges,
+        NodeQuery,
+        QueryRegexes,
+    },
+    property_cache::PropertyCache,
+    property_query::PropertyQueryExecutor,
+    short_circuit::ShortCircuit,
+    visited::Visited,
+};
+use rust_proto::{
+    client_factory::services::{
+        GraphMutationClientConfig,
+        GraphSchemaManagerClientConfig,
+        ScyllaProvisionerClientConfig,
+        UidAllocatorClientConfig,
+    },
+    graplinc::grapl::{
+        api::{
+            graph_mutation::v1beta1::{
+                client::GraphMutationClient,
+                messages as mutation,
+            },
+            graph_query_service::v1beta1::messages::{
+                GraphQuery,
+                NodePropertyQuery,
+            },
+            graph_schema_manager::v1beta1::{
+                client::GraphSchemaManagerClient,
+                messages as graph_schema_manager_api,
+            },
+            scylla_provisioner::v1beta1::{
+                client::ScyllaProvisionerClient,
+                messages as scylla_provisioner_msgs,
+            },
+            uid_allocator::v1beta1::{
+                client::UidAllocatorServiceClient,
+                messages::CreateTenantKeyspaceRequest,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            Uid,
+        },
+    },
+    protocol::service_client::ConnectWithConfig,
+};
+use scylla::CachingSession;
+use secrecy::ExposeSecret;
+
+const FAN_OUT: usize = 500;
+
+// cargo passes `--bench` to the benchmark binary, so configuration is only
+// read from the environment
+fn config_from_env<T: Parser>() -> T {
+    T::parse_from(["traversal"])
+}
+
+async fn connect_scylla() -> eyre::Result<Arc<CachingSession>> {
+    let graph_db_config: GraphDbConfig = config_from_env();
+    let mut scylla_config = scylla::SessionConfig::new();
+    scylla_config.add_known_nodes_addr(&graph_db_config.graph_db_addresses[..]);
+    scylla_config.auth_username = Some(graph_db_config.graph_db_auth_username.to_owned());
+    scylla_config.auth_password = Some(
+        graph_db_config
+            .graph_db_auth_password
+            .expose_secret()
+            .to_owned(),
+    );
+
+    Ok(Arc::new(CachingSession::from(
+        scylla::Session::connect(scylla_config).await?,
+        10_000,
+    )))
+}
+
+/// Provisions a new tenant with a single process that created `FAN_OUT`
+/// files, returning the tenant and the process' uid.
+async fn create_fan_out_graph() -> eyre::Result<(uuid::Uuid, Uid)> {
+    let tenant_id = uuid::Uuid::new_v4();
+
+    let provisioner_client_config: ScyllaProvisionerClientConfig = config_from_env();
+    let mut provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(provisioner_client_config).await?;
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    let uid_allocator_client_config: UidAllocatorClientConfig = config_from_env();
+    let mut uid_allocator_client =
+        UidAllocatorServiceClient::connect_with_config(uid_allocator_client_config).await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    let graph_schema_manager_client_config: GraphSchemaManagerClientConfig = config_from_env();
+    let mut graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(graph_schema_manager_client_config).await?;
+    graph_schema_manager_client
+        .deploy_schema(graph_schema_manager_api::DeploySchemaRequest {
+            tenant_id,
+            // This path is created in rust/Dockerfile
+            schema: std::fs::read("/test-fixtures/example_schemas/example.graphql")
+                .map(Bytes::from)?,
+            schema_type: graph_schema_manager_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+        })
+        .await?;
+
+    let mutation_client_config: GraphMutationClientConfig = config_from_env();
+    let mut graph_mutation_client =
+        GraphMutationClient::connect_with_config(mutation_client_config).await?;
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: NodeType::try_from("Process")?,
+        })
+        .await?;
+
+    for _ in 0..FAN_OUT {
+        let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: NodeType::try_from("File")?,
+            })
+            .await?;
+
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: EdgeName::try_from("created_file")?,
+                tenant_id,
+                from_uid: process_uid,
+                to_uid: file_uid,
+                source_node_type: NodeType::try_from("Process")?,
+            })
+            .await?;
+    }
+
+    Ok((tenant_id, process_uid))
+}
+
+fn process_created_file_query() -> GraphQuery {
+    NodeQuery::root(NodeType::try_from("Process").unwrap())
+        .with_shared_edge(
+            EdgeName::try_from("created_file").unwrap(),
+            EdgeName::try_from("created_by_process").unwrap(),
+            NodePropertyQuery::new(NodeType::try_from("File").unwrap()),
+            |_| {},
+        )
+        .build()
+}
+
+pub fn fan_out_bench(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (tenant_id, process_uid) = runtime.block_on(create_fan_out_graph()).unwrap();
+    let scylla_client = runtime.block_on(connect_scylla()).unwrap();
+    let property_query_executor = PropertyQueryExecutor::new(
+        scylla_client,
+        PropertyCache::with_capacity(FAN_OUT as u64 * 2, Duration::from_secs(60)),
+    );
+
+    let graph_query = process_created_file_query();
+    let root_query = &graph_query.node_property_queries[&graph_query.root_query_id];
+    let regexes = QueryRegexes::compile(&graph_query).unwrap();
+
+    let mut group = c.benchmark_group(format!("fan out of {FAN_OUT}"));
+    for max_concurrent_neighbor_fetches in [1, 4, 16, 64] {
+        group.bench_with_input(
+            BenchmarkId::new("fetch_node_with_edges", max_concurrent_neighbor_fetches),
+            &max_concurrent_neighbor_fetches,
+            |b, max_concurrent_neighbor_fetches| {
+                b.to_async(&runtime).iter(|| async {
+                    let graph = fetch_node_with_edges(
+                        root_query,
+                        &graph_query,
+                        &regexes,
+                        process_uid,
+                        tenant_id,
+                        property_query_executor.clone(),
+                        Visited::new(),
+                        ShortCircuit::new(),
+                        *max_concurrent_neighbor_fetches,
+                        &mut None,
+                    )
+                    .await
+                    .unwrap()
+                    .expect("expected the process to match");
+                    assert_eq!(graph.nodes.len(), FAN_OUT + 1);
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, fan_out_bench);
+criterion_main!(benches);
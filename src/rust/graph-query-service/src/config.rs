@@ -1,4 +1,9 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    num::NonZeroUsize,
+};
+
+use rust_proto::client_factory::services::GraphSchemaManagerClientConfig;
 
 #[derive(clap::Parser, Clone, Debug)]
 #[clap(name = "graph-generator", about = "Graph Generator Service")]
@@ -23,4 +28,75 @@ pub struct GraphQueryServiceConfig {
 
     #[clap(flatten)]
     pub graph_db_config: GraphDbConfig,
+
+    #[clap(flatten)]
+    pub property_cache_config: PropertyCacheConfig,
+
+    #[clap(flatten)]
+    pub graph_schema_manager_client_config: GraphSchemaManagerClientConfig,
+
+    #[clap(
+        long,
+        env = "GRAPH_QUERY_MAX_CONCURRENT_NEIGHBOR_FETCHES",
+        default_value = "16"
+    )]
+    /// The maximum number of a node's neighbors that are fetched concurrently
+    /// when matching one of its edges
+    pub max_concurrent_neighbor_fetches: NonZeroUsize,
+
+    #[clap(
+        long,
+        env = "GRAPH_QUERY_EDGE_CARDINALITY_CACHE_TTL_MS",
+        default_value = "60000"
+    )]
+    /// How long an edge's declared cardinality is cached for, and so how long
+    /// a redeployed schema may take to be picked up
+    pub edge_cardinality_cache_ttl_ms: u64,
+
+    #[clap(flatten)]
+    pub query_limits_config: QueryLimitsConfig,
+}
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct QueryLimitsConfig {
+    #[clap(long, env = "GRAPH_QUERY_MAX_NODES_VISITED", default_value = "10000")]
+    /// The most nodes a single graph query may visit
+    pub max_nodes_visited: usize,
+
+    #[clap(long, env = "GRAPH_QUERY_MAX_ROUND_TRIPS", default_value = "50000")]
+    /// The most queries a single graph query may send to the graph database
+    pub max_round_trips: usize,
+
+    #[clap(long, env = "GRAPH_QUERY_TIMEOUT_MS", default_value = "10000")]
+    /// How long a single graph query may run for
+    pub query_timeout_ms: u64,
+}
+
+#[derive(clap::Parser, Debug, Clone)]
+pub struct PropertyCacheConfig {
+    #[clap(
+        long,
+        env = "GRAPH_QUERY_PROPERTY_CACHE_CAPACITY",
+        default_value = "100000"
+    )]
+    /// The maximum number of entries held by each of the string, int and uint
+    /// property caches, and by the node type cache
+    pub property_cache_capacity: u64,
+
+    #[clap(
+        long,
+        env = "GRAPH_QUERY_PROPERTY_CACHE_STATS_INTERVAL_MS",
+        default_value = "60000"
+    )]
+    /// How often to report the property cache's hit rate
+    pub property_cache_stats_interval_ms: u64,
+
+    #[clap(
+        long,
+        env = "GRAPH_QUERY_PROPERTY_CACHE_TTL_MS",
+        default_value = "300000"
+    )]
+    /// How long a cached property or node type may be served for, which
+    /// bounds how long a deleted one may still be returned
+    pub property_cache_ttl_ms: u64,
 }
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use moka::future::{
+    Cache,
+    CacheBuilder,
+};
+use rust_proto::{
+    graplinc::grapl::{
+        api::graph_schema_manager::v1beta1::{
+            client::{
+                GraphSchemaManagerClient,
+                GraphSchemaManagerClientError,
+            },
+            messages::{
+                EdgeCardinality,
+                GetEdgeSchemaRequest,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+        },
+    },
+    protocol::status::Code,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum EdgeCardinalityResolverError {
+    #[error("couldn't resolve edge cardinality from Graph Schema Manager: {0}")]
+    GraphSchemaManagerClientError(#[from] GraphSchemaManagerClientError),
+    #[error("edge '{edge_name}' is not declared on node type '{node_type}'")]
+    UndeclaredEdge {
+        node_type: NodeType,
+        edge_name: EdgeName,
+    },
+}
+
+/// Looks up whether an edge out of a node type points to one node or many.
+///
+/// A new version of a schema may redeclare an edge with another cardinality,
+/// so lookups are only cached for `time_to_live`. Undeclared edges aren't
+/// cached, so that they're picked up as soon as a schema declaring them is
+/// deployed.
+#[derive(Clone)]
+pub struct EdgeCardinalityResolver {
+    schema_client: GraphSchemaManagerClient,
+    cardinality_cache: Cache<(uuid::Uuid, NodeType, EdgeName), EdgeCardinality>,
+}
+
+impl EdgeCardinalityResolver {
+    pub fn new(
+        schema_client: GraphSchemaManagerClient,
+        cache_size: u64,
+        time_to_live: Duration,
+    ) -> Self {
+        Self {
+            schema_client,
+            cardinality_cache: CacheBuilder::new(cache_size)
+                .time_to_live(time_to_live)
+                .build(),
+        }
+    }
+
+    pub async fn resolve_cardinality(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+        edge_name: EdgeName,
+    ) -> Result<EdgeCardinality, EdgeCardinalityResolverError> {
+        let key = (tenant_id, node_type, edge_name);
+        if let Some(cardinality) = self.cardinality_cache.get(&key) {
+            return Ok(cardinality);
+        }
+
+        let (tenant_id, node_type, edge_name) = key.clone();
+        let mut schema_client = self.schema_client.clone();
+        let response = match schema_client
+            .get_edge_schema(GetEdgeSchemaRequest {
+                tenant_id,
+                node_type: node_type.clone(),
+                edge_name: edge_name.clone(),
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(GraphSchemaManagerClientError::ErrorStatus(status))
+                if status.code() == Code::NotFound =>
+            {
+                return Err(EdgeCardinalityResolverError::UndeclaredEdge {
+                    node_type,
+                    edge_name,
+                })
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.cardinality_cache
+            .insert(key, response.cardinality.clone())
+            .await;
+        Ok(response.cardinality)
+    }
+}
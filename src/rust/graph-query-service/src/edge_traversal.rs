@@ -0,0 +1,254 @@
+use futures::{
+    stream,
+    StreamExt,
+    TryStreamExt,
+};
+use rust_proto::graplinc::grapl::{
+    api::{
+        graph_query_service::v1beta1::messages::GraphView,
+        graph_schema_manager::v1beta1::messages::EdgeCardinality,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        NodeType,
+        Uid,
+    },
+};
+use rustc_hash::FxHashSet;
+use tokio::time::Instant;
+
+use crate::property_query::{
+    EdgeRow,
+    PropertyQueryError,
+    PropertyQueryExecutor,
+};
+
+/// Bounds on how far, and for how long, an edge is followed.
+#[derive(Debug, Clone)]
+pub struct TraversalLimits {
+    /// The most hops away from the starting node to follow the edge
+    pub max_depth: usize,
+    /// The most nodes to visit, including the starting node. This guards
+    /// against edges with a huge fan out, e.g. the process tree of `init`.
+    pub max_nodes: usize,
+    /// When to stop traversing and return whatever has been visited so far
+    pub deadline: Instant,
+}
+
+/// The nodes reachable by following a single edge out of a node, along with
+/// the edges (and their reverses) that were followed to reach them.
+#[derive(Debug, Clone, Default)]
+pub struct EdgeTraversal {
+    pub graph: GraphView,
+    /// True if the traversal hit `max_nodes` or the deadline before visiting
+    /// every node within `max_depth` hops.
+    pub truncated: bool,
+}
+
+/// Follows `edge_name` transitively out of the node with the given uid.
+///
+/// The edge's cardinality picks the strategy: a ToOne edge (like a process'
+/// `parent`) forms a chain, which is followed one node at a time, while a
+/// ToMany edge (like a process' `children`) forms a tree, which is expanded
+/// breadth first with up to `max_concurrent_neighbor_fetches` of each hop's
+/// nodes fetched concurrently. Either way a node is only visited once, so
+/// cycles are harmless.
+pub async fn traverse_edge(
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    node_type: NodeType,
+    edge_name: &EdgeName,
+    cardinality: &EdgeCardinality,
+    limits: &TraversalLimits,
+    property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+) -> Result<EdgeTraversal, PropertyQueryError> {
+    let mut traversal = EdgeTraversal::default();
+    traversal.graph.new_node(uid, node_type);
+
+    let result = match cardinality {
+        EdgeCardinality::ToOne => {
+            tokio::time::timeout_at(
+                limits.deadline,
+                follow_chain(
+                    &mut traversal,
+                    tenant_id,
+                    uid,
+                    edge_name,
+                    limits,
+                    &property_query_executor,
+                ),
+            )
+            .await
+        }
+        EdgeCardinality::ToMany => {
+            tokio::time::timeout_at(
+                limits.deadline,
+                expand_breadth_first(
+                    &mut traversal,
+                    tenant_id,
+                    uid,
+                    edge_name,
+                    limits,
+                    &property_query_executor,
+                    max_concurrent_neighbor_fetches,
+                ),
+            )
+            .await
+        }
+    };
+
+    match result {
+        Ok(result) => result?,
+        Err(_elapsed) => {
+            tracing::debug!(
+                message = "edge traversal timed out",
+                tenant_id =% tenant_id,
+                uid =? uid,
+                edge_name =% edge_name,
+                visited = traversal.graph.nodes.len(),
+            );
+            traversal.truncated = true;
+        }
+    }
+
+    Ok(traversal)
+}
+
+async fn follow_chain(
+    traversal: &mut EdgeTraversal,
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    edge_name: &EdgeName,
+    limits: &TraversalLimits,
+    property_query_executor: &PropertyQueryExecutor,
+) -> Result<(), PropertyQueryError> {
+    let mut current_uid = uid;
+    for _ in 0..limits.max_depth {
+        // A ToOne edge points to at most one node, so there's only ever one
+        // node to follow at each hop
+        let edge_row = match property_query_executor
+            .get_edges(tenant_id, current_uid, edge_name)
+            .await?
+            .and_then(|edge_rows| edge_rows.into_iter().next())
+        {
+            Some(edge_row) => edge_row,
+            None => return Ok(()),
+        };
+
+        let neighbor_uid = edge_row.destination_uid;
+        if traversal.graph.nodes.contains_key(&neighbor_uid) {
+            // The chain loops back on itself
+            add_traversed_edge(&mut traversal.graph, &edge_row);
+            return Ok(());
+        }
+        if traversal.graph.nodes.len() >= limits.max_nodes {
+            traversal.truncated = true;
+            return Ok(());
+        }
+
+        property_query_executor.visit_node()?;
+        let node_type = match property_query_executor
+            .get_node_type(tenant_id, neighbor_uid)
+            .await?
+        {
+            Some(node_type) => node_type,
+            None => return Ok(()),
+        };
+        traversal.graph.new_node(neighbor_uid, node_type);
+        add_traversed_edge(&mut traversal.graph, &edge_row);
+        current_uid = neighbor_uid;
+    }
+
+    Ok(())
+}
+
+async fn expand_breadth_first(
+    traversal: &mut EdgeTraversal,
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    edge_name: &EdgeName,
+    limits: &TraversalLimits,
+    property_query_executor: &PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+) -> Result<(), PropertyQueryError> {
+    let mut frontier = vec![uid];
+    for _ in 0..limits.max_depth {
+        if frontier.is_empty() {
+            break;
+        }
+
+        let edge_rows: Vec<EdgeRow> = stream::iter(frontier)
+            .map(|uid| property_query_executor.get_edges(tenant_id, uid, edge_name))
+            .buffer_unordered(max_concurrent_neighbor_fetches)
+            .try_fold(vec![], |mut edge_rows, hop_edge_rows| async move {
+                edge_rows.extend(hop_edge_rows.into_iter().flatten());
+                Ok(edge_rows)
+            })
+            .await?;
+
+        // Only nodes we haven't seen yet are expanded, and only as many of
+        // them as the cardinality guard allows
+        let mut neighbor_uids = vec![];
+        let mut seen = FxHashSet::default();
+        for edge_row in edge_rows.iter() {
+            let neighbor_uid = edge_row.destination_uid;
+            if traversal.graph.nodes.contains_key(&neighbor_uid) || !seen.insert(neighbor_uid) {
+                continue;
+            }
+            if traversal.graph.nodes.len() + neighbor_uids.len() >= limits.max_nodes {
+                traversal.truncated = true;
+                break;
+            }
+            neighbor_uids.push(neighbor_uid);
+        }
+
+        let neighbors: Vec<(Uid, Option<NodeType>)> = stream::iter(neighbor_uids)
+            .map(|neighbor_uid| async move {
+                property_query_executor.visit_node()?;
+                let node_type = property_query_executor
+                    .get_node_type(tenant_id, neighbor_uid)
+                    .await?;
+                Ok::<_, PropertyQueryError>((neighbor_uid, node_type))
+            })
+            .buffer_unordered(max_concurrent_neighbor_fetches)
+            .try_collect()
+            .await?;
+
+        frontier = vec![];
+        for (neighbor_uid, node_type) in neighbors {
+            if let Some(node_type) = node_type {
+                traversal.graph.new_node(neighbor_uid, node_type);
+                frontier.push(neighbor_uid);
+            }
+        }
+        for edge_row in edge_rows.iter() {
+            if traversal
+                .graph
+                .nodes
+                .contains_key(&edge_row.destination_uid)
+            {
+                add_traversed_edge(&mut traversal.graph, edge_row);
+            }
+        }
+
+        if traversal.truncated {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn add_traversed_edge(graph: &mut GraphView, edge_row: &EdgeRow) {
+    graph.add_edge(
+        edge_row.source_uid,
+        edge_row.f_edge_name.clone(),
+        edge_row.destination_uid,
+    );
+    graph.add_edge(
+        edge_row.destination_uid,
+        edge_row.r_edge_name.clone(),
+        edge_row.source_uid,
+    );
+}
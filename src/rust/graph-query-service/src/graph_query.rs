@@ -4,14 +4,24 @@ use rust_proto::graplinc::grapl::{
     api::graph_query_service::v1beta1::messages::{
         GraphQuery,
         GraphView,
+        QueryId,
     },
     common::v1beta1::types::Uid,
 };
+use rustc_hash::{
+    FxHashMap,
+    FxHashSet,
+};
 
 use crate::{
+    node_matches::{
+        fetch_node_matches,
+        plan_traversal,
+    },
     node_query::{
         fetch_node_with_edges,
         NodeQueryError,
+        QueryRegexes,
     },
     property_query::PropertyQueryExecutor,
     short_circuit::ShortCircuit,
@@ -24,16 +34,49 @@ pub enum GraphQueryError {
     NodeQueryError { uid: Uid, source: NodeQueryError },
 }
 
+/// The node queries that a graph query can start from, which are those
+/// reachable from the root node query by required edges. The neighbors across
+/// OR groups and negated edges aren't connected back to the rest of the query.
+fn anchor_query_ids(graph_query: &GraphQuery) -> FxHashSet<QueryId> {
+    let mut anchor_query_ids = FxHashSet::default();
+    anchor_query_ids.insert(graph_query.root_query_id);
+
+    let mut to_visit = vec![graph_query.root_query_id];
+    while let Some(src_id) = to_visit.pop() {
+        for ((edge_src_id, _), dst_ids) in graph_query.edge_filters.iter() {
+            if *edge_src_id != src_id {
+                continue;
+            }
+            for dst_id in dst_ids {
+                if anchor_query_ids.insert(*dst_id) {
+                    to_visit.push(*dst_id);
+                }
+            }
+        }
+    }
+
+    anchor_query_ids
+}
+
 #[tracing::instrument(skip(graph_query, property_query_executor))]
 pub async fn query_graph(
     graph_query: &GraphQuery,
     uid: Uid,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
 ) -> Result<Option<(GraphView, Uid)>, GraphQueryError> {
+    let regexes = QueryRegexes::compile(graph_query)
+        .map_err(|e| GraphQueryError::NodeQueryError { uid, source: e })?;
+    let regexes = &regexes;
+
     let mut query_handles = Vec::with_capacity(graph_query.node_property_queries.len());
     let x_query_short_circuiter = ShortCircuit::new();
+    let anchor_query_ids = anchor_query_ids(graph_query);
     for node_query in graph_query.node_property_queries.values() {
+        if !anchor_query_ids.contains(&node_query.query_id) {
+            continue;
+        }
         let property_query_executor = property_query_executor.clone();
         let node_query = node_query.clone();
         let x_query_short_circuiter = x_query_short_circuiter.clone();
@@ -43,11 +86,13 @@ pub async fn query_graph(
             match fetch_node_with_edges(
                 &node_query,
                 graph_query,
+                regexes,
                 uid,
                 tenant_id,
                 property_query_executor,
                 visited,
                 x_query_short_circuiter.clone(),
+                max_concurrent_neighbor_fetches,
                 &mut root_query_uid,
             )
             .await
@@ -84,3 +129,102 @@ pub async fn query_graph(
     }
     Ok(None)
 }
+
+/// Returns up to `max_results` distinct graphs matching the query in which the
+/// node with the given uid matches any of the query's nodes, along with whether
+/// more matches than that exist.
+#[tracing::instrument(skip(graph_query, property_query_executor))]
+pub async fn query_all_graphs(
+    graph_query: &GraphQuery,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+    max_results: usize,
+) -> Result<(Vec<(GraphView, Uid)>, bool), GraphQueryError> {
+    let regexes = QueryRegexes::compile(graph_query)
+        .map_err(|e| GraphQueryError::NodeQueryError { uid, source: e })?;
+    let regexes = &regexes;
+
+    // Look for one more match than we'll return, so that we can tell whether
+    // the results were truncated
+    let max_matches = max_results + 1;
+
+    let mut query_handles = Vec::with_capacity(graph_query.node_property_queries.len());
+    let anchor_query_ids = anchor_query_ids(graph_query);
+    for node_query in graph_query.node_property_queries.values() {
+        if !anchor_query_ids.contains(&node_query.query_id) {
+            continue;
+        }
+        let property_query_executor = property_query_executor.clone();
+        query_handles.push(async move {
+            let plan = plan_traversal(graph_query, node_query.query_id);
+            fetch_node_matches(
+                node_query,
+                graph_query,
+                regexes,
+                &plan,
+                uid,
+                tenant_id,
+                property_query_executor,
+                max_concurrent_neighbor_fetches,
+                max_matches,
+            )
+            .await
+            .map_err(|e| GraphQueryError::NodeQueryError { uid, source: e })
+        });
+    }
+
+    let mut matches: Vec<(GraphView, Uid)> = vec![];
+    // The matches seen so far by their shape. A GraphView can't be hashed,
+    // since its properties may be floats, so only matches of the same shape
+    // are compared in full.
+    let mut seen: FxHashMap<MatchShape, Vec<usize>> = FxHashMap::default();
+    for node_matches in join_all(query_handles).await {
+        for graph_match in node_matches? {
+            let root_uid = match graph_match.root_uid {
+                Some(root_uid) => root_uid,
+                None => {
+                    tracing::error!(
+                        message = "Graph query matched without finding root_uid. This is a bug.",
+                    );
+                    continue;
+                }
+            };
+            // Different assignments of nodes to the query's nodes can produce
+            // the same subgraph, e.g. when a query node has two identical
+            // neighbors, or when the uid matches several of the query's nodes
+            let graph_match = (graph_match.graph, root_uid);
+            let same_shape = seen
+                .entry(match_shape(&graph_match.0, root_uid))
+                .or_default();
+            if !same_shape.iter().any(|i| matches[*i] == graph_match) {
+                same_shape.push(matches.len());
+                matches.push(graph_match);
+            }
+        }
+    }
+
+    let truncated = matches.len() > max_results;
+    matches.truncate(max_results);
+    Ok((matches, truncated))
+}
+
+/// The root uid, node uids and edges of a match, each sorted
+type MatchShape = (Uid, Vec<Uid>, Vec<(Uid, String, Uid)>);
+
+fn match_shape(graph: &GraphView, root_uid: Uid) -> MatchShape {
+    let mut uids: Vec<Uid> = graph.nodes.keys().copied().collect();
+    uids.sort_unstable();
+    let mut edges: Vec<(Uid, String, Uid)> = graph
+        .edges
+        .iter()
+        .flat_map(|((src_uid, edge_name), dst_uids)| {
+            dst_uids
+                .iter()
+                .map(move |dst_uid| (*src_uid, edge_name.value.clone(), *dst_uid))
+        })
+        .collect();
+    edges.sort_unstable();
+    (root_uid, uids, edges)
+}
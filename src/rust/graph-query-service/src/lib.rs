@@ -1,8 +1,13 @@
 pub mod config;
+pub mod edge_cardinality;
+pub mod edge_traversal;
 pub mod graph_query;
+pub mod neighborhood;
+pub mod node_matches;
 pub mod node_query;
+pub mod property_cache;
 pub mod property_query;
+pub mod query_budget;
 pub mod server;
 pub mod short_circuit;
-pub mod table_names;
 pub mod visited;
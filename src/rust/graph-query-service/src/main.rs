@@ -6,12 +6,21 @@ use std::{
 use clap::Parser;
 use graph_query::{
     config,
+    edge_cardinality::EdgeCardinalityResolver,
+    property_cache::PropertyCache,
+    query_budget::QueryLimits,
     server,
 };
 use grapl_tracing::setup_tracing;
 use rust_proto::{
-    graplinc::grapl::api::graph_query_service::v1beta1::server::GraphQueryServiceServer,
-    protocol::healthcheck::HealthcheckStatus,
+    graplinc::grapl::api::{
+        graph_query_service::v1beta1::server::GraphQueryServiceServer,
+        graph_schema_manager::v1beta1::client::GraphSchemaManagerClient,
+    },
+    protocol::{
+        healthcheck::HealthcheckStatus,
+        service_client::ConnectWithConfig,
+    },
 };
 use scylla::CachingSession;
 use secrecy::ExposeSecret;
@@ -41,11 +50,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         scylla::Session::connect(scylla_config).await?,
         10_000,
     ));
-    let graph_query_service = GraphQueryService::new(scylla_client);
+    let property_cache = PropertyCache::with_capacity(
+        config.property_cache_config.property_cache_capacity,
+        Duration::from_millis(config.property_cache_config.property_cache_ttl_ms),
+    );
+    report_property_cache_stats(
+        property_cache.clone(),
+        Duration::from_millis(
+            config
+                .property_cache_config
+                .property_cache_stats_interval_ms,
+        ),
+    );
+    let graph_schema_manager_client = GraphSchemaManagerClient::connect_with_config(
+        config.graph_schema_manager_client_config.clone(),
+    )
+    .await?;
+    let graph_query_service = GraphQueryService::new(
+        scylla_client,
+        property_cache,
+        EdgeCardinalityResolver::new(
+            graph_schema_manager_client,
+            10_000,
+            Duration::from_millis(config.edge_cardinality_cache_ttl_ms),
+        ),
+        config.max_concurrent_neighbor_fetches.get(),
+        QueryLimits {
+            max_nodes_visited: config.query_limits_config.max_nodes_visited,
+            max_round_trips: config.query_limits_config.max_round_trips,
+            timeout: Duration::from_millis(config.query_limits_config.query_timeout_ms),
+        },
+    );
 
     exec_service(config, graph_query_service).await
 }
 
+fn report_property_cache_stats(property_cache: PropertyCache, interval: Duration) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            property_cache.report_stats();
+        }
+    });
+}
+
 #[tracing::instrument(skip(config, api_server))]
 pub async fn exec_service(
     config: GraphQueryServiceConfig,
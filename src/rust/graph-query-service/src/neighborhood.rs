@@ -0,0 +1,93 @@
+use futures::{
+    stream,
+    StreamExt,
+    TryStreamExt,
+};
+use rust_proto::graplinc::grapl::{
+    api::graph_query_service::v1beta1::messages::{
+        GraphView,
+        NodePropertiesView,
+    },
+    common::v1beta1::types::Uid,
+};
+use rustc_hash::FxHashSet;
+
+use crate::property_query::{
+    PropertyQueryError,
+    PropertyQueryExecutor,
+};
+
+/// A node with every one of its properties, its edges and optionally its
+/// neighbors.
+#[derive(Debug, Clone)]
+pub struct Neighborhood {
+    pub graph: GraphView,
+    /// True if the node has more than `max_edges` edges, so that only some of
+    /// them (and their neighbors) are in the graph.
+    pub truncated: bool,
+}
+
+/// Fetches a node with every one of its populated properties, and up to
+/// `max_edges` edges out of it (along with each edge's reverse), or None if
+/// the node doesn't exist.
+///
+/// With `include_neighbors`, the neighbor across each of those edges is
+/// fetched along with every one of its properties too, up to
+/// `max_concurrent_neighbor_fetches` at a time. The neighbors' own edges aren't
+/// fetched, other than those back to the node.
+pub async fn fetch_neighborhood(
+    tenant_id: uuid::Uuid,
+    uid: Uid,
+    include_neighbors: bool,
+    max_edges: usize,
+    property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+) -> Result<Option<Neighborhood>, PropertyQueryError> {
+    property_query_executor.visit_node()?;
+    // Fetch one extra edge to tell whether there were more than requested
+    let (node, mut edge_rows) = futures::try_join!(
+        property_query_executor.get_all_properties(tenant_id, uid),
+        property_query_executor.get_all_edges(tenant_id, uid, max_edges + 1),
+    )?;
+    let node = match node {
+        Some(node) => node,
+        None => return Ok(None),
+    };
+    let truncated = edge_rows.len() > max_edges;
+    edge_rows.truncate(max_edges);
+
+    let mut graph = GraphView::default();
+    graph.add_node(node);
+    for edge_row in edge_rows.iter() {
+        graph.add_edge(uid, edge_row.f_edge_name.clone(), edge_row.destination_uid);
+        graph.add_edge(edge_row.destination_uid, edge_row.r_edge_name.clone(), uid);
+    }
+
+    if !include_neighbors {
+        return Ok(Some(Neighborhood { graph, truncated }));
+    }
+
+    let neighbor_uids: FxHashSet<Uid> = edge_rows
+        .iter()
+        .map(|edge_row| edge_row.destination_uid)
+        .filter(|neighbor_uid| *neighbor_uid != uid)
+        .collect();
+
+    let property_query_executor = &property_query_executor;
+    let neighbors: Vec<Option<NodePropertiesView>> = stream::iter(neighbor_uids)
+        .map(|neighbor_uid| async move {
+            property_query_executor.visit_node()?;
+            property_query_executor
+                .get_all_properties(tenant_id, neighbor_uid)
+                .await
+        })
+        .buffer_unordered(max_concurrent_neighbor_fetches)
+        .try_collect()
+        .await?;
+
+    for neighbor in neighbors.into_iter().flatten() {
+        graph.add_node(neighbor);
+    }
+
+    Ok(Some(Neighborhood { graph, truncated }))
+}
@@ -0,0 +1,516 @@
+use async_recursion::async_recursion;
+use futures::{
+    stream,
+    StreamExt,
+};
+use rust_proto::graplinc::grapl::{
+    api::graph_query_service::v1beta1::messages::{
+        GraphQuery,
+        GraphView,
+        NodePropertyQuery,
+        QueryId,
+    },
+    common::v1beta1::types::{
+        EdgeName,
+        Uid,
+    },
+};
+use rustc_hash::{
+    FxHashMap,
+    FxHashSet,
+};
+
+use crate::{
+    node_query::{
+        fetch_edges,
+        fetch_matching_node,
+        NodeQueryError,
+        QueryRegexes,
+    },
+    property_query::{
+        EdgeRow,
+        PropertyQueryExecutor,
+    },
+};
+
+/// A single match of a graph query, along with the uid of the node that
+/// matched the query's root node (if that node is part of the match).
+#[derive(Debug, Clone, Default)]
+pub struct GraphMatch {
+    pub graph: GraphView,
+    pub root_uid: Option<Uid>,
+    /// The uid matched by each node query in this match
+    bindings: FxHashMap<QueryId, Uid>,
+}
+
+impl GraphMatch {
+    fn merge(&mut self, other: GraphMatch) {
+        self.graph.merge(other.graph);
+        self.root_uid = self.root_uid.or(other.root_uid);
+        self.bindings.extend(other.bindings);
+    }
+}
+
+/// A required edge between two node queries that the traversal doesn't follow,
+/// because its destination is already reached through another path.
+type ClosingEdge = (QueryId, EdgeName, QueryId);
+
+/// How to match a graph query from a given starting node query.
+///
+/// Every node query is visited once, so the edges we traverse form a spanning
+/// tree of the graph query rooted at the starting node query. In particular
+/// the reverse of an edge we've already traversed is never traversed again.
+/// OR groups and negated edges aren't traversed, since they're always
+/// evaluated from their source.
+///
+/// Any other required edge closes a cycle (or a diamond) in the graph query.
+/// Those are checked once a match binds both of their ends, i.e. at the
+/// closest node query whose matches include both ends.
+#[derive(Debug, Default)]
+pub struct TraversalPlan {
+    edges: FxHashMap<QueryId, Vec<(EdgeName, QueryId)>>,
+    closing_edges: FxHashMap<QueryId, Vec<ClosingEdge>>,
+}
+
+pub fn plan_traversal(graph_query: &GraphQuery, start: QueryId) -> TraversalPlan {
+    let mut plan = TraversalPlan::default();
+    // The node query each visited node query is matched from
+    let mut parents = FxHashMap::default();
+    let mut tree_edges = FxHashSet::default();
+    let mut visited = FxHashSet::default();
+    visited.insert(start);
+
+    let mut to_visit = vec![start];
+    while let Some(src_id) = to_visit.pop() {
+        let mut edges = vec![];
+        for ((edge_src_id, edge_name), dst_ids) in graph_query.edge_filters.iter() {
+            if *edge_src_id != src_id {
+                continue;
+            }
+            for dst_id in dst_ids {
+                if visited.insert(*dst_id) {
+                    edges.push((edge_name.clone(), *dst_id));
+                    parents.insert(*dst_id, src_id);
+                    tree_edges.insert((src_id, edge_name.clone(), *dst_id));
+                    to_visit.push(*dst_id);
+                }
+            }
+        }
+        plan.edges.insert(src_id, edges);
+
+        // The neighbors of OR groups and negated edges are matched from this
+        // node rather than traversed to, but their own edges need planning
+        let optional_neighbor_ids = graph_query
+            .or_edge_filters
+            .get(&src_id)
+            .into_iter()
+            .flatten()
+            .flat_map(|or_edge_filters| or_edge_filters.edge_filters.iter())
+            .chain(
+                graph_query
+                    .negated_edge_filters
+                    .get(&src_id)
+                    .into_iter()
+                    .flatten(),
+            )
+            .map(|edge_filter| edge_filter.neighbor_query_id);
+        for neighbor_id in optional_neighbor_ids {
+            if visited.insert(neighbor_id) {
+                parents.insert(neighbor_id, src_id);
+                to_visit.push(neighbor_id);
+            }
+        }
+    }
+
+    // `edge_filters` holds every edge in both directions, so each closing edge
+    // is only recorded in one of them
+    let mut closing_edges = FxHashSet::default();
+    for ((src_id, edge_name), dst_ids) in graph_query.edge_filters.iter() {
+        if !visited.contains(src_id) {
+            continue;
+        }
+        let reverse_edge_name = &graph_query.edge_map[edge_name];
+        for dst_id in dst_ids {
+            let reverse = (*dst_id, reverse_edge_name.clone(), *src_id);
+            if tree_edges.contains(&reverse) || closing_edges.contains(&reverse) {
+                continue;
+            }
+            let edge = (*src_id, edge_name.clone(), *dst_id);
+            if tree_edges.contains(&edge) || !closing_edges.insert(edge.clone()) {
+                continue;
+            }
+            let binding_id = closest_common_ancestor(&parents, *src_id, *dst_id);
+            plan.closing_edges.entry(binding_id).or_default().push(edge);
+        }
+    }
+
+    plan
+}
+
+fn closest_common_ancestor(
+    parents: &FxHashMap<QueryId, QueryId>,
+    a: QueryId,
+    b: QueryId,
+) -> QueryId {
+    let mut ancestors = FxHashSet::default();
+    let mut query_id = Some(a);
+    while let Some(id) = query_id {
+        ancestors.insert(id);
+        query_id = parents.get(&id).copied();
+    }
+
+    let mut query_id = b;
+    while !ancestors.contains(&query_id) {
+        // Both ends are reachable from the start, which is an ancestor of both
+        query_id = parents[&query_id];
+    }
+    query_id
+}
+
+/// Returns up to `max_matches` distinct matches of the graph query in which
+/// the node with the given uid matches `node_properties_query`.
+///
+/// Unlike `fetch_node_with_edges`, which merges every matching neighbor into a
+/// single graph, each match here holds exactly one node per visited node query.
+/// A node with several matching neighbors for the same edge therefore yields
+/// one match per neighbor (and the cross product of those across its edges).
+///
+/// Closing edges are checked against the uids a match binds to both of their
+/// ends. A closing edge into an OR alternative that the match didn't take, or
+/// into a negated edge's neighbor, binds no uid and so isn't checked. Since a
+/// neighbor's matches are capped at `max_matches` before the closing edges
+/// above it are checked, a query with closing edges may return fewer than
+/// `max_matches` matches even when more exist.
+///
+/// Like `fetch_node_with_edges`, the node's edges are fetched in one round
+/// trip, and the neighbors across each edge are matched up to
+/// `max_concurrent_neighbor_fetches` at a time.
+#[allow(clippy::too_many_arguments)]
+#[async_recursion]
+pub async fn fetch_node_matches(
+    node_properties_query: &NodePropertyQuery,
+    graph_query: &GraphQuery,
+    regexes: &QueryRegexes,
+    plan: &TraversalPlan,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+    max_matches: usize,
+) -> Result<Vec<GraphMatch>, NodeQueryError> {
+    if max_matches == 0 {
+        return Ok(vec![]);
+    }
+
+    let node = match fetch_matching_node(
+        node_properties_query,
+        regexes,
+        uid,
+        tenant_id,
+        property_query_executor.clone(),
+    )
+    .await?
+    {
+        Some(node) => node,
+        None => return Ok(vec![]),
+    };
+
+    let query_id = node_properties_query.query_id;
+    let mut closing_edges = ClosingEdges {
+        closing_edges: plan
+            .closing_edges
+            .get(&query_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+        graph_query,
+        tenant_id,
+        property_query_executor: property_query_executor.clone(),
+        destinations: FxHashMap::default(),
+    };
+
+    let mut graph_match = GraphMatch::default();
+    graph_match.graph.add_node(node);
+    graph_match.bindings.insert(query_id, uid);
+    if query_id == graph_query.root_query_id {
+        graph_match.root_uid = Some(uid);
+    }
+    // A closing edge from the node query to itself is already bound
+    if !closing_edges.check(&mut graph_match).await? {
+        return Ok(vec![]);
+    }
+    let mut matches = vec![graph_match];
+
+    // A node missing any of its required edges isn't a match
+    let edge_rows = match fetch_edges(
+        node_properties_query,
+        uid,
+        graph_query,
+        tenant_id,
+        property_query_executor.clone(),
+    )
+    .await?
+    {
+        Some(edge_rows) => edge_rows,
+        None => return Ok(vec![]),
+    };
+
+    let edges = plan
+        .edges
+        .get(&query_id)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for (edge_name, neighbor_query_id) in edges {
+        // Every way of satisfying this edge, each of which is combined with
+        // every match we've built up so far
+        let neighbor_matches = fetch_neighbor_matches(
+            edge_name,
+            edge_rows_of(&edge_rows, edge_name),
+            *neighbor_query_id,
+            graph_query,
+            regexes,
+            plan,
+            uid,
+            tenant_id,
+            property_query_executor.clone(),
+            max_concurrent_neighbor_fetches,
+            max_matches,
+        )
+        .await?;
+        if neighbor_matches.is_empty() {
+            return Ok(vec![]);
+        }
+        matches =
+            combine_matches(&matches, &neighbor_matches, max_matches, &mut closing_edges).await?;
+        if matches.is_empty() {
+            return Ok(vec![]);
+        }
+    }
+
+    // Each OR group is satisfied by a match of any of its edges
+    for or_edge_filters in graph_query
+        .or_edge_filters
+        .get(&query_id)
+        .into_iter()
+        .flatten()
+    {
+        let mut neighbor_matches = vec![];
+        for edge_filter in or_edge_filters.edge_filters.iter() {
+            let remaining = max_matches - neighbor_matches.len();
+            if remaining == 0 {
+                break;
+            }
+            neighbor_matches.extend(
+                fetch_neighbor_matches(
+                    &edge_filter.edge_name,
+                    edge_rows_of(&edge_rows, &edge_filter.edge_name),
+                    edge_filter.neighbor_query_id,
+                    graph_query,
+                    regexes,
+                    plan,
+                    uid,
+                    tenant_id,
+                    property_query_executor.clone(),
+                    max_concurrent_neighbor_fetches,
+                    remaining,
+                )
+                .await?,
+            );
+        }
+        if neighbor_matches.is_empty() {
+            return Ok(vec![]);
+        }
+        matches =
+            combine_matches(&matches, &neighbor_matches, max_matches, &mut closing_edges).await?;
+        if matches.is_empty() {
+            return Ok(vec![]);
+        }
+    }
+
+    for edge_filter in graph_query
+        .negated_edge_filters
+        .get(&query_id)
+        .into_iter()
+        .flatten()
+    {
+        let neighbor_matches = fetch_neighbor_matches(
+            &edge_filter.edge_name,
+            edge_rows_of(&edge_rows, &edge_filter.edge_name),
+            edge_filter.neighbor_query_id,
+            graph_query,
+            regexes,
+            plan,
+            uid,
+            tenant_id,
+            property_query_executor.clone(),
+            max_concurrent_neighbor_fetches,
+            1,
+        )
+        .await?;
+        if !neighbor_matches.is_empty() {
+            return Ok(vec![]);
+        }
+    }
+
+    Ok(matches)
+}
+
+/// The edges of the given name, of which OR groups and negated edges may have
+/// none.
+fn edge_rows_of<'a>(
+    edge_rows: &'a FxHashMap<EdgeName, Vec<EdgeRow>>,
+    edge_name: &EdgeName,
+) -> &'a [EdgeRow] {
+    edge_rows
+        .get(edge_name)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Returns up to `max_matches` matches of the neighbor query across the edge,
+/// each including the edge (and its reverse) from `uid` to the neighbor.
+///
+/// The neighbors are matched concurrently, but their matches are taken in the
+/// order of `edge_rows`, so which matches make the cut doesn't depend on which
+/// neighbors happen to be matched first.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_neighbor_matches(
+    edge_name: &EdgeName,
+    edge_rows: &[EdgeRow],
+    neighbor_query_id: QueryId,
+    graph_query: &GraphQuery,
+    regexes: &QueryRegexes,
+    plan: &TraversalPlan,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    max_concurrent_neighbor_fetches: usize,
+    max_matches: usize,
+) -> Result<Vec<GraphMatch>, NodeQueryError> {
+    let neighbor_query = &graph_query.node_property_queries[&neighbor_query_id];
+    let reverse_edge_name = &graph_query.edge_map[edge_name];
+
+    // Each neighbor may make up every match, so each is capped at
+    // `max_matches`, and we stop matching more once we have enough
+    let neighbor_uids: Vec<Uid> = edge_rows.iter().map(|row| row.destination_uid).collect();
+    let mut neighbors = stream::iter(neighbor_uids)
+        .map(|neighbor_uid| {
+            let property_query_executor = property_query_executor.clone();
+            async move {
+                let matches = fetch_node_matches(
+                    neighbor_query,
+                    graph_query,
+                    regexes,
+                    plan,
+                    neighbor_uid,
+                    tenant_id,
+                    property_query_executor,
+                    max_concurrent_neighbor_fetches,
+                    max_matches,
+                )
+                .await?;
+                Ok::<_, NodeQueryError>((neighbor_uid, matches))
+            }
+        })
+        .buffered(max_concurrent_neighbor_fetches);
+
+    let mut neighbor_matches = vec![];
+    while let Some(neighbor) = neighbors.next().await {
+        let (neighbor_uid, matches) = neighbor?;
+        for mut neighbor_match in matches {
+            if neighbor_matches.len() == max_matches {
+                break;
+            }
+            neighbor_match
+                .graph
+                .add_edge(uid, edge_name.clone(), neighbor_uid);
+            neighbor_match
+                .graph
+                .add_edge(neighbor_uid, reverse_edge_name.clone(), uid);
+            neighbor_matches.push(neighbor_match);
+        }
+        if neighbor_matches.len() == max_matches {
+            break;
+        }
+    }
+
+    Ok(neighbor_matches)
+}
+
+/// The cross product of two sets of matches, up to `max_matches` of them.
+/// Combinations that fail a closing edge are dropped, and don't count towards
+/// `max_matches`.
+async fn combine_matches(
+    matches: &[GraphMatch],
+    neighbor_matches: &[GraphMatch],
+    max_matches: usize,
+    closing_edges: &mut ClosingEdges<'_>,
+) -> Result<Vec<GraphMatch>, NodeQueryError> {
+    let mut combined = Vec::with_capacity(max_matches.min(matches.len() * neighbor_matches.len()));
+    'combine: for graph_match in matches.iter() {
+        for neighbor_match in neighbor_matches.iter() {
+            if combined.len() == max_matches {
+                break 'combine;
+            }
+            let mut graph_match = graph_match.clone();
+            graph_match.merge(neighbor_match.clone());
+            if closing_edges.check(&mut graph_match).await? {
+                combined.push(graph_match);
+            }
+        }
+    }
+    Ok(combined)
+}
+
+/// The closing edges bound at a node query, along with the destinations of
+/// each edge we've fetched so far, so that matches sharing a node only fetch
+/// its edges once.
+struct ClosingEdges<'a> {
+    closing_edges: &'a [ClosingEdge],
+    graph_query: &'a GraphQuery,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    destinations: FxHashMap<(Uid, EdgeName), FxHashSet<Uid>>,
+}
+
+impl ClosingEdges<'_> {
+    /// Whether the match has every closing edge between the uids it binds to
+    /// their ends, adding those edges (and their reverses) to the match.
+    async fn check(&mut self, graph_match: &mut GraphMatch) -> Result<bool, NodeQueryError> {
+        for (src_id, edge_name, dst_id) in self.closing_edges {
+            let (src_uid, dst_uid) = match (
+                graph_match.bindings.get(src_id),
+                graph_match.bindings.get(dst_id),
+            ) {
+                (Some(src_uid), Some(dst_uid)) => (*src_uid, *dst_uid),
+                _ => continue,
+            };
+
+            let key = (src_uid, edge_name.clone());
+            if !self.destinations.contains_key(&key) {
+                let edge_rows = self
+                    .property_query_executor
+                    .get_edges(self.tenant_id, src_uid, edge_name)
+                    .await?
+                    .unwrap_or_default();
+                let destinations = edge_rows
+                    .into_iter()
+                    .map(|edge_row| edge_row.destination_uid)
+                    .collect();
+                self.destinations.insert(key.clone(), destinations);
+            }
+            if !self.destinations[&key].contains(&dst_uid) {
+                return Ok(false);
+            }
+
+            graph_match
+                .graph
+                .add_edge(src_uid, edge_name.clone(), dst_uid);
+            graph_match.graph.add_edge(
+                dst_uid,
+                self.graph_query.edge_map[edge_name].clone(),
+                src_uid,
+            );
+        }
+        Ok(true)
+    }
+}
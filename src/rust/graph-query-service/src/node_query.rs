@@ -4,17 +4,27 @@ use std::{
 };
 
 use async_recursion::async_recursion;
+use futures::{
+    stream,
+    StreamExt,
+};
 use rust_proto::graplinc::grapl::{
     api::graph_query_service::v1beta1::messages::{
+        AndIntFilters,
         AndStringFilters,
+        EdgeFilter,
         GraphQuery,
         GraphView,
+        IntCmp,
         NodePropertiesView,
         NodePropertyQuery,
+        OrEdgeFilters,
+        OrIntFilters,
         OrStringFilters,
         QueryId,
         StrCmp,
-        StringProperties,
+        UidFilter,
+        UidOperation,
     },
     common::v1beta1::types::{
         EdgeName,
@@ -31,6 +41,8 @@ use rustc_hash::{
 use crate::{
     property_query::{
         EdgeRow,
+        IntField,
+        IntValue,
         PropertyQueryError,
         PropertyQueryExecutor,
         StringField,
@@ -43,10 +55,55 @@ use crate::{
 pub enum NodeQueryError {
     #[error("Property query failed: {0:?}")]
     PropertyQueryError(#[from] PropertyQueryError),
+    #[error("Invalid regex in string filter: {0}")]
+    InvalidRegex(#[from] regex::Error),
+}
+
+/// The properties of a node that are referenced by a NodePropertyQuery's
+/// filters.
+#[derive(Debug, Clone, Default)]
+pub struct NodeProperties {
+    pub string_fields: Vec<StringField>,
+    pub int_fields: Vec<IntField>,
+}
+
+/// The regexes of a graph query's string filters, compiled once per query
+/// rather than for every node they're matched against.
+#[derive(Debug, Clone, Default)]
+pub struct QueryRegexes {
+    regexes: FxHashMap<String, regex::Regex>,
+}
+
+impl QueryRegexes {
+    /// Compiles every regex in the query, so that an invalid one is rejected
+    /// before any node is fetched.
+    pub fn compile(graph_query: &GraphQuery) -> Result<Self, NodeQueryError> {
+        let mut regexes = FxHashMap::default();
+        let string_filters = graph_query
+            .node_property_queries
+            .values()
+            .flat_map(|node_property_query| node_property_query.string_filters.values())
+            .flat_map(|or_filters| or_filters.and_string_filters.iter())
+            .flat_map(|and_filters| and_filters.string_filters.iter());
+        for string_filter in string_filters {
+            if let StrCmp::Regex(pattern, _) = StrCmp::from(string_filter) {
+                if !regexes.contains_key(pattern) {
+                    regexes.insert(pattern.to_owned(), regex::Regex::new(pattern)?);
+                }
+            }
+        }
+        Ok(Self { regexes })
+    }
+
+    fn get(&self, pattern: &str) -> &regex::Regex {
+        // Every pattern was compiled from the same query it's matched for
+        &self.regexes[pattern]
+    }
 }
 
 pub(crate) fn match_property(
     node_properties_query: &NodePropertyQuery,
+    regexes: &QueryRegexes,
     property_name: &PropertyName,
     property_value: &str,
 ) -> bool {
@@ -65,6 +122,13 @@ pub(crate) fn match_property(
                     (true, true) => continue 'outer,
                     (_, _) => (),
                 },
+                StrCmp::Regex(pattern, negated) => {
+                    match (negated, regexes.get(pattern).is_match(property_value)) {
+                        (false, false) => continue 'outer,
+                        (true, true) => continue 'outer,
+                        (_, _) => (),
+                    }
+                }
                 StrCmp::Has => (),
             };
         }
@@ -74,31 +138,71 @@ pub(crate) fn match_property(
     false
 }
 
+pub(crate) fn match_int_property(
+    node_properties_query: &NodePropertyQuery,
+    property_name: &PropertyName,
+    property_value: IntValue,
+) -> bool {
+    let property_value = property_value.as_i128();
+    'outer: for or_filters in &node_properties_query.int_filters[property_name].and_int_filters {
+        for and_filter in &or_filters.int_filters {
+            let (matched, negated) = match IntCmp::from(and_filter) {
+                IntCmp::Eq(to, negated) => (property_value == to as i128, negated),
+                IntCmp::Lt(to, negated) => (property_value < to as i128, negated),
+                IntCmp::Lte(to, negated) => (property_value <= to as i128, negated),
+                IntCmp::Gt(to, negated) => (property_value > to as i128, negated),
+                IntCmp::Gte(to, negated) => (property_value >= to as i128, negated),
+                IntCmp::Has => (true, false),
+            };
+            if matched == negated {
+                continue 'outer;
+            }
+        }
+        return true;
+    }
+
+    false
+}
+
+/// A node matches if any of the query's uid filters match it. A query without
+/// uid filters matches every node.
+pub(crate) fn match_uid(node_properties_query: &NodePropertyQuery, uid: Uid) -> bool {
+    let uid_filters = &node_properties_query.uid_filters.uid_filters;
+    uid_filters.is_empty()
+        || uid_filters
+            .iter()
+            .any(|uid_filter| match uid_filter.operation {
+                UidOperation::Equal => uid_filter.value == uid,
+            })
+}
+
 #[tracing::instrument(skip(node_properties_query, property_query_executor))]
 pub async fn fetch_node_properties(
     node_properties_query: &NodePropertyQuery,
     uid: Uid,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
-) -> Result<Option<Vec<StringField>>, NodeQueryError> {
-    let mut fields = vec![];
-    if !node_properties_query.string_filters.is_empty() {
-        let mut filter_names: FxHashSet<_> = node_properties_query.string_filters.keys().collect();
-
-        for prop_name in node_properties_query.string_filters.keys() {
-            let property = property_query_executor
-                .get_immutable_string(tenant_id, uid, prop_name)
-                .await?;
-            match property {
-                Some(p) => fields.push(p),
-                None => return Ok(None),
-            }
-            filter_names.remove(prop_name);
+) -> Result<Option<NodeProperties>, NodeQueryError> {
+    let mut fields = NodeProperties::default();
+
+    // Any property that doesn't exist means this node is not a match
+    for prop_name in node_properties_query.string_filters.keys() {
+        let property = property_query_executor
+            .get_string(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(p) => fields.string_fields.push(p),
+            None => return Ok(None),
         }
+    }
 
-        if !filter_names.is_empty() {
-            // some values didn't exist, not a match
-            return Ok(None);
+    for prop_name in node_properties_query.int_filters.keys() {
+        let property = property_query_executor
+            .get_int(tenant_id, uid, prop_name)
+            .await?;
+        match property {
+            Some(p) => fields.int_fields.push(p),
+            None => return Ok(None),
         }
     }
 
@@ -113,69 +217,155 @@ pub async fn fetch_edges(
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
 ) -> Result<Option<FxHashMap<EdgeName, Vec<EdgeRow>>>, NodeQueryError> {
-    let mut edge_rows = FxHashMap::default();
-    for (src_id, edge_name) in graph_query.edge_filters.keys() {
-        if *src_id != node_properties_query.query_id {
-            continue;
+    let query_id = node_properties_query.query_id;
+    let required_edge_names: Vec<EdgeName> = graph_query
+        .edge_filters
+        .keys()
+        .filter(|(src_id, _)| *src_id == query_id)
+        .map(|(_, edge_name)| edge_name.clone())
+        .collect();
+
+    // The edges of OR groups and negated edges are fetched in the same round
+    // trip, but they don't have to exist
+    let mut edge_names = required_edge_names.clone();
+    let optional_edge_filters = graph_query
+        .or_edge_filters
+        .get(&query_id)
+        .into_iter()
+        .flatten()
+        .flat_map(|or_edge_filters| or_edge_filters.edge_filters.iter())
+        .chain(
+            graph_query
+                .negated_edge_filters
+                .get(&query_id)
+                .into_iter()
+                .flatten(),
+        );
+    for edge_filter in optional_edge_filters {
+        if !edge_names.contains(&edge_filter.edge_name) {
+            edge_names.push(edge_filter.edge_name.clone());
         }
+    }
 
-        let rows = property_query_executor
-            .get_edges(tenant_id, uid, edge_name)
-            .await?;
+    let edge_rows = property_query_executor
+        .get_edges_batch(tenant_id, uid, &edge_names)
+        .await?;
 
-        let rows = match rows {
-            None => return Ok(None),
-            Some(rows) => rows,
-        };
-        debug_assert!(!rows.is_empty());
-
-        println!("edge name {}, rows {:?}", edge_name, rows);
-        edge_rows.insert(edge_name.to_owned(), rows);
+    // Any required edge that doesn't exist means this node is not a match
+    if required_edge_names
+        .iter()
+        .any(|edge_name| !edge_rows.contains_key(edge_name))
+    {
+        return Ok(None);
     }
 
     Ok(Some(edge_rows))
 }
 
-#[async_recursion]
-pub async fn fetch_node_with_edges(
-    node_properties_query: &NodePropertyQuery,
+/// Matches the neighbors across one of a node's OR or negated edges, returning
+/// the uid and graph of each neighbor that matches. Unlike the neighbors across
+/// required edges, these are matched with their own `Visited`, since a neighbor
+/// that doesn't match mustn't short circuit the rest of the query. With
+/// `first_only` set, stops at the first match.
+#[allow(clippy::too_many_arguments)]
+async fn fetch_isolated_neighbors(
+    edge_filter: &EdgeFilter,
+    edge_rows: &[EdgeRow],
     graph_query: &GraphQuery,
-    uid: Uid,
+    regexes: &QueryRegexes,
     tenant_id: uuid::Uuid,
     property_query_executor: PropertyQueryExecutor,
-    visited: Visited,
     x_short_circuit: ShortCircuit,
-    root_node_uid: &mut Option<Uid>,
-) -> Result<Option<GraphView>, NodeQueryError> {
-    if visited.get_short_circuit() || x_short_circuit.get_short_circuit() {
+    max_concurrent_neighbor_fetches: usize,
+    first_only: bool,
+) -> Result<Vec<(Uid, GraphView)>, NodeQueryError> {
+    let neighbor_query = &graph_query.node_property_queries[&edge_filter.neighbor_query_id];
+
+    let neighbor_uids: Vec<Uid> = edge_rows.iter().map(|row| row.destination_uid).collect();
+    let mut neighbors = stream::iter(neighbor_uids)
+        .map(|neighbor_uid| {
+            let property_query_executor = property_query_executor.clone();
+            let x_short_circuit = x_short_circuit.clone();
+            async move {
+                let neighbor = fetch_node_with_edges(
+                    neighbor_query,
+                    graph_query,
+                    regexes,
+                    neighbor_uid,
+                    tenant_id,
+                    property_query_executor,
+                    Visited::new(),
+                    x_short_circuit,
+                    max_concurrent_neighbor_fetches,
+                    &mut None,
+                )
+                .await?;
+                Ok::<_, NodeQueryError>(neighbor.map(|neighbor| (neighbor_uid, neighbor)))
+            }
+        })
+        .buffer_unordered(max_concurrent_neighbor_fetches);
+
+    let mut matches = vec![];
+    while let Some(neighbor) = neighbors.next().await {
+        if let Some(neighbor) = neighbor? {
+            matches.push(neighbor);
+            if first_only {
+                break;
+            }
+        }
+    }
+
+    Ok(matches)
+}
+
+/// Fetches the node with the given uid if it matches the node property query,
+/// ignoring the query's edges. The returned view holds the properties that the
+/// query filters on.
+#[tracing::instrument(skip(node_properties_query, regexes, property_query_executor))]
+pub async fn fetch_matching_node(
+    node_properties_query: &NodePropertyQuery,
+    regexes: &QueryRegexes,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+) -> Result<Option<NodePropertiesView>, NodeQueryError> {
+    // uid filters are checked first, since they don't require a round trip
+    if !match_uid(node_properties_query, uid) {
         return Ok(None);
     }
 
-    let mut node = NodePropertiesView::new(
-        uid,
-        node_properties_query.node_type.clone(),
-        StringProperties::default(),
-    );
+    property_query_executor.visit_node()?;
+
+    // A node of the wrong type may still share property names with the
+    // queried type, so the type has to be checked explicitly
+    let node_type = property_query_executor
+        .get_node_type(tenant_id, uid)
+        .await?;
+    if node_type.as_ref() != Some(&node_properties_query.node_type) {
+        return Ok(None);
+    }
+
+    let mut node = NodePropertiesView::new(uid, node_properties_query.node_type.clone());
 
     let node_properties = fetch_node_properties(
         node_properties_query,
         uid,
         tenant_id,
-        property_query_executor.clone(),
+        property_query_executor,
     )
     .await?;
 
     let node_properties = match node_properties {
         None => {
-            visited.set_short_circuit();
             return Ok(None);
         }
         Some(node_properties) => node_properties,
     };
 
-    for node_property in node_properties.iter() {
+    for node_property in node_properties.string_fields.iter() {
         if match_property(
             node_properties_query,
+            regexes,
             &node_property.populated_field,
             &node_property.value,
         ) {
@@ -184,11 +374,69 @@ pub async fn fetch_node_with_edges(
                 node_property.value.clone(),
             );
         } else {
-            visited.set_short_circuit();
             return Ok(None);
         }
     }
 
+    for node_property in node_properties.int_fields.iter() {
+        if !match_int_property(
+            node_properties_query,
+            &node_property.populated_field,
+            node_property.value,
+        ) {
+            return Ok(None);
+        }
+        match node_property.value {
+            IntValue::Int64(value) => {
+                node.add_int64_property(node_property.populated_field.clone(), value)
+            }
+            IntValue::UInt64(value) => {
+                node.add_uint64_property(node_property.populated_field.clone(), value)
+            }
+        }
+    }
+
+    tracing::debug!(
+        message = "Retrieved node indices",
+        count = node_properties.string_fields.len() + node_properties.int_fields.len(),
+    );
+
+    Ok(Some(node))
+}
+
+#[async_recursion]
+pub async fn fetch_node_with_edges(
+    node_properties_query: &NodePropertyQuery,
+    graph_query: &GraphQuery,
+    regexes: &QueryRegexes,
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+    property_query_executor: PropertyQueryExecutor,
+    visited: Visited,
+    x_short_circuit: ShortCircuit,
+    max_concurrent_neighbor_fetches: usize,
+    root_node_uid: &mut Option<Uid>,
+) -> Result<Option<GraphView>, NodeQueryError> {
+    if x_short_circuit.get_short_circuit() {
+        return Ok(None);
+    }
+
+    // A node which doesn't match the query only rules itself out, not the
+    // other candidates for the same query (e.g. its siblings), so neither a
+    // mismatch nor a missing edge short circuits `visited`.
+    let node = match fetch_matching_node(
+        node_properties_query,
+        regexes,
+        uid,
+        tenant_id,
+        property_query_executor.clone(),
+    )
+    .await?
+    {
+        Some(node) => node,
+        None => return Ok(None),
+    };
+
     if node_properties_query.query_id == graph_query.root_query_id {
         *root_node_uid = Some(uid);
     }
@@ -196,11 +444,6 @@ pub async fn fetch_node_with_edges(
     let mut graph = GraphView::default();
     graph.add_node(node);
 
-    tracing::debug!(
-        message = "Retrieved node indices",
-        count = node_properties.len(),
-    );
-
     if x_short_circuit.get_short_circuit() {
         return Ok(None);
     }
@@ -216,10 +459,7 @@ pub async fn fetch_node_with_edges(
     .await?
     {
         Some(edges) => edges,
-        None => {
-            visited.set_short_circuit();
-            return Ok(None);
-        }
+        None => return Ok(None),
     };
 
     for ((src_id, edge_name), edge_queries) in graph_query.edge_filters.iter() {
@@ -247,29 +487,48 @@ pub async fn fetch_node_with_edges(
                 continue;
             }
 
-            // When we support 'OR' logic on edges we'll add that logic here
+            // The neighbors are fetched concurrently, so they can't share
+            // `root_node_uid` and each gets its own
+            let neighbor_uids: Vec<Uid> = edge_rows.iter().map(|row| row.destination_uid).collect();
+            let mut neighbors = stream::iter(neighbor_uids)
+                .map(|neighbor_uid| {
+                    let property_query_executor = property_query_executor.clone();
+                    let visited = visited.fork();
+                    let x_short_circuit = x_short_circuit.clone();
+                    async move {
+                        let mut neighbor_root_uid = None;
+                        let neighbors = fetch_node_with_edges(
+                            edge_query,
+                            graph_query,
+                            regexes,
+                            neighbor_uid,
+                            tenant_id,
+                            property_query_executor,
+                            visited,
+                            x_short_circuit,
+                            max_concurrent_neighbor_fetches,
+                            &mut neighbor_root_uid,
+                        )
+                        .await?;
+                        Ok::<_, NodeQueryError>(
+                            neighbors.map(|neighbors| (neighbors, neighbor_root_uid)),
+                        )
+                    }
+                })
+                .buffer_unordered(max_concurrent_neighbor_fetches);
 
             let mut any = false;
-            for edge_row in edge_rows {
-                // we can do this in parallel
+            while let Some(neighbors) = neighbors.next().await {
                 if x_short_circuit.get_short_circuit() {
                     return Ok(None);
                 }
-                let neighbors = match fetch_node_with_edges(
-                    edge_query,
-                    graph_query,
-                    edge_row.destination_uid,
-                    tenant_id,
-                    property_query_executor.clone(),
-                    visited.clone(),
-                    x_short_circuit.clone(),
-                    root_node_uid,
-                )
-                .await?
-                {
+                let (neighbors, neighbor_root_uid) = match neighbors? {
                     Some(neighbors) => neighbors,
                     None => continue,
                 };
+                if neighbor_root_uid.is_some() {
+                    *root_node_uid = neighbor_root_uid;
+                }
                 any = true;
                 for neighbor in neighbors.nodes.keys() {
                     graph.add_edge(uid, edge_name.to_owned(), *neighbor);
@@ -278,13 +537,79 @@ pub async fn fetch_node_with_edges(
                 graph.merge(neighbors);
             }
             if !any {
-                // if a given query has no matches, return
-                visited.set_short_circuit();
                 return Ok(None);
             }
         }
     }
 
+    // Like a mismatching property, a missing OR alternative or a present
+    // negated edge only rules out this node, so neither short circuits
+    let query_id = node_properties_query.query_id;
+    for or_edge_filters in graph_query
+        .or_edge_filters
+        .get(&query_id)
+        .into_iter()
+        .flatten()
+    {
+        let mut any = false;
+        for edge_filter in or_edge_filters.edge_filters.iter() {
+            let neighbors = fetch_isolated_neighbors(
+                edge_filter,
+                edges
+                    .get(&edge_filter.edge_name)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                graph_query,
+                regexes,
+                tenant_id,
+                property_query_executor.clone(),
+                x_short_circuit.clone(),
+                max_concurrent_neighbor_fetches,
+                false,
+            )
+            .await?;
+            for (neighbor_uid, neighbor) in neighbors {
+                any = true;
+                graph.add_edge(uid, edge_filter.edge_name.to_owned(), neighbor_uid);
+                graph.add_edge(
+                    neighbor_uid,
+                    graph_query.edge_map[&edge_filter.edge_name].to_owned(),
+                    uid,
+                );
+                graph.merge(neighbor);
+            }
+        }
+        if x_short_circuit.get_short_circuit() || !any {
+            return Ok(None);
+        }
+    }
+
+    for edge_filter in graph_query
+        .negated_edge_filters
+        .get(&query_id)
+        .into_iter()
+        .flatten()
+    {
+        let neighbors = fetch_isolated_neighbors(
+            edge_filter,
+            edges
+                .get(&edge_filter.edge_name)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+            graph_query,
+            regexes,
+            tenant_id,
+            property_query_executor.clone(),
+            x_short_circuit.clone(),
+            max_concurrent_neighbor_fetches,
+            true,
+        )
+        .await?;
+        if x_short_circuit.get_short_circuit() || !neighbors.is_empty() {
+            return Ok(None);
+        }
+    }
+
     Ok(Some(graph))
 }
 
@@ -312,6 +637,8 @@ impl NodeQuery {
             node_property_queries,
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            or_edge_filters: Default::default(),
+            negated_edge_filters: Default::default(),
         };
 
         Self {
@@ -338,6 +665,37 @@ impl NodeQuery {
         self
     }
 
+    pub fn with_int_comparisons(
+        &mut self,
+        property_name: PropertyName,
+        comparisons: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .int_filters
+            .entry(property_name)
+            .or_insert_with(OrIntFilters::new)
+            .push(comparisons.into());
+        drop(inner);
+        self
+    }
+
+    pub fn with_uid_filter(&mut self, uid_filter: UidFilter) -> &mut Self {
+        let mut inner = self.graph.as_mut().unwrap().borrow_mut();
+        inner
+            .node_property_queries
+            .get_mut(&self.query_id)
+            .unwrap()
+            .uid_filters
+            .uid_filters
+            .push(uid_filter);
+        drop(inner);
+        self
+    }
+
     pub fn overwrite_string_comparisons(
         &mut self,
         property_name: PropertyName,
@@ -436,6 +794,82 @@ impl NodeQuery {
         self
     }
 
+    /// Requires at least one of the edges added by `init_edges` to match.
+    /// Unlike with `with_edge_to`, the neighbors aren't connected back to this
+    /// node, so a query can't start from one of them.
+    pub fn with_any_edge(&mut self, init_edges: impl FnOnce(&mut AnyEdge<'_>)) -> &mut Self {
+        let mut any_edge = AnyEdge {
+            source: self,
+            or_edge_filters: OrEdgeFilters::new(),
+        };
+        init_edges(&mut any_edge);
+        let or_edge_filters = any_edge.or_edge_filters;
+
+        let graph = self.graph.as_mut().unwrap();
+        graph
+            .borrow_mut()
+            .or_edge_filters
+            .entry(self.query_id)
+            .or_default()
+            .push(or_edge_filters);
+        self
+    }
+
+    /// Requires that no neighbor across `edge_name` matches the neighbor query
+    /// built by `init_edge`, e.g. a process with no parent process. As with
+    /// `with_any_edge`, the neighbor isn't connected back to this node.
+    pub fn with_no_edge_to(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        node_type: NodeType,
+        init_edge: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        let neighbor_query_id = self.new_neighbor(node_type, init_edge);
+
+        let graph = self.graph.as_mut().unwrap();
+        let mut graph = graph.borrow_mut();
+        graph
+            .negated_edge_filters
+            .entry(self.query_id)
+            .or_default()
+            .push(EdgeFilter {
+                edge_name: edge_name.clone(),
+                neighbor_query_id,
+            });
+        graph
+            .edge_map
+            .insert(edge_name.clone(), reverse_edge_name.clone());
+        graph.edge_map.insert(reverse_edge_name, edge_name);
+        drop(graph);
+        self
+    }
+
+    /// Adds an unconnected node query for a neighbor of this node, which
+    /// `init_neighbor` can add filters and edges to.
+    fn new_neighbor(
+        &mut self,
+        node_type: NodeType,
+        init_neighbor: impl FnOnce(&mut Self),
+    ) -> QueryId {
+        let neighbor_query_id = QueryId::default();
+
+        {
+            let graph = self.graph.as_mut().unwrap();
+            let mut graph = graph.borrow_mut();
+            graph.add_node(neighbor_query_id, node_type);
+        }
+
+        let mut neighbor = Self {
+            query_id: neighbor_query_id,
+            graph: self.graph.clone(),
+        };
+
+        init_neighbor(&mut neighbor);
+        neighbor.graph = None;
+        neighbor_query_id
+    }
+
     pub fn build(&mut self) -> GraphQuery {
         // This will panic if you have not attached this node to a graph ie: it must be attached
         // to a root node somewhere
@@ -447,6 +881,39 @@ impl NodeQuery {
             node_property_queries: Default::default(),
             edge_filters: Default::default(),
             edge_map: Default::default(),
+            or_edge_filters: Default::default(),
+            negated_edge_filters: Default::default(),
         })
     }
 }
+
+/// The alternatives of an OR group of edges, see `NodeQuery::with_any_edge`.
+pub struct AnyEdge<'a> {
+    source: &'a mut NodeQuery,
+    or_edge_filters: OrEdgeFilters,
+}
+
+impl AnyEdge<'_> {
+    pub fn with_edge_to(
+        &mut self,
+        edge_name: EdgeName,
+        reverse_edge_name: EdgeName,
+        node_type: NodeType,
+        init_edge: impl FnOnce(&mut NodeQuery),
+    ) -> &mut Self {
+        let neighbor_query_id = self.source.new_neighbor(node_type, init_edge);
+        self.or_edge_filters.push(EdgeFilter {
+            edge_name: edge_name.clone(),
+            neighbor_query_id,
+        });
+
+        let graph = self.source.graph.as_mut().unwrap();
+        let mut graph = graph.borrow_mut();
+        graph
+            .edge_map
+            .insert(edge_name.clone(), reverse_edge_name.clone());
+        graph.edge_map.insert(reverse_edge_name, edge_name);
+        drop(graph);
+        self
+    }
+}
@@ -1,19 +1,27 @@
 use std::{
     fmt::Debug,
     hash::Hash,
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+    },
+    time::Duration,
 };
 
 use moka::future::{
     Cache,
     CacheBuilder,
 };
-use rust_proto::graplinc::grapl::common::v1beta1::types::Uid;
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub enum CacheMatch {
-    Matched,
-    NotMatched,
-    CouldMatch,
-}
+use rust_proto::graplinc::grapl::common::v1beta1::types::{
+    NodeType,
+    PropertyName,
+    Uid,
+};
+
+use crate::property_query::IntValue;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Key {
@@ -22,11 +30,68 @@ pub struct Key {
     tenant_id: uuid::Uuid,
 }
 
+impl Key {
+    pub fn new(tenant_id: uuid::Uuid, uid: Uid, property_name: &PropertyName) -> Self {
+        Self {
+            property_name: property_name.value.clone(),
+            uid,
+            tenant_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeTypeKey {
+    uid: Uid,
+    tenant_id: uuid::Uuid,
+}
+
+impl NodeTypeKey {
+    pub fn new(tenant_id: uuid::Uuid, uid: Uid) -> Self {
+        Self { uid, tenant_id }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Caches values read from the immutable property tables, and each node's
+/// type. Only immutable properties may be cached, since they can never change
+/// once written. A node's type is likewise set once, when it's created.
+///
+/// They can still be deleted though, and graph-mutation has no way to reach
+/// into this cache when they are, so entries expire after a time to live.
+///
+/// Only lookups of values that can be cached count towards the hit rate: a
+/// hit is counted when a lookup finds a value, and a miss when a value it
+/// didn't find is inserted. Lookups of mutable, max or min properties, and of
+/// properties that don't exist, are never counted.
 #[derive(Clone)]
 pub struct PropertyCache {
     string_cache: Cache<Key, String>,
     int_cache: Cache<Key, i64>,
     uint_cache: Cache<Key, u64>,
+    node_type_cache: Cache<NodeTypeKey, NodeType>,
+    counters: Arc<CacheCounters>,
 }
 
 impl PropertyCache {
@@ -34,11 +99,125 @@ impl PropertyCache {
         string_cache: Cache<Key, String>,
         int_cache: Cache<Key, i64>,
         uint_cache: Cache<Key, u64>,
+        node_type_cache: Cache<NodeTypeKey, NodeType>,
     ) -> Self {
         Self {
             string_cache,
             int_cache,
             uint_cache,
+            node_type_cache,
+            counters: Arc::new(CacheCounters::default()),
         }
     }
+
+    /// Each of the string, int, uint and node type caches holds at most
+    /// `capacity` entries, each for at most `time_to_live`.
+    pub fn with_capacity(capacity: u64, time_to_live: Duration) -> Self {
+        Self::new(
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+            CacheBuilder::new(capacity)
+                .time_to_live(time_to_live)
+                .build(),
+        )
+    }
+
+    pub fn get_string(&self, key: &Key) -> Option<String> {
+        self.record_hit(self.string_cache.get(key))
+    }
+
+    pub async fn insert_string(&self, key: Key, value: String) {
+        self.record_miss();
+        self.string_cache.insert(key, value).await
+    }
+
+    pub fn get_int(&self, key: &Key) -> Option<IntValue> {
+        let value = match self.int_cache.get(key) {
+            Some(value) => Some(IntValue::Int64(value)),
+            None => self.uint_cache.get(key).map(IntValue::UInt64),
+        };
+        self.record_hit(value)
+    }
+
+    pub async fn insert_int(&self, key: Key, value: IntValue) {
+        self.record_miss();
+        match value {
+            IntValue::Int64(value) => self.int_cache.insert(key, value).await,
+            IntValue::UInt64(value) => self.uint_cache.insert(key, value).await,
+        }
+    }
+
+    pub fn get_node_type(&self, key: &NodeTypeKey) -> Option<NodeType> {
+        self.record_hit(self.node_type_cache.get(key))
+    }
+
+    pub async fn insert_node_type(&self, key: NodeTypeKey, node_type: NodeType) {
+        self.record_miss();
+        self.node_type_cache.insert(key, node_type).await
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn report_stats(&self) {
+        let stats = self.stats();
+        tracing::info!(
+            message = "property cache stats",
+            hits = stats.hits,
+            misses = stats.misses,
+            hit_rate = stats.hit_rate(),
+            string_entries = self.string_cache.entry_count(),
+            int_entries = self.int_cache.entry_count(),
+            uint_entries = self.uint_cache.entry_count(),
+            node_type_entries = self.node_type_cache.entry_count(),
+        );
+    }
+
+    fn record_hit<T>(&self, value: Option<T>) -> Option<T> {
+        if value.is_some() {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    fn record_miss(&self) {
+        self.counters.misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_only_cacheable_lookups_are_counted() {
+        let property_cache = PropertyCache::with_capacity(10, Duration::from_secs(60));
+        let tenant_id = uuid::Uuid::new_v4();
+        let uid = Uid::from_u64(1).unwrap();
+        let immutable = Key::new(tenant_id, uid, &"immutable".try_into().unwrap());
+        let max = Key::new(tenant_id, uid, &"max".try_into().unwrap());
+
+        // Looked up, found in the immutable table and inserted
+        assert_eq!(property_cache.get_int(&immutable), None);
+        property_cache
+            .insert_int(immutable.clone(), IntValue::Int64(1))
+            .await;
+        assert_eq!(property_cache.get_int(&immutable), Some(IntValue::Int64(1)));
+
+        // Looked up and found in a max or min table, so never inserted
+        assert_eq!(property_cache.get_int(&max), None);
+
+        assert_eq!(property_cache.stats(), CacheStats { hits: 1, misses: 1 });
+    }
 }
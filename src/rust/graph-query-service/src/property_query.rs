@@ -1,25 +1,69 @@
 use std::sync::Arc;
 
+use futures::{
+    future::try_join_all,
+    stream,
+    StreamExt,
+    TryStreamExt,
+};
 use rust_proto::{
-    graplinc::grapl::common::v1beta1::types::{
-        EdgeName,
-        PropertyName,
-        Uid,
+    graplinc::grapl::{
+        api::graph_query_service::v1beta1::messages::{
+            NodePropertiesView,
+            PropertyValueFilter,
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
     },
     SerDeError,
 };
+use rustc_hash::FxHashMap;
 use scylla::{
-    cql_to_rust::FromRowError,
+    cql_to_rust::{
+        FromRow,
+        FromRowError,
+    },
+    frame::value::ValueList,
+    query::Query,
     transport::{
         errors::QueryError,
         query_result::MaybeFirstRowTypedError,
     },
     CachingSession,
+    QueryResult,
 };
-
-use crate::table_names::{
+use scylla_table_names::{
     tenant_keyspace_name,
+    EDGES_TABLE_NAME,
+    IMM_I_64_INDEX_TABLE_NAME,
+    IMM_I_64_TABLE_NAME,
+    IMM_STRING_INDEX_TABLE_NAME,
     IMM_STRING_TABLE_NAME,
+    IMM_U_64_INDEX_TABLE_NAME,
+    IMM_U_64_TABLE_NAME,
+    MAX_I_64_TABLE_NAME,
+    MAX_U_64_TABLE_NAME,
+    MIN_I_64_TABLE_NAME,
+    MIN_U_64_TABLE_NAME,
+    MUT_F_64_TABLE_NAME,
+    MUT_STRING_TABLE_NAME,
+    NODE_TYPE_TABLE_NAME,
+};
+
+use crate::{
+    property_cache::{
+        Key,
+        NodeTypeKey,
+        PropertyCache,
+    },
+    query_budget::{
+        QueryBudget,
+        QueryBudgetExceeded,
+    },
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -38,6 +82,12 @@ pub enum PropertyQueryError {
     },
     #[error("Invalid stored edge name {0}")]
     InvalidStoredEdgeName(#[from] SerDeError),
+    #[error("Invalid stored property name {0}")]
+    InvalidStoredPropertyName(SerDeError),
+    #[error("Invalid uid '{0}' in property index")]
+    InvalidIndexedUid(i64),
+    #[error("{0}")]
+    QueryBudgetExceeded(#[from] QueryBudgetExceeded),
 }
 
 #[derive(Debug, Clone)]
@@ -56,55 +106,272 @@ pub struct StringField {
     pub value: String,
 }
 
+/// Signed and unsigned properties live in separate tables, but are filtered
+/// with the same `IntFilter`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntValue {
+    Int64(i64),
+    UInt64(u64),
+}
+
+impl IntValue {
+    /// Widened so that any i64 filter value can be compared against any
+    /// stored value without overflow.
+    pub fn as_i128(self) -> i128 {
+        match self {
+            IntValue::Int64(value) => value as i128,
+            IntValue::UInt64(value) => value as i128,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IntField {
+    pub uid: Uid,
+    pub populated_field: PropertyName,
+    pub value: IntValue,
+}
+
+/// How many of the nodes found through a property index have their current
+/// value checked at once.
+const MAX_CONCURRENT_INDEX_CHECKS: usize = 32;
+
 // We should push our filtering logic into here
 
+/// Every query is addressed by its table's partition key (and, where there is
+/// one, the leading clustering key), never with ALLOW FILTERING. Queries are
+/// built per tenant keyspace, and the `CachingSession` prepares each distinct
+/// query once and reuses the prepared statement from then on.
+///
+/// An executor with a `QueryBudget` charges every query it sends to Scylla
+/// against it, failing once the budget's round trips are used up.
 #[derive(Clone)]
 pub struct PropertyQueryExecutor {
     scylla_client: Arc<CachingSession>,
+    property_cache: PropertyCache,
+    query_budget: Option<QueryBudget>,
 }
 
 impl PropertyQueryExecutor {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
-        Self { scylla_client }
+    pub fn new(scylla_client: Arc<CachingSession>, property_cache: PropertyCache) -> Self {
+        Self {
+            scylla_client,
+            property_cache,
+            query_budget: None,
+        }
+    }
+
+    /// A copy of this executor which charges its work to `query_budget`.
+    pub fn with_budget(&self, query_budget: QueryBudget) -> Self {
+        Self {
+            query_budget: Some(query_budget),
+            ..self.clone()
+        }
+    }
+
+    /// Charges a node visit to the query budget, if there is one.
+    pub fn visit_node(&self) -> Result<(), PropertyQueryError> {
+        if let Some(query_budget) = &self.query_budget {
+            query_budget.visit_node()?;
+        }
+        Ok(())
     }
 
-    pub async fn get_immutable_string(
+    async fn execute(
+        &self,
+        query: Query,
+        values: impl ValueList,
+    ) -> Result<QueryResult, PropertyQueryError> {
+        if let Some(query_budget) = &self.query_budget {
+            query_budget.round_trip()?;
+        }
+        Ok(self.scylla_client.execute(query, &values).await?)
+    }
+
+    /// Looks the property up in the immutable string table, then the mutable
+    /// one. Only immutable values are cached.
+    pub async fn get_string(
         &self,
         tenant_id: uuid::Uuid,
         uid: Uid,
         property_name: &PropertyName,
     ) -> Result<Option<StringField>, PropertyQueryError> {
+        let key = Key::new(tenant_id, uid, property_name);
+        let value = match self.property_cache.get_string(&key) {
+            Some(value) => value,
+            None => {
+                let value = self
+                    .get_property_value::<String>(
+                        tenant_id,
+                        uid,
+                        property_name,
+                        IMM_STRING_TABLE_NAME,
+                    )
+                    .await?;
+                match value {
+                    Some(value) => {
+                        self.property_cache.insert_string(key, value.clone()).await;
+                        value
+                    }
+                    None => {
+                        match self
+                            .get_property_value::<String>(
+                                tenant_id,
+                                uid,
+                                property_name,
+                                MUT_STRING_TABLE_NAME,
+                            )
+                            .await?
+                        {
+                            Some(value) => value,
+                            None => return Ok(None),
+                        }
+                    }
+                }
+            }
+        };
+
+        Ok(Some(StringField {
+            uid,
+            populated_field: property_name.clone(),
+            value,
+        }))
+    }
+
+    /// Looks the property up in all of the signed and unsigned tables at once,
+    /// returning the value from the first of them (in the order below) that
+    /// has one.
+    pub async fn get_int(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+    ) -> Result<Option<IntField>, PropertyQueryError> {
+        const INT_TABLES: [(&str, bool); 6] = [
+            (IMM_I_64_TABLE_NAME, false),
+            (MAX_I_64_TABLE_NAME, false),
+            (MIN_I_64_TABLE_NAME, false),
+            (IMM_U_64_TABLE_NAME, true),
+            (MAX_U_64_TABLE_NAME, true),
+            (MIN_U_64_TABLE_NAME, true),
+        ];
+
+        let key = Key::new(tenant_id, uid, property_name);
+        if let Some(value) = self.property_cache.get_int(&key) {
+            return Ok(Some(IntField {
+                uid,
+                populated_field: property_name.clone(),
+                value,
+            }));
+        }
+
+        let values = try_join_all(INT_TABLES.iter().map(|(table_name, _)| {
+            self.get_property_value::<i64>(tenant_id, uid, property_name, table_name)
+        }))
+        .await?;
+
+        let found = INT_TABLES
+            .iter()
+            .zip(values)
+            .find_map(|(&(table_name, unsigned), value)| {
+                value.map(|value| (table_name, unsigned, value))
+            });
+        let value = match found {
+            // u64 values are stored as bigint, see graph-mutation
+            Some((table_name, unsigned, value)) => {
+                let value = if unsigned {
+                    IntValue::UInt64(value as u64)
+                } else {
+                    IntValue::Int64(value)
+                };
+
+                // max and min values may still change, so we only cache immutables
+                if table_name == IMM_I_64_TABLE_NAME || table_name == IMM_U_64_TABLE_NAME {
+                    self.property_cache.insert_int(key, value).await;
+                }
+                value
+            }
+            None => return Ok(None),
+        };
+
+        Ok(Some(IntField {
+            uid,
+            populated_field: property_name.clone(),
+            value,
+        }))
+    }
+
+    /// Looks up the type the node was created with. A node's type never
+    /// changes, so it's always cached.
+    pub async fn get_node_type(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) -> Result<Option<NodeType>, PropertyQueryError> {
+        let key = NodeTypeKey::new(tenant_id, uid);
+        if let Some(node_type) = self.property_cache.get_node_type(&key) {
+            return Ok(Some(node_type));
+        }
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT node_type
+            FROM {tenant_ks}.{NODE_TYPE_TABLE_NAME}
+            WHERE uid = ?
+            LIMIT 1;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self.execute(query, &(uid.as_i64(),)).await?;
+
+        let node_type = match query_result.maybe_first_row_typed::<(String,)>()? {
+            Some((value,)) => NodeType { value },
+            None => return Ok(None),
+        };
+
+        self.property_cache
+            .insert_node_type(key, node_type.clone())
+            .await;
+
+        Ok(Some(node_type))
+    }
+
+    async fn get_property_value<T>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        property_name: &PropertyName,
+        table_name: &str,
+    ) -> Result<Option<T>, PropertyQueryError>
+    where
+        (T,): FromRow,
+    {
         let tenant_ks = tenant_keyspace_name(tenant_id);
 
         let mut query = scylla::query::Query::from(format!(
             r"
             SELECT value
-            FROM {tenant_ks}.{IMM_STRING_TABLE_NAME}
+            FROM {tenant_ks}.{table_name}
             WHERE
                 uid = ? AND
-                populated_field = ?
-            LIMIT 1
-            ALLOW FILTERING;
+                populated_field = ?;
             "
         ));
 
         query.set_is_idempotent(true);
 
         let query_result = self
-            .scylla_client
             .execute(query, &(uid.as_i64(), &property_name.value))
             .await?;
 
-        let row = match query_result.maybe_first_row_typed::<(String,)>()? {
-            Some((row,)) => row,
-            None => return Ok(None),
-        };
-
-        Ok(Some(StringField {
-            uid,
-            populated_field: property_name.clone(),
-            value: row,
-        }))
+        match query_result.maybe_first_row_typed::<(T,)>()? {
+            Some((value,)) => Ok(Some(value)),
+            None => Ok(None),
+        }
     }
 
     pub async fn get_edges(
@@ -118,20 +385,16 @@ impl PropertyQueryExecutor {
         let mut query = scylla::query::Query::from(format!(
             r"
             SELECT r_edge_name, destination_uid
-            FROM {tenant_ks}.edges
+            FROM {tenant_ks}.{EDGES_TABLE_NAME}
             WHERE
                 source_uid = ? AND
-                f_edge_name = ?
-            ALLOW FILTERING;
+                f_edge_name = ?;
             "
         ));
 
-        println!("query: \n{}\n", &query.contents);
-
         query.set_is_idempotent(true);
 
         let query_result = self
-            .scylla_client
             .execute(query, &(uid.as_i64(), &edge_name.value))
             .await?;
 
@@ -156,10 +419,412 @@ impl PropertyQueryExecutor {
             });
         }
 
+        tracing::trace!(
+            message = "fetched edges",
+            tenant_id =% tenant_id,
+            uid =? uid,
+            edge_name =% edge_name,
+            edge_count = edge_rows.len(),
+        );
+
         if edge_rows.is_empty() {
             Ok(None)
         } else {
             Ok(Some(edge_rows))
         }
     }
+
+    /// Fetches the edges of several edge names out of a single node with one
+    /// round trip. Edge names without any edges are missing from the result.
+    pub async fn get_edges_batch(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        edge_names: &[EdgeName],
+    ) -> Result<FxHashMap<EdgeName, Vec<EdgeRow>>, PropertyQueryError> {
+        let mut edge_rows: FxHashMap<EdgeName, Vec<EdgeRow>> = FxHashMap::default();
+        if edge_names.is_empty() {
+            return Ok(edge_rows);
+        }
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT f_edge_name, r_edge_name, destination_uid
+            FROM {tenant_ks}.{EDGES_TABLE_NAME}
+            WHERE
+                source_uid = ? AND
+                f_edge_name IN ?;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let f_edge_names: Vec<&str> = edge_names
+            .iter()
+            .map(|edge_name| edge_name.value.as_str())
+            .collect();
+        let query_result = self.execute(query, &(uid.as_i64(), f_edge_names)).await?;
+
+        let rows = query_result.rows_typed_or_empty::<(String, String, i64)>();
+
+        for row in rows {
+            let (f_edge_name, r_edge_name, destination_uid) = row?;
+            let f_edge_name = EdgeName::try_from(f_edge_name)
+                .map_err(PropertyQueryError::InvalidStoredEdgeName)?;
+            let destination_uid = Uid::from_i64(destination_uid).ok_or_else(|| {
+                PropertyQueryError::InvalidUidInDb {
+                    destination_uid,
+                    source_uid: uid,
+                    f_edge_name: f_edge_name.to_string(),
+                }
+            })?;
+            edge_rows
+                .entry(f_edge_name.clone())
+                .or_default()
+                .push(EdgeRow {
+                    source_uid: uid,
+                    f_edge_name,
+                    r_edge_name: EdgeName::try_from(r_edge_name)
+                        .map_err(PropertyQueryError::InvalidStoredEdgeName)?,
+                    destination_uid,
+                    tenant_id,
+                });
+        }
+
+        Ok(edge_rows)
+    }
+
+    /// Fetches every populated property of a node from each of the property
+    /// tables, or None if the node doesn't exist.
+    pub async fn get_all_properties(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+    ) -> Result<Option<NodePropertiesView>, PropertyQueryError> {
+        let node_type = match self.get_node_type(tenant_id, uid).await? {
+            Some(node_type) => node_type,
+            None => return Ok(None),
+        };
+
+        let (string_rows, mutable_string_rows, float_rows, int_rows) = futures::try_join!(
+            self.get_property_rows::<String>(tenant_id, uid, IMM_STRING_TABLE_NAME),
+            self.get_property_rows::<String>(tenant_id, uid, MUT_STRING_TABLE_NAME),
+            self.get_property_rows::<f64>(tenant_id, uid, MUT_F_64_TABLE_NAME),
+            futures::future::try_join_all(
+                [
+                    IMM_I_64_TABLE_NAME,
+                    MAX_I_64_TABLE_NAME,
+                    MIN_I_64_TABLE_NAME,
+                    IMM_U_64_TABLE_NAME,
+                    MAX_U_64_TABLE_NAME,
+                    MIN_U_64_TABLE_NAME,
+                ]
+                .into_iter()
+                .map(|table_name| async move {
+                    let rows = self
+                        .get_property_rows::<i64>(tenant_id, uid, table_name)
+                        .await?;
+                    Ok::<_, PropertyQueryError>((table_name, rows))
+                }),
+            ),
+        )?;
+
+        let mut node = NodePropertiesView::new(uid, node_type);
+        for (property_name, value) in string_rows.into_iter().chain(mutable_string_rows) {
+            node.add_string_property(property_name, value);
+        }
+        for (property_name, value) in float_rows {
+            node.add_float64_property(property_name, value);
+        }
+        for (table_name, rows) in int_rows {
+            let unsigned = table_name == IMM_U_64_TABLE_NAME
+                || table_name == MAX_U_64_TABLE_NAME
+                || table_name == MIN_U_64_TABLE_NAME;
+            for (property_name, value) in rows {
+                // u64 values are stored as bigint, see graph-mutation
+                if unsigned {
+                    node.add_uint64_property(property_name, value as u64);
+                } else {
+                    node.add_int64_property(property_name, value);
+                }
+            }
+        }
+
+        Ok(Some(node))
+    }
+
+    /// Fetches up to `limit` edges out of a node, of any edge name.
+    pub async fn get_all_edges(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        limit: usize,
+    ) -> Result<Vec<EdgeRow>, PropertyQueryError> {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT f_edge_name, r_edge_name, destination_uid
+            FROM {tenant_ks}.{EDGES_TABLE_NAME}
+            WHERE source_uid = ?
+            LIMIT ?;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self.execute(query, &(uid.as_i64(), limit)).await?;
+
+        let rows = query_result.rows_typed_or_empty::<(String, String, i64)>();
+
+        let mut edge_rows = Vec::new();
+        for row in rows {
+            let (f_edge_name, r_edge_name, destination_uid) = row?;
+            let f_edge_name = EdgeName::try_from(f_edge_name)
+                .map_err(PropertyQueryError::InvalidStoredEdgeName)?;
+            let destination_uid = Uid::from_i64(destination_uid).ok_or_else(|| {
+                PropertyQueryError::InvalidUidInDb {
+                    destination_uid,
+                    source_uid: uid,
+                    f_edge_name: f_edge_name.to_string(),
+                }
+            })?;
+            edge_rows.push(EdgeRow {
+                source_uid: uid,
+                f_edge_name,
+                r_edge_name: EdgeName::try_from(r_edge_name)
+                    .map_err(PropertyQueryError::InvalidStoredEdgeName)?,
+                destination_uid,
+                tenant_id,
+            });
+        }
+
+        Ok(edge_rows)
+    }
+
+    async fn get_property_rows<T>(
+        &self,
+        tenant_id: uuid::Uuid,
+        uid: Uid,
+        table_name: &str,
+    ) -> Result<Vec<(PropertyName, T)>, PropertyQueryError>
+    where
+        (String, T): FromRow,
+    {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT populated_field, value
+            FROM {tenant_ks}.{table_name}
+            WHERE uid = ?;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let query_result = self.execute(query, &(uid.as_i64(),)).await?;
+
+        let mut property_rows = Vec::new();
+        for row in query_result.rows_typed_or_empty::<(String, T)>() {
+            let (populated_field, value) = row?;
+            let property_name = PropertyName::try_from(populated_field)
+                .map_err(PropertyQueryError::InvalidStoredPropertyName)?;
+            property_rows.push((property_name, value));
+        }
+
+        Ok(property_rows)
+    }
+
+    /// Finds up to `limit` nodes of the given type whose (immutable) property
+    /// matches the filter, using the property index tables rather than
+    /// starting from a known uid.
+    ///
+    /// Rewriting an immutable property with a different value, e.g. from a
+    /// graph-mutation instance whose write dropper doesn't know it was already
+    /// written, leaves the old value's index entry behind. So each candidate's
+    /// current value is read back, and candidates whose value no longer
+    /// matches their index entry are dropped.
+    pub async fn find_nodes(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: &NodeType,
+        property_name: &PropertyName,
+        filter: &PropertyValueFilter,
+        limit: usize,
+    ) -> Result<Vec<Uid>, PropertyQueryError> {
+        let node_type = node_type.value.as_str();
+        let populated_field = property_name.value.as_str();
+
+        match filter {
+            PropertyValueFilter::StringEqual(value) => {
+                self.find_indexed_uids::<String, _>(
+                    tenant_id,
+                    property_name,
+                    IMM_STRING_TABLE_NAME,
+                    IMM_STRING_INDEX_TABLE_NAME,
+                    "value = ?",
+                    limit,
+                    |fetch_limit| (node_type, populated_field, value, fetch_limit),
+                )
+                .await
+            }
+            PropertyValueFilter::StringPrefix(prefix) => match prefix_upper_bound(prefix) {
+                Some(upper_bound) => {
+                    self.find_indexed_uids::<String, _>(
+                        tenant_id,
+                        property_name,
+                        IMM_STRING_TABLE_NAME,
+                        IMM_STRING_INDEX_TABLE_NAME,
+                        "value >= ? AND value < ?",
+                        limit,
+                        |fetch_limit| {
+                            (
+                                node_type,
+                                populated_field,
+                                prefix,
+                                &upper_bound,
+                                fetch_limit,
+                            )
+                        },
+                    )
+                    .await
+                }
+                None => {
+                    self.find_indexed_uids::<String, _>(
+                        tenant_id,
+                        property_name,
+                        IMM_STRING_TABLE_NAME,
+                        IMM_STRING_INDEX_TABLE_NAME,
+                        "value >= ?",
+                        limit,
+                        |fetch_limit| (node_type, populated_field, prefix, fetch_limit),
+                    )
+                    .await
+                }
+            },
+            PropertyValueFilter::Int64Equal(value) => {
+                self.find_indexed_uids::<i64, _>(
+                    tenant_id,
+                    property_name,
+                    IMM_I_64_TABLE_NAME,
+                    IMM_I_64_INDEX_TABLE_NAME,
+                    "value = ?",
+                    limit,
+                    |fetch_limit| (node_type, populated_field, *value, fetch_limit),
+                )
+                .await
+            }
+            PropertyValueFilter::UInt64Equal(value) => {
+                // u64 values are stored as bigint, see graph-mutation
+                self.find_indexed_uids::<i64, _>(
+                    tenant_id,
+                    property_name,
+                    IMM_U_64_TABLE_NAME,
+                    IMM_U_64_INDEX_TABLE_NAME,
+                    "value = ?",
+                    limit,
+                    |fetch_limit| (node_type, populated_field, *value as i64, fetch_limit),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Reads index entries matching `value_condition` until `limit` of them
+    /// are found to still match their node's current value, or the entries
+    /// run out. `values` binds the condition's values, followed by how many
+    /// entries to fetch.
+    async fn find_indexed_uids<T, V>(
+        &self,
+        tenant_id: uuid::Uuid,
+        property_name: &PropertyName,
+        table_name: &str,
+        index_table_name: &str,
+        value_condition: &str,
+        limit: usize,
+        values: impl Fn(i32) -> V,
+    ) -> Result<Vec<Uid>, PropertyQueryError>
+    where
+        (i64, T): FromRow,
+        (T,): FromRow,
+        T: PartialEq,
+        V: ValueList,
+    {
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        let mut query = scylla::query::Query::from(format!(
+            r"
+            SELECT uid, value
+            FROM {tenant_ks}.{index_table_name}
+            WHERE
+                node_type = ? AND
+                populated_field = ? AND
+                {value_condition}
+            LIMIT ?;
+            "
+        ));
+
+        query.set_is_idempotent(true);
+
+        let mut fetch_limit = limit;
+        loop {
+            let query_result = self
+                .execute(
+                    query.clone(),
+                    values(i32::try_from(fetch_limit).unwrap_or(i32::MAX)),
+                )
+                .await?;
+
+            let mut entries = Vec::new();
+            for row in query_result.rows_typed_or_empty::<(i64, T)>() {
+                let (uid, value) = row?;
+                let uid = Uid::from_i64(uid).ok_or(PropertyQueryError::InvalidIndexedUid(uid))?;
+                entries.push((uid, value));
+            }
+            let exhausted = entries.len() < fetch_limit;
+
+            let mut uids: Vec<Uid> = stream::iter(entries)
+                .map(|(uid, indexed_value)| async move {
+                    let current_value = self
+                        .get_property_value::<T>(tenant_id, uid, property_name, table_name)
+                        .await?;
+                    Ok::<_, PropertyQueryError>(
+                        (current_value.as_ref() == Some(&indexed_value)).then_some(uid),
+                    )
+                })
+                .buffered(MAX_CONCURRENT_INDEX_CHECKS)
+                .try_filter_map(|uid| async move { Ok(uid) })
+                .try_collect()
+                .await?;
+
+            if uids.len() >= limit || exhausted || fetch_limit >= i32::MAX as usize {
+                uids.truncate(limit);
+                return Ok(uids);
+            }
+
+            // stale entries took the place of some matches, so fetch further
+            fetch_limit = fetch_limit.saturating_mul(2);
+        }
+    }
+}
+
+/// The smallest string greater than every string starting with `prefix`, or
+/// None if there isn't one (i.e. every char of the prefix is `char::MAX`).
+///
+/// Scylla compares text by its UTF-8 bytes, which orders the same as comparing
+/// by code point, so incrementing the last char that can be incremented works.
+fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut chars: Vec<char> = prefix.chars().collect();
+    while let Some(last) = chars.pop() {
+        // from_u32 skips over the surrogate range, which isn't valid chars
+        if let Some(next) = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
 }
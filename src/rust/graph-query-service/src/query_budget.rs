@@ -0,0 +1,276 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages as native;
+use tokio::time::Instant;
+
+/// Server-enforced bounds on the cost of a single graph query, so that one
+/// badly shaped query (cycles, huge fan out, many node queries) can't run
+/// unbounded against the shared cluster.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryLimits {
+    /// The most nodes a query may fetch and match against its node queries
+    pub max_nodes_visited: usize,
+    /// The most queries a query may send to Scylla. Cache hits are free.
+    pub max_round_trips: usize,
+    /// How long a query may run for
+    pub timeout: Duration,
+}
+
+/// Which of the `QueryLimits` a query ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryLimit {
+    NodesVisited,
+    RoundTrips,
+    Deadline,
+}
+
+impl fmt::Display for QueryLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryLimit::NodesVisited => write!(f, "max nodes visited"),
+            QueryLimit::RoundTrips => write!(f, "max round trips"),
+            QueryLimit::Deadline => write!(f, "timeout"),
+        }
+    }
+}
+
+impl From<QueryLimit> for native::QueryLimit {
+    fn from(limit: QueryLimit) -> Self {
+        match limit {
+            QueryLimit::NodesVisited => native::QueryLimit::NodesVisited,
+            QueryLimit::RoundTrips => native::QueryLimit::RoundTrips,
+            QueryLimit::Deadline => native::QueryLimit::Deadline,
+        }
+    }
+}
+
+/// How much of its budget a query had used.
+#[derive(Debug, Clone, Copy)]
+pub struct QueryStats {
+    pub nodes_visited: usize,
+    pub round_trips: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+#[error(
+    "query exceeded its {limit} limit after visiting {} nodes with {} round trips in {:?}",
+    stats.nodes_visited,
+    stats.round_trips,
+    stats.elapsed
+)]
+pub struct QueryBudgetExceeded {
+    pub limit: QueryLimit,
+    pub stats: QueryStats,
+}
+
+impl From<QueryBudgetExceeded> for native::QueryBudgetExceeded {
+    fn from(exceeded: QueryBudgetExceeded) -> Self {
+        Self {
+            limit: exceeded.limit.into(),
+            nodes_visited: exceeded.stats.nodes_visited as u64,
+            round_trips: exceeded.stats.round_trips as u64,
+            elapsed: exceeded.stats.elapsed,
+        }
+    }
+}
+
+/// Tracks the cost of a single query against its `QueryLimits`. Clones share
+/// the same counters, so every branch of a query draws from one budget.
+#[derive(Clone)]
+pub struct QueryBudget {
+    inner: Arc<QueryBudgetInner>,
+}
+
+struct QueryBudgetInner {
+    limits: QueryLimits,
+    started: Instant,
+    nodes_visited: AtomicUsize,
+    round_trips: AtomicUsize,
+    /// The first limit the query ran into, if any
+    exceeded: Mutex<Option<QueryLimit>>,
+}
+
+impl QueryBudget {
+    pub fn new(limits: QueryLimits) -> Self {
+        Self {
+            inner: Arc::new(QueryBudgetInner {
+                limits,
+                started: Instant::now(),
+                nodes_visited: AtomicUsize::new(0),
+                round_trips: AtomicUsize::new(0),
+                exceeded: Mutex::new(None),
+            }),
+        }
+    }
+
+    pub fn deadline(&self) -> Instant {
+        self.inner.started + self.inner.limits.timeout
+    }
+
+    pub fn visit_node(&self) -> Result<(), QueryBudgetExceeded> {
+        self.charge(
+            &self.inner.nodes_visited,
+            self.inner.limits.max_nodes_visited,
+            QueryLimit::NodesVisited,
+        )
+    }
+
+    pub fn round_trip(&self) -> Result<(), QueryBudgetExceeded> {
+        self.charge(
+            &self.inner.round_trips,
+            self.inner.limits.max_round_trips,
+            QueryLimit::RoundTrips,
+        )
+    }
+
+    /// Records that the query ran past its deadline.
+    pub fn exceed_deadline(&self) -> QueryBudgetExceeded {
+        self.exceed(QueryLimit::Deadline)
+    }
+
+    /// The first limit the query ran into, along with its stats at the time
+    /// of asking, or None if it has stayed within its budget.
+    pub fn exceeded(&self) -> Option<QueryBudgetExceeded> {
+        let limit = (*self.inner.exceeded.lock().unwrap())?;
+        Some(QueryBudgetExceeded {
+            limit,
+            stats: self.stats(),
+        })
+    }
+
+    pub fn stats(&self) -> QueryStats {
+        QueryStats {
+            nodes_visited: self.inner.nodes_visited.load(Ordering::Relaxed),
+            round_trips: self.inner.round_trips.load(Ordering::Relaxed),
+            elapsed: self.inner.started.elapsed(),
+        }
+    }
+
+    fn charge(
+        &self,
+        counter: &AtomicUsize,
+        max: usize,
+        limit: QueryLimit,
+    ) -> Result<(), QueryBudgetExceeded> {
+        // The counter never goes past the limit, so the stats report what was
+        // actually done
+        match counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+            (count < max).then(|| count + 1)
+        }) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(self.exceed(limit)),
+        }
+    }
+
+    fn exceed(&self, limit: QueryLimit) -> QueryBudgetExceeded {
+        let limit = *self.inner.exceeded.lock().unwrap().get_or_insert(limit);
+        QueryBudgetExceeded {
+            limit,
+            stats: self.stats(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_budget(max_nodes_visited: usize, max_round_trips: usize) -> QueryBudget {
+        QueryBudget::new(QueryLimits {
+            max_nodes_visited,
+            max_round_trips,
+            timeout: Duration::from_secs(60),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_charges_up_to_the_limit() {
+        let query_budget = query_budget(2, 3);
+        for _ in 0..2 {
+            query_budget.visit_node().expect("within the node limit");
+        }
+        for _ in 0..3 {
+            query_budget
+                .round_trip()
+                .expect("within the round trip limit");
+        }
+        assert!(query_budget.exceeded().is_none());
+
+        let stats = query_budget.stats();
+        assert_eq!(stats.nodes_visited, 2);
+        assert_eq!(stats.round_trips, 3);
+    }
+
+    #[tokio::test]
+    async fn test_exceeding_a_limit_stops_its_count() {
+        let query_budget = query_budget(1, 10);
+        query_budget.visit_node().expect("within the node limit");
+        query_budget
+            .round_trip()
+            .expect("within the round trip limit");
+
+        for _ in 0..2 {
+            let exceeded = query_budget.visit_node().expect_err("over the node limit");
+            assert_eq!(exceeded.limit, QueryLimit::NodesVisited);
+            assert_eq!(exceeded.stats.nodes_visited, 1);
+            assert_eq!(exceeded.stats.round_trips, 1);
+        }
+
+        let exceeded = query_budget.exceeded().expect("the budget to be exceeded");
+        assert_eq!(exceeded.limit, QueryLimit::NodesVisited);
+        assert_eq!(exceeded.stats.nodes_visited, 1);
+    }
+
+    #[tokio::test]
+    async fn test_first_limit_exceeded_is_kept() {
+        let query_budget = query_budget(0, 0);
+        // Clones draw from the same budget
+        let clone = query_budget.clone();
+
+        assert_eq!(
+            clone
+                .round_trip()
+                .expect_err("over the round trip limit")
+                .limit,
+            QueryLimit::RoundTrips
+        );
+        // Later limits report the first one hit, since that's what stopped
+        // the query
+        assert_eq!(
+            query_budget
+                .visit_node()
+                .expect_err("over the node limit")
+                .limit,
+            QueryLimit::RoundTrips
+        );
+        assert_eq!(query_budget.exceed_deadline().limit, QueryLimit::RoundTrips);
+        assert_eq!(
+            query_budget.exceeded().map(|exceeded| exceeded.limit),
+            Some(QueryLimit::RoundTrips)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exceeded_converts_to_status_details() {
+        let query_budget = query_budget(1, 1);
+        query_budget.visit_node().expect("within the node limit");
+        let exceeded = query_budget.exceed_deadline();
+
+        let details = native::QueryBudgetExceeded::from(exceeded);
+        assert_eq!(details.limit, native::QueryLimit::Deadline);
+        assert_eq!(details.nodes_visited, 1);
+        assert_eq!(details.round_trips, 0);
+    }
+}
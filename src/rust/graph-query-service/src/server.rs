@@ -1,35 +1,75 @@
 #![allow(warnings)]
-use std::sync::Arc;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
 
 use rust_proto::{
-    graplinc::grapl::api::graph_query_service::v1beta1::{
-        messages::{
-            GraphQuery,
-            GraphView,
-            MatchedGraphWithUid,
-            MaybeMatchWithUid,
-            NoMatchWithUid,
-            QueryGraphFromUidRequest,
-            QueryGraphFromUidResponse,
-            QueryGraphWithUidRequest,
-            QueryGraphWithUidResponse,
+    graplinc::grapl::{
+        api::graph_query_service::v1beta1::{
+            messages::{
+                FindNodesRequest,
+                FindNodesResponse,
+                GetNeighborhoodRequest,
+                GetNeighborhoodResponse,
+                GraphQuery,
+                GraphView,
+                MatchedGraphWithUid,
+                MaybeMatchWithUid,
+                NoMatchWithUid,
+                QueryAllGraphsWithUidRequest,
+                QueryAllGraphsWithUidResponse,
+                QueryBudgetExceeded as QueryBudgetExceededDetails,
+                QueryGraphFromUidRequest,
+                QueryGraphFromUidResponse,
+                QueryGraphWithUidRequest,
+                QueryGraphWithUidResponse,
+                TraverseEdgeRequest,
+                TraverseEdgeResponse,
+            },
+            server::GraphQueryApi,
         },
-        server::GraphQueryApi,
+        common::v1beta1::types::Uid,
+    },
+    protocol::status::{
+        Code,
+        Status,
     },
-    protocol::status::Status,
+    SerDe,
 };
 use scylla::CachingSession;
 
 use crate::{
+    edge_cardinality::{
+        EdgeCardinalityResolver,
+        EdgeCardinalityResolverError,
+    },
+    edge_traversal::{
+        traverse_edge,
+        TraversalLimits,
+    },
     graph_query::{
+        query_all_graphs,
         query_graph,
         GraphQueryError,
     },
+    neighborhood::fetch_neighborhood,
     node_query::{
         fetch_node_with_edges,
         NodeQueryError,
+        QueryRegexes,
+    },
+    property_cache::PropertyCache,
+    property_query::{
+        PropertyQueryError,
+        PropertyQueryExecutor,
+    },
+    query_budget::{
+        QueryBudget,
+        QueryBudgetExceeded,
+        QueryLimits,
     },
-    property_query::PropertyQueryExecutor,
     short_circuit::ShortCircuit,
     visited::Visited,
 };
@@ -40,14 +80,77 @@ pub enum GraphQueryServiceError {
     GraphQueryError(#[from] GraphQueryError),
     #[error("NodeQueryError {0}")]
     NodeQueryError(#[from] NodeQueryError),
+    #[error("PropertyQueryError {0}")]
+    PropertyQueryError(#[from] PropertyQueryError),
+    #[error("EdgeCardinalityResolverError {0}")]
+    EdgeCardinalityResolverError(#[from] EdgeCardinalityResolverError),
+    #[error("{0}")]
+    QueryBudgetExceeded(#[from] QueryBudgetExceeded),
+    #[error("max_results must be between 1 and {MAX_RESULTS_LIMIT}, got {0}")]
+    InvalidMaxResults(u32),
+    #[error("max_depth must be between 1 and {MAX_TRAVERSAL_DEPTH}, got {0}")]
+    InvalidMaxDepth(u32),
+    #[error("max_nodes must be between 1 and {MAX_TRAVERSAL_NODES}, got {0}")]
+    InvalidMaxNodes(u32),
+    #[error("max_edges must be between 1 and {MAX_NEIGHBORHOOD_EDGES}, got {0}")]
+    InvalidMaxEdges(u32),
+    #[error("timeout must be non-zero and at most {MAX_TRAVERSAL_TIMEOUT:?}, got {0:?}")]
+    InvalidTimeout(Duration),
+    #[error("node {0:?} does not exist")]
+    NodeNotFound(Uid),
 }
 
+/// The most matches a single QueryAllGraphsWithUid or FindNodes request may
+/// ask for
+pub const MAX_RESULTS_LIMIT: u32 = 1_000;
+
+/// The most hops a single TraverseEdge request may follow
+pub const MAX_TRAVERSAL_DEPTH: u32 = 100;
+
+/// The most nodes a single TraverseEdge request may visit
+pub const MAX_TRAVERSAL_NODES: u32 = 10_000;
+
+/// The longest a single TraverseEdge request may traverse for
+pub const MAX_TRAVERSAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The most edges (and so neighbors) a single GetNeighborhood request may
+/// fetch
+pub const MAX_NEIGHBORHOOD_EDGES: u32 = 1_000;
+
 impl From<GraphQueryServiceError> for Status {
     fn from(gqs_err: GraphQueryServiceError) -> Self {
         type GQSErr = GraphQueryServiceError;
         match gqs_err {
+            GQSErr::GraphQueryError(
+                e @ GraphQueryError::NodeQueryError {
+                    source: NodeQueryError::InvalidRegex(_),
+                    ..
+                },
+            ) => Status::invalid_argument(e.to_string()),
+            GQSErr::NodeQueryError(e @ NodeQueryError::InvalidRegex(_)) => {
+                Status::invalid_argument(e.to_string())
+            }
+            e @ GQSErr::InvalidMaxResults(_) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::InvalidMaxDepth(_) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::InvalidMaxNodes(_) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::InvalidMaxEdges(_) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::InvalidTimeout(_) => Status::invalid_argument(e.to_string()),
+            e @ GQSErr::NodeNotFound(_) => Status::not_found(e.to_string()),
+            // The details carry how far the query got before it was stopped
+            GQSErr::QueryBudgetExceeded(e) => {
+                let message = e.to_string();
+                match QueryBudgetExceededDetails::from(e).serialize() {
+                    Ok(details) => Status::with_details(Code::ResourceExhausted, message, details),
+                    Err(_) => Status::resource_exhausted(message),
+                }
+            }
             GQSErr::GraphQueryError(e) => Status::unknown(e.to_string()),
             GQSErr::NodeQueryError(e) => Status::unknown(e.to_string()),
+            GQSErr::PropertyQueryError(e) => Status::unknown(e.to_string()),
+            e @ GQSErr::EdgeCardinalityResolverError(
+                EdgeCardinalityResolverError::UndeclaredEdge { .. },
+            ) => Status::invalid_argument(e.to_string()),
+            GQSErr::EdgeCardinalityResolverError(e) => Status::unknown(e.to_string()),
         }
     }
 }
@@ -55,14 +158,66 @@ impl From<GraphQueryServiceError> for Status {
 #[derive(Clone)]
 pub struct GraphQueryService {
     property_query_executor: PropertyQueryExecutor,
+    edge_cardinality_resolver: EdgeCardinalityResolver,
+    max_concurrent_neighbor_fetches: usize,
+    query_limits: QueryLimits,
 }
 
 impl GraphQueryService {
-    pub fn new(scylla_client: Arc<CachingSession>) -> Self {
+    pub fn new(
+        scylla_client: Arc<CachingSession>,
+        property_cache: PropertyCache,
+        edge_cardinality_resolver: EdgeCardinalityResolver,
+        max_concurrent_neighbor_fetches: usize,
+        query_limits: QueryLimits,
+    ) -> Self {
         Self {
-            property_query_executor: PropertyQueryExecutor::new(scylla_client),
+            property_query_executor: PropertyQueryExecutor::new(scylla_client, property_cache),
+            edge_cardinality_resolver,
+            max_concurrent_neighbor_fetches,
+            query_limits,
         }
     }
+
+    /// A fresh budget for a single request, along with an executor that
+    /// charges its round trips (and the nodes it visits) to it.
+    fn budgeted_executor(&self) -> (QueryBudget, PropertyQueryExecutor) {
+        let query_budget = QueryBudget::new(self.query_limits);
+        let property_query_executor = self
+            .property_query_executor
+            .with_budget(query_budget.clone());
+        (query_budget, property_query_executor)
+    }
+}
+
+/// Runs a graph query until it completes or runs past its budget's deadline.
+///
+/// Once any limit has been hit, the query's result can't be trusted to be
+/// complete (e.g. a node that would have matched may not have been visited),
+/// so the budget error is returned in its place even if the query then went
+/// on to succeed.
+async fn run_within_budget<T, E>(
+    query_budget: &QueryBudget,
+    query: impl Future<Output = Result<T, E>>,
+) -> Result<T, GraphQueryServiceError>
+where
+    GraphQueryServiceError: From<E>,
+{
+    let result = match tokio::time::timeout_at(query_budget.deadline(), query).await {
+        Ok(result) => result,
+        Err(_elapsed) => return Err(query_budget.exceed_deadline().into()),
+    };
+    if let Some(exceeded) = query_budget.exceeded() {
+        tracing::warn!(
+            message = "graph query exceeded its budget",
+            limit =% exceeded.limit,
+            nodes_visited = exceeded.stats.nodes_visited,
+            round_trips = exceeded.stats.round_trips,
+            elapsed =? exceeded.stats.elapsed,
+        );
+        return Err(exceeded.into());
+    }
+    Ok(result?)
 }
 
 #[async_trait::async_trait]
@@ -76,11 +231,16 @@ impl GraphQueryApi for GraphQueryService {
         let node_uid = request.node_uid;
 
         let graph_query: GraphQuery = request.graph_query;
-        let graph = query_graph(
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let graph = run_within_budget(
+            &query_budget,
+            query_graph(
+                &graph_query,
+                node_uid,
+                request.tenant_id,
+                property_query_executor,
+                self.max_concurrent_neighbor_fetches,
+            ),
         )
         .await?;
 
@@ -113,17 +273,25 @@ impl GraphQueryApi for GraphQueryService {
             .get(&graph_query.root_query_id)
             .unwrap();
 
+        let regexes = QueryRegexes::compile(&graph_query)?;
+
         let visited = Visited::new();
         let x_short_circuit = ShortCircuit::new();
-        let graph = fetch_node_with_edges(
-            &node_query,
-            &graph_query,
-            node_uid,
-            request.tenant_id,
-            self.property_query_executor.clone(),
-            visited,
-            x_short_circuit,
-            &mut None,
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let graph = run_within_budget(
+            &query_budget,
+            fetch_node_with_edges(
+                &node_query,
+                &graph_query,
+                &regexes,
+                node_uid,
+                request.tenant_id,
+                property_query_executor,
+                visited,
+                x_short_circuit,
+                self.max_concurrent_neighbor_fetches,
+                &mut None,
+            ),
         )
         .await?;
 
@@ -131,4 +299,157 @@ impl GraphQueryApi for GraphQueryService {
             matched_graph: graph,
         })
     }
+
+    async fn query_all_graphs_with_uid(
+        &self,
+        request: QueryAllGraphsWithUidRequest,
+    ) -> Result<QueryAllGraphsWithUidResponse, GraphQueryServiceError> {
+        let max_results = request.max_results;
+        if max_results == 0 || max_results > MAX_RESULTS_LIMIT {
+            return Err(GraphQueryServiceError::InvalidMaxResults(max_results));
+        }
+
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let (matches, truncated) = run_within_budget(
+            &query_budget,
+            query_all_graphs(
+                &request.graph_query,
+                request.node_uid,
+                request.tenant_id,
+                property_query_executor,
+                self.max_concurrent_neighbor_fetches,
+                max_results as usize,
+            ),
+        )
+        .await?;
+
+        Ok(QueryAllGraphsWithUidResponse {
+            matches: matches
+                .into_iter()
+                .map(|(matched_graph, root_uid)| MatchedGraphWithUid {
+                    matched_graph,
+                    root_uid,
+                })
+                .collect(),
+            truncated,
+        })
+    }
+
+    async fn traverse_edge(
+        &self,
+        request: TraverseEdgeRequest,
+    ) -> Result<TraverseEdgeResponse, GraphQueryServiceError> {
+        let deadline = tokio::time::Instant::now() + request.timeout;
+
+        if request.max_depth == 0 || request.max_depth > MAX_TRAVERSAL_DEPTH {
+            return Err(GraphQueryServiceError::InvalidMaxDepth(request.max_depth));
+        }
+        if request.max_nodes == 0 || request.max_nodes > MAX_TRAVERSAL_NODES {
+            return Err(GraphQueryServiceError::InvalidMaxNodes(request.max_nodes));
+        }
+        if request.timeout.is_zero() || request.timeout > MAX_TRAVERSAL_TIMEOUT {
+            return Err(GraphQueryServiceError::InvalidTimeout(request.timeout));
+        }
+
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let node_type = run_within_budget(
+            &query_budget,
+            property_query_executor.get_node_type(request.tenant_id, request.node_uid),
+        )
+        .await?
+        .ok_or(GraphQueryServiceError::NodeNotFound(request.node_uid))?;
+
+        let cardinality = self
+            .edge_cardinality_resolver
+            .resolve_cardinality(
+                request.tenant_id,
+                node_type.clone(),
+                request.edge_name.clone(),
+            )
+            .await?;
+
+        let traversal = run_within_budget(
+            &query_budget,
+            traverse_edge(
+                request.tenant_id,
+                request.node_uid,
+                node_type,
+                &request.edge_name,
+                &cardinality,
+                &TraversalLimits {
+                    max_depth: request.max_depth as usize,
+                    max_nodes: request.max_nodes as usize,
+                    // Stop in time to return what's been visited, rather than
+                    // running into the query budget's own deadline
+                    deadline: deadline.min(query_budget.deadline()),
+                },
+                property_query_executor,
+                self.max_concurrent_neighbor_fetches,
+            ),
+        )
+        .await?;
+
+        Ok(TraverseEdgeResponse {
+            graph: traversal.graph,
+            truncated: traversal.truncated,
+        })
+    }
+
+    async fn get_neighborhood(
+        &self,
+        request: GetNeighborhoodRequest,
+    ) -> Result<GetNeighborhoodResponse, GraphQueryServiceError> {
+        let max_edges = request.max_edges;
+        if max_edges == 0 || max_edges > MAX_NEIGHBORHOOD_EDGES {
+            return Err(GraphQueryServiceError::InvalidMaxEdges(max_edges));
+        }
+
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let neighborhood = run_within_budget(
+            &query_budget,
+            fetch_neighborhood(
+                request.tenant_id,
+                request.node_uid,
+                request.include_neighbors,
+                max_edges as usize,
+                property_query_executor,
+                self.max_concurrent_neighbor_fetches,
+            ),
+        )
+        .await?
+        .ok_or(GraphQueryServiceError::NodeNotFound(request.node_uid))?;
+
+        Ok(GetNeighborhoodResponse {
+            graph: neighborhood.graph,
+            truncated: neighborhood.truncated,
+        })
+    }
+
+    async fn find_nodes(
+        &self,
+        request: FindNodesRequest,
+    ) -> Result<FindNodesResponse, GraphQueryServiceError> {
+        let max_results = request.max_results;
+        if max_results == 0 || max_results > MAX_RESULTS_LIMIT {
+            return Err(GraphQueryServiceError::InvalidMaxResults(max_results));
+        }
+
+        // Fetch one extra uid to tell whether there were more than requested
+        let (query_budget, property_query_executor) = self.budgeted_executor();
+        let mut uids = run_within_budget(
+            &query_budget,
+            property_query_executor.find_nodes(
+                request.tenant_id,
+                &request.node_type,
+                &request.property_name,
+                &request.filter,
+                max_results as usize + 1,
+            ),
+        )
+        .await?;
+        let truncated = uids.len() > max_results as usize;
+        uids.truncate(max_results as usize);
+
+        Ok(FindNodesResponse { uids, truncated })
+    }
 }
@@ -1,10 +1,6 @@
 use std::{
     collections::HashSet,
     sync::{
-        atomic::{
-            AtomicBool,
-            Ordering,
-        },
         Arc,
         Mutex,
     },
@@ -15,12 +11,11 @@ use rust_proto::graplinc::grapl::{
     common::v1beta1::types::EdgeName,
 };
 
-// We should have the short circuit logic get shared between tasks
-// so that parallel queries in WithUid queries can short circuit
-
+/// The query edges a match has already followed, in either direction, so that
+/// matching a query with cycles in it doesn't follow the same edges forever.
+/// Short circuiting the tasks matching a query is done with a `ShortCircuit`.
 #[derive(Clone)]
 pub struct Visited {
-    short_circuit: Arc<AtomicBool>,
     already_visited: Arc<Mutex<HashSet<(QueryId, EdgeName, QueryId)>>>,
 }
 
@@ -33,17 +28,18 @@ impl Default for Visited {
 impl Visited {
     pub fn new() -> Self {
         Self {
-            short_circuit: Arc::new(AtomicBool::new(false)),
             already_visited: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
-    pub fn get_short_circuit(&self) -> bool {
-        self.short_circuit.as_ref().load(Ordering::Acquire)
-    }
-
-    pub fn set_short_circuit(&self) {
-        self.short_circuit.as_ref().store(true, Ordering::Release)
+    /// A copy of the edges visited so far. Each candidate node for the same
+    /// query gets its own fork, so that the edges one candidate visits don't
+    /// stop its siblings from checking them too.
+    pub fn fork(&self) -> Self {
+        let already_visited = self.already_visited.lock().unwrap().clone();
+        Self {
+            already_visited: Arc::new(Mutex::new(already_visited)),
+        }
     }
 
     pub fn check_and_add(&self, src: QueryId, edge_name: EdgeName, dst: QueryId) -> bool {
@@ -1,7 +1,19 @@
 #![cfg(feature = "integration_tests")]
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
 use bytes::Bytes;
 use clap::Parser;
-use graph_query::node_query::NodeQuery;
+use graph_query::{
+    config::GraphDbConfig,
+    edge_cardinality::EdgeCardinalityResolver,
+    node_query::NodeQuery,
+    property_cache::PropertyCache,
+    query_budget::QueryLimits,
+    server::GraphQueryService,
+};
 use rust_proto::{
     client_factory::services::{
         GraphMutationClientConfig,
@@ -14,6 +26,7 @@ use rust_proto::{
         api::{
             graph::v1beta1::{
                 ImmutableStrProp,
+                ImmutableUintProp,
                 NodeProperty,
                 Property,
             },
@@ -24,13 +37,24 @@ use rust_proto::{
             graph_query_service::v1beta1::{
                 client::GraphQueryClient,
                 messages::{
+                    FindNodesRequest,
+                    GetNeighborhoodRequest,
+                    IntCmp,
                     MatchedGraphWithUid,
                     MaybeMatchWithUid,
                     NodePropertyQuery,
+                    PropertyValueFilter,
+                    QueryAllGraphsWithUidRequest,
+                    QueryBudgetExceeded,
                     QueryGraphFromUidRequest,
                     QueryGraphWithUidRequest,
+                    QueryLimit,
                     StringCmp,
+                    TraverseEdgeRequest,
+                    UidFilter,
+                    UidOperation,
                 },
+                server::GraphQueryApi,
             },
             graph_schema_manager::v1beta1::{
                 client::GraphSchemaManagerClient,
@@ -48,10 +72,27 @@ use rust_proto::{
         common::v1beta1::types::{
             EdgeName,
             NodeType,
+            PropertyName,
+        },
+    },
+    protocol::{
+        error::GrpcClientError,
+        service_client::ConnectWithConfig,
+        status::{
+            Code,
+            Status,
         },
     },
-    protocol::service_client::ConnectWithConfig,
+    SerDe,
+};
+use rustc_hash::FxHashSet;
+use scylla::CachingSession;
+use scylla_table_names::{
+    tenant_keyspace_name,
+    IMM_STRING_INDEX_TABLE_NAME,
+    IMM_STRING_TABLE_NAME,
 };
+use secrecy::ExposeSecret;
 
 async fn provision_example_graph_schema(tenant_id: uuid::Uuid) -> eyre::Result<()> {
     let graph_schema_manager_client_config = GraphSchemaManagerClientConfig::parse();
@@ -75,26 +116,28 @@ async fn provision_example_graph_schema(tenant_id: uuid::Uuid) -> eyre::Result<(
     Ok(())
 }
 
-#[test_log::test(tokio::test)]
-async fn test_query_two_attached_nodes() -> eyre::Result<()> {
-    let _span = tracing::info_span!(
-        "tenant_id", tenant_id=?tracing::field::Empty,
-    );
-
-    let query_client_config = GraphQueryClientConfig::parse();
-    let mut graph_query_client = GraphQueryClient::connect_with_config(query_client_config).await?;
-
-    let mutation_client_config = GraphMutationClientConfig::parse();
-    let mut graph_mutation_client =
-        GraphMutationClient::connect_with_config(mutation_client_config).await?;
+/// A new tenant with the example schema deployed, along with clients of the
+/// services under test
+struct TestTenant {
+    tenant_id: uuid::Uuid,
+    graph_query_client: GraphQueryClient,
+    graph_mutation_client: GraphMutationClient,
+}
 
-    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
-    let mut provisioner_client =
-        ScyllaProvisionerClient::connect_with_config(provisioner_client_config).await?;
+/// Provisions a new tenant's graph keyspace and uid keyspace, deploys the
+/// example schema for it, and records its id on the test's span.
+async fn provision_tenant(span: &tracing::Span) -> eyre::Result<TestTenant> {
+    let graph_query_client =
+        GraphQueryClient::connect_with_config(GraphQueryClientConfig::parse()).await?;
+    let graph_mutation_client =
+        GraphMutationClient::connect_with_config(GraphMutationClientConfig::parse()).await?;
 
     let tenant_id = uuid::Uuid::new_v4();
-    _span.record("tenant_id", &format!("{tenant_id}"));
+    span.record("tenant_id", &format!("{tenant_id}"));
 
+    let mut provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(ScyllaProvisionerClientConfig::parse())
+            .await?;
     provisioner_client
         .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
             tenant_id,
@@ -103,16 +146,33 @@ async fn test_query_two_attached_nodes() -> eyre::Result<()> {
 
     // Only used to provision the keyspace. It's okay here to use the
     // otherwise-unrecommended non-caching UidAllocator client.
-
     let mut uid_allocator_client =
         UidAllocatorServiceClient::connect_with_config(UidAllocatorClientConfig::parse()).await?;
     uid_allocator_client
         .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
         .await?;
 
-    // Provision a Schema so we can test how edges work
     provision_example_graph_schema(tenant_id).await?;
 
+    Ok(TestTenant {
+        tenant_id,
+        graph_query_client,
+        graph_mutation_client,
+    })
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_two_attached_nodes() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
     let process_node_type = NodeType::try_from("Process").unwrap();
     let file_node_type = NodeType::try_from("File").unwrap();
 
@@ -265,4 +325,1574 @@ async fn test_query_two_attached_nodes() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test_log::test(tokio::test)]
+async fn test_query_with_regex_filter() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+
+    let mutation::CreateNodeResponse { uid: node_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: node_uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_name".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "chrome.exe".into(),
+                }),
+            },
+        })
+        .await?;
+
+    for (pattern, negated, expect_match) in [
+        (r"^chr.*\.exe$", false, true),
+        (r"^chr.*\.exe$", true, false),
+        (r"^svchost", false, false),
+        (r"^svchost", true, true),
+    ] {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_string_comparisons(
+                "process_name".try_into()?,
+                vec![StringCmp::Regex(pattern.to_owned(), negated)],
+            )
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(
+            matched, expect_match,
+            "pattern {pattern} (negated: {negated})"
+        );
+    }
+
+    // The node isn't a File, so it's never matched against the regex, but the
+    // invalid regex is still rejected
+    let graph_query = NodeQuery::root(NodeType::try_from("File").unwrap())
+        .with_string_comparisons(
+            "process_name".try_into()?,
+            vec![StringCmp::Regex("(unclosed".to_owned(), false)],
+        )
+        .build();
+    let invalid_regex = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id,
+            node_uid,
+            graph_query,
+        })
+        .await;
+    match invalid_regex {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument, "{status}")
+        }
+        other => panic!("expected InvalidArgument, got {other:?}"),
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_with_int_filter() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let process_id = PropertyName::try_from("process_id")?;
+
+    let mutation::CreateNodeResponse { uid: node_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: node_uid,
+            node_type: process_node_type.clone(),
+            property_name: process_id.clone(),
+            property: NodeProperty {
+                property: Property::ImmutableUintProp(ImmutableUintProp { prop: 1234 }),
+            },
+        })
+        .await?;
+
+    for (comparison, expect_match) in [
+        (IntCmp::Gt(4, false), true),
+        (IntCmp::Gt(4, true), false),
+        (IntCmp::Eq(1234, false), true),
+        (IntCmp::Lte(1000, false), false),
+    ] {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_int_comparisons(process_id.clone(), vec![comparison.clone()])
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query,
+            })
+            .await?;
+
+        match response.maybe_match {
+            MaybeMatchWithUid::Matched(MatchedGraphWithUid { matched_graph, .. }) => {
+                assert!(expect_match, "unexpected match for {comparison:?}");
+                let node = &matched_graph.nodes[&node_uid];
+                assert_eq!(node.uint64_properties.prop_map[&process_id], 1234);
+            }
+            MaybeMatchWithUid::Missed(_) => {
+                assert!(!expect_match, "expected a match for {comparison:?}")
+            }
+        }
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_enforces_node_type() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid: node_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid: node_uid,
+            node_type: process_node_type.clone(),
+            property_name: "process_name".try_into()?,
+            property: NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "chrome.exe".into(),
+                }),
+            },
+        })
+        .await?;
+
+    // The File query filters on a property the Process node has, so only the
+    // node type can tell them apart
+    for (node_type, expect_match) in [(process_node_type, true), (file_node_type, false)] {
+        let graph_query = NodeQuery::root(node_type.clone())
+            .with_string_comparisons(
+                "process_name".try_into()?,
+                vec![StringCmp::Eq("chrome.exe".to_owned(), false)],
+            )
+            .build();
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched = matches!(response.maybe_match, MaybeMatchWithUid::Matched(_));
+        assert_eq!(matched, expect_match, "node type {node_type}");
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_with_uid_filter() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // The process creates two files, only one of which we'll pin the query to
+    let forward_edge_name = EdgeName::try_from("created_file")?;
+    let reverse_edge_name = EdgeName::try_from("created_by_process")?;
+    let mut file_uids = vec![];
+    for _ in 0..2 {
+        let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: file_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uid,
+                to_uid: file_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        file_uids.push(file_uid);
+    }
+
+    for file_uid in file_uids.iter().copied() {
+        let graph_query = NodeQuery::root(process_node_type.clone())
+            .with_uid_filter(UidFilter {
+                operation: UidOperation::Equal,
+                value: process_uid,
+            })
+            .with_edge_to(
+                forward_edge_name.clone(),
+                reverse_edge_name.clone(),
+                file_node_type.clone(),
+                |file| {
+                    file.with_uid_filter(UidFilter {
+                        operation: UidOperation::Equal,
+                        value: file_uid,
+                    });
+                },
+            )
+            .build();
+
+        let response = graph_query_client
+            .query_graph_from_uid(QueryGraphFromUidRequest {
+                tenant_id,
+                node_uid: process_uid,
+                graph_query,
+            })
+            .await?;
+
+        let matched_graph = response.matched_graph.expect("Expected a matched graph");
+        assert_eq!(matched_graph.nodes.len(), 2);
+        assert!(matched_graph.nodes.contains_key(&process_uid));
+        assert!(matched_graph.nodes.contains_key(&file_uid));
+    }
+
+    // A query pinned to a uid that isn't the node being queried never matches
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_uid_filter(UidFilter {
+            operation: UidOperation::Equal,
+            value: file_uids[0],
+        })
+        .build();
+
+    let response = graph_query_client
+        .query_graph_with_uid(QueryGraphWithUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query,
+        })
+        .await?;
+
+    assert!(matches!(response.maybe_match, MaybeMatchWithUid::Missed(_)));
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_all_graphs_with_uid() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // The process creates three files, each of which is a distinct match
+    let forward_edge_name = EdgeName::try_from("created_file")?;
+    let reverse_edge_name = EdgeName::try_from("created_by_process")?;
+    let mut file_uids = vec![];
+    for _ in 0..3 {
+        let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: file_node_type.clone(),
+            })
+            .await?;
+
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: forward_edge_name.clone(),
+                tenant_id,
+                from_uid: process_uid,
+                to_uid: file_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        file_uids.push(file_uid);
+    }
+
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_shared_edge(
+            forward_edge_name.clone(),
+            reverse_edge_name.clone(),
+            NodePropertyQuery::new(file_node_type.clone()),
+            |_| {},
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_all_graphs_with_uid(QueryAllGraphsWithUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query: graph_query.clone(),
+            max_results: 10,
+        })
+        .await?;
+
+    assert!(!response.truncated);
+    assert_eq!(response.matches.len(), 3);
+    for file_uid in file_uids.iter() {
+        let matched = response
+            .matches
+            .iter()
+            .find(|graph_match| graph_match.matched_graph.nodes.contains_key(file_uid));
+        let matched = matched.expect("expected a match for every file");
+        assert_eq!(matched.root_uid, process_uid);
+        assert_eq!(matched.matched_graph.nodes.len(), 2);
+    }
+
+    // Querying from one of the files only matches that file
+    let response = graph_query_client
+        .query_all_graphs_with_uid(QueryAllGraphsWithUidRequest {
+            tenant_id,
+            node_uid: file_uids[0],
+            graph_query: graph_query.clone(),
+            max_results: 10,
+        })
+        .await?;
+
+    assert_eq!(response.matches.len(), 1);
+    assert_eq!(response.matches[0].root_uid, process_uid);
+    assert!(response.matches[0]
+        .matched_graph
+        .nodes
+        .contains_key(&file_uids[0]));
+
+    // The results are truncated to max_results
+    let response = graph_query_client
+        .query_all_graphs_with_uid(QueryAllGraphsWithUidRequest {
+            tenant_id,
+            node_uid: process_uid,
+            graph_query,
+            max_results: 2,
+        })
+        .await?;
+
+    assert!(response.truncated);
+    assert_eq!(response.matches.len(), 2);
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_all_graphs_with_diamond() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+
+    let children = EdgeName::try_from("children")?;
+    let parent = EdgeName::try_from("parent")?;
+    let binary_file = EdgeName::try_from("binary_file")?;
+    let executed_as_processes = EdgeName::try_from("executed_as_processes")?;
+    let created_file = EdgeName::try_from("created_file")?;
+    let created_by_process = EdgeName::try_from("created_by_process")?;
+
+    let mut uids = vec![];
+    for node_type in [
+        &process_node_type,
+        &process_node_type,
+        &process_node_type,
+        &file_node_type,
+        &file_node_type,
+    ] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: node_type.clone(),
+            })
+            .await?;
+        uids.push(uid);
+    }
+    let (parent_uid, child_uid, other_child_uid, file_uid, other_file_uid) =
+        (uids[0], uids[1], uids[2], uids[3], uids[4]);
+
+    // The parent creates a file, which is the binary of only one of its
+    // children. The other child is missing the edge that closes the diamond.
+    for (from_uid, edge_name, to_uid) in [
+        (parent_uid, &children, child_uid),
+        (parent_uid, &children, other_child_uid),
+        (parent_uid, &created_file, file_uid),
+        (child_uid, &binary_file, file_uid),
+        (other_child_uid, &binary_file, other_file_uid),
+    ] {
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: edge_name.clone(),
+                tenant_id,
+                from_uid,
+                to_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    // parent -children-> child -binary_file-> file <-created_file- parent
+    let file_query = NodePropertyQuery::new(file_node_type.clone());
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_shared_edge(
+            children.clone(),
+            parent.clone(),
+            NodePropertyQuery::new(process_node_type.clone()),
+            |child| {
+                child.with_shared_edge(
+                    binary_file.clone(),
+                    executed_as_processes.clone(),
+                    file_query.clone(),
+                    |_| {},
+                );
+            },
+        )
+        .with_shared_edge(
+            created_file.clone(),
+            created_by_process.clone(),
+            file_query,
+            |_| {},
+        )
+        .build();
+
+    let response = graph_query_client
+        .query_all_graphs_with_uid(QueryAllGraphsWithUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query: graph_query.clone(),
+            max_results: 10,
+        })
+        .await?;
+
+    assert!(!response.truncated);
+    assert_eq!(response.matches.len(), 1);
+    let matched_graph = &response.matches[0].matched_graph;
+    assert_eq!(response.matches[0].root_uid, parent_uid);
+    assert_eq!(matched_graph.nodes.len(), 3);
+    assert!(matched_graph.nodes.contains_key(&child_uid));
+    assert!(matched_graph.nodes.contains_key(&file_uid));
+    assert!(!matched_graph.nodes.contains_key(&other_child_uid));
+
+    // The same holds whichever node of the diamond the traversal starts from
+    for (node_uid, expected_matches) in [
+        (child_uid, 1),
+        (file_uid, 1),
+        (other_child_uid, 0),
+        (other_file_uid, 0),
+    ] {
+        let response = graph_query_client
+            .query_all_graphs_with_uid(QueryAllGraphsWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query: graph_query.clone(),
+                max_results: 10,
+            })
+            .await?;
+        assert_eq!(response.matches.len(), expected_matches);
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_with_or_and_negated_edges() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let created_file = EdgeName::try_from("created_file")?;
+    let created_by_process = EdgeName::try_from("created_by_process")?;
+    let binary_file = EdgeName::try_from("binary_file")?;
+    let executed_as_processes = EdgeName::try_from("executed_as_processes")?;
+
+    let create_node = |node_type: NodeType| {
+        let mut graph_mutation_client = graph_mutation_client.clone();
+        async move {
+            let mutation::CreateNodeResponse { uid } = graph_mutation_client
+                .create_node(mutation::CreateNodeRequest {
+                    tenant_id,
+                    node_type,
+                })
+                .await?;
+            Ok::<_, eyre::Report>(uid)
+        }
+    };
+
+    // One process that created a file, one that was executed from a file, and
+    // one with no edges at all
+    let creator_uid = create_node(process_node_type.clone()).await?;
+    let created_file_uid = create_node(file_node_type.clone()).await?;
+    let executed_uid = create_node(process_node_type.clone()).await?;
+    let binary_file_uid = create_node(file_node_type.clone()).await?;
+    let lonely_uid = create_node(process_node_type.clone()).await?;
+
+    for (from_uid, edge_name, to_uid) in [
+        (creator_uid, created_file.clone(), created_file_uid),
+        (executed_uid, binary_file.clone(), binary_file_uid),
+    ] {
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name,
+                tenant_id,
+                from_uid,
+                to_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    // A process that either created a file or was executed from one
+    let any_file_query = NodeQuery::root(process_node_type.clone())
+        .with_any_edge(|any_edge| {
+            any_edge
+                .with_edge_to(
+                    created_file.clone(),
+                    created_by_process.clone(),
+                    file_node_type.clone(),
+                    |_| {},
+                )
+                .with_edge_to(
+                    binary_file.clone(),
+                    executed_as_processes.clone(),
+                    file_node_type.clone(),
+                    |_| {},
+                );
+        })
+        .build();
+
+    // A process that didn't create any files
+    let no_created_file_query = NodeQuery::root(process_node_type.clone())
+        .with_no_edge_to(
+            created_file.clone(),
+            created_by_process.clone(),
+            file_node_type.clone(),
+            |_| {},
+        )
+        .build();
+
+    // A file that was never executed
+    let never_executed_query = NodeQuery::root(file_node_type.clone())
+        .with_no_edge_to(
+            executed_as_processes.clone(),
+            binary_file.clone(),
+            process_node_type.clone(),
+            |_| {},
+        )
+        .build();
+
+    for (graph_query, node_uid, expect_match) in [
+        (&any_file_query, creator_uid, true),
+        (&any_file_query, executed_uid, true),
+        (&any_file_query, lonely_uid, false),
+        (&no_created_file_query, creator_uid, false),
+        (&no_created_file_query, executed_uid, true),
+        (&no_created_file_query, lonely_uid, true),
+        (&never_executed_query, created_file_uid, true),
+        (&never_executed_query, binary_file_uid, false),
+    ] {
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query: graph_query.clone(),
+            })
+            .await?;
+
+        match response.maybe_match {
+            MaybeMatchWithUid::Matched(MatchedGraphWithUid { root_uid, .. }) => {
+                assert!(expect_match, "unexpected match for {node_uid:?}");
+                assert_eq!(root_uid, node_uid);
+            }
+            MaybeMatchWithUid::Missed(_) => {
+                assert!(!expect_match, "expected a match for {node_uid:?}");
+            }
+        }
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_high_fan_out() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let children = EdgeName::try_from("children")?;
+    let parent = EdgeName::try_from("parent")?;
+    let created_file = EdgeName::try_from("created_file")?;
+    let created_by_process = EdgeName::try_from("created_by_process")?;
+
+    let mutation::CreateNodeResponse { uid: parent_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // Well over GRAPH_QUERY_MAX_CONCURRENT_NEIGHBOR_FETCHES children, only a
+    // few of which match, so that mismatching neighbors are fetched
+    // concurrently with the matching ones. Only some of the matching children
+    // created a file.
+    let child_count = 40;
+    let mut matching_child_uids = FxHashSet::default();
+    let mut created_files = vec![];
+    for i in 0..child_count {
+        let mutation::CreateNodeResponse { uid: child_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        let process_name = if i % 13 == 0 {
+            matching_child_uids.insert(child_uid);
+            "match.exe"
+        } else {
+            "other.exe"
+        };
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid: child_uid,
+                node_type: process_node_type.clone(),
+                property_name: "process_name".try_into()?,
+                property: NodeProperty {
+                    property: Property::ImmutableStrProp(ImmutableStrProp {
+                        prop: process_name.into(),
+                    }),
+                },
+            })
+            .await?;
+
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: children.clone(),
+                tenant_id,
+                from_uid: parent_uid,
+                to_uid: child_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+
+        if i % 26 == 0 {
+            let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+                .create_node(mutation::CreateNodeRequest {
+                    tenant_id,
+                    node_type: file_node_type.clone(),
+                })
+                .await?;
+            graph_mutation_client
+                .create_edge(mutation::CreateEdgeRequest {
+                    edge_name: created_file.clone(),
+                    tenant_id,
+                    from_uid: child_uid,
+                    to_uid: file_uid,
+                    source_node_type: process_node_type.clone(),
+                })
+                .await?;
+            created_files.push((child_uid, file_uid));
+        }
+    }
+    assert_eq!(matching_child_uids.len(), 4);
+    assert_eq!(created_files.len(), 2);
+
+    let children_named = |process_name: &str| {
+        let process_name = process_name.to_owned();
+        NodeQuery::root(process_node_type.clone())
+            .with_edge_to(
+                children.clone(),
+                parent.clone(),
+                process_node_type.clone(),
+                |child| {
+                    child.with_string_comparisons(
+                        "process_name".try_into().unwrap(),
+                        vec![StringCmp::Eq(process_name, false)],
+                    );
+                },
+            )
+            .build()
+    };
+
+    // A mismatching sibling only rules itself out, so every matching child is
+    // in the graph
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query: children_named("match.exe"),
+        })
+        .await?;
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+
+    assert_eq!(matched_graph.nodes.len(), matching_child_uids.len() + 1);
+    assert_eq!(
+        matched_graph.edges[&(parent_uid, children.clone())],
+        matching_child_uids
+    );
+    for child_uid in matching_child_uids.iter() {
+        assert!(matched_graph.nodes.contains_key(child_uid));
+        assert_eq!(
+            matched_graph.edges[&(*child_uid, parent.clone())],
+            FxHashSet::from_iter([parent_uid])
+        );
+    }
+
+    // Neither does a matching child without its own required edge, so only
+    // the children that created a file are in the graph
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_edge_to(
+            children.clone(),
+            parent.clone(),
+            process_node_type.clone(),
+            |child| {
+                child
+                    .with_string_comparisons(
+                        "process_name".try_into().unwrap(),
+                        vec![StringCmp::Eq("match.exe".to_owned(), false)],
+                    )
+                    .with_edge_to(
+                        created_file.clone(),
+                        created_by_process.clone(),
+                        file_node_type.clone(),
+                        |_| {},
+                    );
+            },
+        )
+        .build();
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query,
+        })
+        .await?;
+    let matched_graph = response.matched_graph.expect("Expected a matched graph");
+
+    assert_eq!(matched_graph.nodes.len(), 2 * created_files.len() + 1);
+    assert_eq!(
+        matched_graph.edges[&(parent_uid, children.clone())],
+        created_files
+            .iter()
+            .map(|(child_uid, _)| *child_uid)
+            .collect::<FxHashSet<_>>()
+    );
+    for (child_uid, file_uid) in created_files.iter() {
+        assert_eq!(
+            matched_graph.edges[&(*child_uid, created_file.clone())],
+            FxHashSet::from_iter([*file_uid])
+        );
+    }
+
+    // When no neighbor matches a required edge, the whole query misses
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query: children_named("absent.exe"),
+        })
+        .await?;
+    assert!(response.matched_graph.is_none());
+
+    // And so does a query whose other required edge is missing, however many
+    // of the children match
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_edge_to(
+            children.clone(),
+            parent.clone(),
+            process_node_type.clone(),
+            |child| {
+                child.with_string_comparisons(
+                    "process_name".try_into().unwrap(),
+                    vec![StringCmp::Eq("other.exe".to_owned(), false)],
+                );
+            },
+        )
+        .with_edge_to(
+            created_file.clone(),
+            created_by_process.clone(),
+            file_node_type.clone(),
+            |_| {},
+        )
+        .build();
+    let response = graph_query_client
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query,
+        })
+        .await?;
+    assert!(response.matched_graph.is_none());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_traverse_edge() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let children = EdgeName::try_from("children")?;
+    let parent = EdgeName::try_from("parent")?;
+
+    let create_process = || {
+        let mut graph_mutation_client = graph_mutation_client.clone();
+        let node_type = process_node_type.clone();
+        async move {
+            let mutation::CreateNodeResponse { uid } = graph_mutation_client
+                .create_node(mutation::CreateNodeRequest {
+                    tenant_id,
+                    node_type,
+                })
+                .await?;
+            Ok::<_, eyre::Report>(uid)
+        }
+    };
+
+    // grandparent -> parent -> { child, sibling }
+    let grandparent_uid = create_process().await?;
+    let parent_uid = create_process().await?;
+    let child_uid = create_process().await?;
+    let sibling_uid = create_process().await?;
+
+    for (from_uid, to_uid) in [
+        (grandparent_uid, parent_uid),
+        (parent_uid, child_uid),
+        (parent_uid, sibling_uid),
+    ] {
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: children.clone(),
+                tenant_id,
+                from_uid,
+                to_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    let traverse_request =
+        |node_uid, edge_name: &EdgeName, max_depth, max_nodes| TraverseEdgeRequest {
+            tenant_id,
+            node_uid,
+            edge_name: edge_name.clone(),
+            max_depth,
+            max_nodes,
+            timeout: Duration::from_secs(10),
+        };
+
+    // `parent` is ToOne, so the ancestors form a chain
+    let ancestors = graph_query_client
+        .traverse_edge(traverse_request(child_uid, &parent, 10, 100))
+        .await?;
+    assert!(!ancestors.truncated);
+    assert_eq!(ancestors.graph.nodes.len(), 3);
+    assert!(ancestors.graph.nodes.contains_key(&grandparent_uid));
+    assert!(ancestors.graph.edges[&(child_uid, parent.clone())].contains(&parent_uid));
+    assert!(ancestors.graph.edges[&(parent_uid, children.clone())].contains(&child_uid));
+
+    // `children` is ToMany, so the descendants form a tree
+    let process_tree = graph_query_client
+        .traverse_edge(traverse_request(grandparent_uid, &children, 10, 100))
+        .await?;
+    assert!(!process_tree.truncated);
+    assert_eq!(process_tree.graph.nodes.len(), 4);
+    assert_eq!(
+        process_tree.graph.edges[&(parent_uid, children.clone())].len(),
+        2
+    );
+
+    // Stopping at max_depth isn't truncation, but hitting max_nodes is
+    let first_hop = graph_query_client
+        .traverse_edge(traverse_request(grandparent_uid, &children, 1, 100))
+        .await?;
+    assert!(!first_hop.truncated);
+    assert_eq!(first_hop.graph.nodes.len(), 2);
+
+    let guarded = graph_query_client
+        .traverse_edge(traverse_request(grandparent_uid, &children, 10, 3))
+        .await?;
+    assert!(guarded.truncated);
+    assert_eq!(guarded.graph.nodes.len(), 3);
+
+    let invalid_depth = graph_query_client
+        .traverse_edge(traverse_request(grandparent_uid, &children, 0, 100))
+        .await;
+    assert!(invalid_depth.is_err());
+
+    // An edge the schema doesn't declare is the caller's mistake
+    let undeclared_edge = graph_query_client
+        .traverse_edge(traverse_request(
+            grandparent_uid,
+            &EdgeName::try_from("siblings")?,
+            10,
+            100,
+        ))
+        .await;
+    match undeclared_edge {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument, "{status}")
+        }
+        other => panic!("expected InvalidArgument, got {other:?}"),
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_get_neighborhood() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let file_node_type = NodeType::try_from("File").unwrap();
+    let process_name = PropertyName::try_from("process_name")?;
+    let process_id = PropertyName::try_from("process_id")?;
+    let file_path = PropertyName::try_from("file_path")?;
+    let created_file = EdgeName::try_from("created_file")?;
+    let created_by_process = EdgeName::try_from("created_by_process")?;
+
+    let mutation::CreateNodeResponse { uid: process_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+    let mutation::CreateNodeResponse { uid: file_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+
+    for (uid, node_type, property_name, property) in [
+        (
+            process_uid,
+            process_node_type.clone(),
+            process_name.clone(),
+            Property::ImmutableStrProp(ImmutableStrProp {
+                prop: "chrome.exe".into(),
+            }),
+        ),
+        (
+            process_uid,
+            process_node_type.clone(),
+            process_id.clone(),
+            Property::ImmutableUintProp(ImmutableUintProp { prop: 1234 }),
+        ),
+        (
+            file_uid,
+            file_node_type.clone(),
+            file_path.clone(),
+            Property::ImmutableStrProp(ImmutableStrProp {
+                prop: "/usr/bin/chrome".into(),
+            }),
+        ),
+    ] {
+        graph_mutation_client
+            .set_node_property(mutation::SetNodePropertyRequest {
+                tenant_id,
+                uid,
+                node_type,
+                property_name,
+                property: NodeProperty { property },
+            })
+            .await?;
+    }
+
+    graph_mutation_client
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: created_file.clone(),
+            tenant_id,
+            from_uid: process_uid,
+            to_uid: file_uid,
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    // Every property is returned without having to name any of them
+    let node_only = graph_query_client
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: process_uid,
+            include_neighbors: false,
+            max_edges: 100,
+        })
+        .await?
+        .graph;
+    assert_eq!(node_only.nodes.len(), 1);
+    let process = &node_only.nodes[&process_uid];
+    assert_eq!(process.node_type, process_node_type);
+    assert_eq!(
+        process.string_properties.prop_map[&process_name],
+        "chrome.exe"
+    );
+    assert_eq!(process.uint64_properties.prop_map[&process_id], 1234);
+    assert!(node_only.edges[&(process_uid, created_file.clone())].contains(&file_uid));
+    assert!(node_only.edges[&(file_uid, created_by_process.clone())].contains(&process_uid));
+
+    let with_neighbors = graph_query_client
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: process_uid,
+            include_neighbors: true,
+            max_edges: 100,
+        })
+        .await?
+        .graph;
+    assert_eq!(with_neighbors.nodes.len(), 2);
+    assert_eq!(
+        with_neighbors.nodes[&file_uid].string_properties.prop_map[&file_path],
+        "/usr/bin/chrome"
+    );
+
+    // With more edges than max_edges, only max_edges of them and their
+    // neighbors are returned
+    let mutation::CreateNodeResponse {
+        uid: second_file_uid,
+    } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: file_node_type.clone(),
+        })
+        .await?;
+    graph_mutation_client
+        .create_edge(mutation::CreateEdgeRequest {
+            edge_name: created_file.clone(),
+            tenant_id,
+            from_uid: process_uid,
+            to_uid: second_file_uid,
+            source_node_type: process_node_type.clone(),
+        })
+        .await?;
+
+    let truncated = graph_query_client
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: process_uid,
+            include_neighbors: true,
+            max_edges: 1,
+        })
+        .await?;
+    assert!(truncated.truncated);
+    assert_eq!(truncated.graph.nodes.len(), 2);
+    assert_eq!(
+        truncated.graph.edges[&(process_uid, created_file.clone())].len(),
+        1
+    );
+
+    let untruncated = graph_query_client
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: process_uid,
+            include_neighbors: true,
+            max_edges: 2,
+        })
+        .await?;
+    assert!(!untruncated.truncated);
+    assert_eq!(untruncated.graph.nodes.len(), 3);
+
+    match graph_query_client
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: process_uid,
+            include_neighbors: false,
+            max_edges: 0,
+        })
+        .await
+    {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::InvalidArgument, "{status}")
+        }
+        other => panic!("expected InvalidArgument, got {other:?}"),
+    }
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_find_nodes() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let process_name = PropertyName::try_from("process_name")?;
+    let process_id = PropertyName::try_from("process_id")?;
+
+    let mut uids = vec![];
+    for (name, pid) in [
+        ("mimikatz.exe", 100),
+        ("chrome.exe", 200),
+        ("chromium", 300),
+    ] {
+        let mutation::CreateNodeResponse { uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+        for (property_name, property) in [
+            (
+                process_name.clone(),
+                Property::ImmutableStrProp(ImmutableStrProp { prop: name.into() }),
+            ),
+            (
+                process_id.clone(),
+                Property::ImmutableUintProp(ImmutableUintProp { prop: pid }),
+            ),
+        ] {
+            graph_mutation_client
+                .set_node_property(mutation::SetNodePropertyRequest {
+                    tenant_id,
+                    uid,
+                    node_type: process_node_type.clone(),
+                    property_name,
+                    property: NodeProperty { property },
+                })
+                .await?;
+        }
+        uids.push(uid);
+    }
+    let (mimikatz_uid, chrome_uid, chromium_uid) = (uids[0], uids[1], uids[2]);
+
+    let find_request = |property_name: &PropertyName, filter, max_results| FindNodesRequest {
+        tenant_id,
+        node_type: process_node_type.clone(),
+        property_name: property_name.clone(),
+        filter,
+        max_results,
+    };
+
+    let response = graph_query_client
+        .find_nodes(find_request(
+            &process_name,
+            PropertyValueFilter::StringEqual("mimikatz.exe".to_owned()),
+            10,
+        ))
+        .await?;
+    assert_eq!(response.uids, vec![mimikatz_uid]);
+    assert!(!response.truncated);
+
+    let mut response = graph_query_client
+        .find_nodes(find_request(
+            &process_name,
+            PropertyValueFilter::StringPrefix("chrom".to_owned()),
+            10,
+        ))
+        .await?;
+    response.uids.sort();
+    let mut expected = vec![chrome_uid, chromium_uid];
+    expected.sort();
+    assert_eq!(response.uids, expected);
+    assert!(!response.truncated);
+
+    let response = graph_query_client
+        .find_nodes(find_request(
+            &process_name,
+            PropertyValueFilter::StringPrefix("chrom".to_owned()),
+            1,
+        ))
+        .await?;
+    assert_eq!(response.uids.len(), 1);
+    assert!(response.truncated);
+
+    let response = graph_query_client
+        .find_nodes(find_request(
+            &process_id,
+            PropertyValueFilter::UInt64Equal(200),
+            10,
+        ))
+        .await?;
+    assert_eq!(response.uids, vec![chrome_uid]);
+
+    let response = graph_query_client
+        .find_nodes(find_request(
+            &process_name,
+            PropertyValueFilter::StringEqual("firefox.exe".to_owned()),
+            10,
+        ))
+        .await?;
+    assert!(response.uids.is_empty());
+
+    drop(_span);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_find_nodes_drops_rewritten_values() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_query_client,
+        mut graph_mutation_client,
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let process_name = PropertyName::try_from("process_name")?;
+
+    let mutation::CreateNodeResponse { uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+    graph_mutation_client
+        .set_node_property(mutation::SetNodePropertyRequest {
+            tenant_id,
+            uid,
+            node_type: process_node_type.clone(),
+            property_name: process_name.clone(),
+            property: NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "notepad.exe".into(),
+                }),
+            },
+        })
+        .await?;
+
+    // Rewrite the value the way another graph-mutation instance, whose write
+    // dropper hadn't seen the first write, would. The first value's index
+    // entry is left behind.
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+    let session = scylla_session().await?;
+    session
+        .query(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{IMM_STRING_TABLE_NAME}
+                (uid, populated_field, value)
+                VALUES (?, ?, ?)
+                "
+            ),
+            (uid.as_i64(), &process_name.value, "calc.exe"),
+        )
+        .await?;
+    session
+        .query(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{IMM_STRING_INDEX_TABLE_NAME}
+                (node_type, populated_field, value, uid)
+                VALUES (?, ?, ?, ?)
+                "
+            ),
+            (
+                &process_node_type.value,
+                &process_name.value,
+                "calc.exe",
+                uid.as_i64(),
+            ),
+        )
+        .await?;
+
+    let find_request = |filter| FindNodesRequest {
+        tenant_id,
+        node_type: process_node_type.clone(),
+        property_name: process_name.clone(),
+        filter,
+        max_results: 10,
+    };
+
+    for stale_filter in [
+        PropertyValueFilter::StringEqual("notepad.exe".to_owned()),
+        PropertyValueFilter::StringPrefix("notepad".to_owned()),
+    ] {
+        let response = graph_query_client
+            .find_nodes(find_request(stale_filter))
+            .await?;
+        assert!(response.uids.is_empty());
+    }
+
+    let response = graph_query_client
+        .find_nodes(find_request(PropertyValueFilter::StringEqual(
+            "calc.exe".to_owned(),
+        )))
+        .await?;
+    assert_eq!(response.uids, vec![uid]);
+
+    drop(_span);
+    Ok(())
+}
+
+async fn scylla_session() -> eyre::Result<scylla::Session> {
+    let graph_db_config = GraphDbConfig::parse();
+    let mut scylla_config = scylla::SessionConfig::new();
+    scylla_config.add_known_nodes_addr(&graph_db_config.graph_db_addresses[..]);
+    scylla_config.auth_username = Some(graph_db_config.graph_db_auth_username.to_owned());
+    scylla_config.auth_password = Some(
+        graph_db_config
+            .graph_db_auth_password
+            .expose_secret()
+            .to_owned(),
+    );
+    Ok(scylla::Session::connect(scylla_config).await?)
+}
+
+/// A service running in the test, rather than the deployed one, so that its
+/// query limits can be small enough for a test to run into.
+async fn graph_query_service_with_limits(
+    query_limits: QueryLimits,
+) -> eyre::Result<GraphQueryService> {
+    let scylla_client = Arc::new(CachingSession::from(scylla_session().await?, 10_000));
+
+    let graph_schema_manager_client =
+        GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+            .await?;
+
+    Ok(GraphQueryService::new(
+        scylla_client,
+        PropertyCache::with_capacity(1_000, Duration::from_secs(60)),
+        EdgeCardinalityResolver::new(graph_schema_manager_client, 1_000, Duration::from_secs(60)),
+        4,
+        query_limits,
+    ))
+}
+
+fn query_budget_exceeded(status: Status) -> QueryBudgetExceeded {
+    assert_eq!(status.code(), Code::ResourceExhausted, "{status}");
+    QueryBudgetExceeded::deserialize(status.details()).expect("budget exceeded details")
+}
+
+#[test_log::test(tokio::test)]
+async fn test_query_budget_exceeded() -> eyre::Result<()> {
+    let _span = tracing::info_span!(
+        "tenant_id", tenant_id=?tracing::field::Empty,
+    );
+
+    let TestTenant {
+        tenant_id,
+        mut graph_mutation_client,
+        ..
+    } = provision_tenant(&_span).await?;
+
+    let process_node_type = NodeType::try_from("Process").unwrap();
+    let children = EdgeName::try_from("children")?;
+    let parent = EdgeName::try_from("parent")?;
+
+    let mutation::CreateNodeResponse { uid: parent_uid } = graph_mutation_client
+        .create_node(mutation::CreateNodeRequest {
+            tenant_id,
+            node_type: process_node_type.clone(),
+        })
+        .await?;
+    for _ in 0..5 {
+        let mutation::CreateNodeResponse { uid: child_uid } = graph_mutation_client
+            .create_node(mutation::CreateNodeRequest {
+                tenant_id,
+                node_type: process_node_type.clone(),
+            })
+            .await?;
+        graph_mutation_client
+            .create_edge(mutation::CreateEdgeRequest {
+                edge_name: children.clone(),
+                tenant_id,
+                from_uid: parent_uid,
+                to_uid: child_uid,
+                source_node_type: process_node_type.clone(),
+            })
+            .await?;
+    }
+
+    let generous_limits = QueryLimits {
+        max_nodes_visited: 1_000,
+        max_round_trips: 1_000,
+        timeout: Duration::from_secs(30),
+    };
+    let graph_query = NodeQuery::root(process_node_type.clone())
+        .with_edge_to(
+            children.clone(),
+            parent.clone(),
+            process_node_type.clone(),
+            |_| {},
+        )
+        .build();
+
+    // Within its limits, the query matches
+    let graph_query_service = graph_query_service_with_limits(generous_limits).await?;
+    let response = graph_query_service
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query: graph_query.clone(),
+        })
+        .await?;
+    assert!(response.matched_graph.is_some());
+
+    // Matching the parent and its children visits more than 2 nodes
+    let graph_query_service = graph_query_service_with_limits(QueryLimits {
+        max_nodes_visited: 2,
+        ..generous_limits
+    })
+    .await?;
+    let status: Status = graph_query_service
+        .query_graph_from_uid(QueryGraphFromUidRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            graph_query,
+        })
+        .await
+        .expect_err("expected the query to exceed its node limit")
+        .into();
+    let exceeded = query_budget_exceeded(status);
+    assert_eq!(exceeded.limit, QueryLimit::NodesVisited);
+    assert_eq!(exceeded.nodes_visited, 2);
+
+    // Fetching a node's properties and edges takes more than one round trip,
+    // and GetNeighborhood is held to the same limits as graph queries
+    let graph_query_service = graph_query_service_with_limits(QueryLimits {
+        max_round_trips: 1,
+        ..generous_limits
+    })
+    .await?;
+    let status: Status = graph_query_service
+        .get_neighborhood(GetNeighborhoodRequest {
+            tenant_id,
+            node_uid: parent_uid,
+            include_neighbors: true,
+            max_edges: 100,
+        })
+        .await
+        .expect_err("expected the neighborhood to exceed its round trip limit")
+        .into();
+    let exceeded = query_budget_exceeded(status);
+    assert_eq!(exceeded.limit, QueryLimit::RoundTrips);
+    assert_eq!(exceeded.round_trips, 1);
+
+    drop(_span);
+    Ok(())
+}
+
 // TODO: test `with_edge_to`
@@ -0,0 +1,4 @@
+-- Properties which keep the last value written, rather than the first,
+-- largest or smallest.
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'MutableString';
+ALTER TYPE graph_schema_manager.property_type ADD VALUE IF NOT EXISTS 'MutableF64';
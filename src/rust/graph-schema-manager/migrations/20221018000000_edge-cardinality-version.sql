@@ -0,0 +1,14 @@
+-- EdgeRel::to_one/to_many used to have OneToMany and ManyToOne swapped, so
+-- every edge whose forward and reverse cardinalities differ was stored the
+-- wrong way around. Rather than rewriting those rows, each row records how its
+-- cardinalities were stored:
+--
+--   1: swapped whenever the forward and reverse cardinalities differ
+--   2: as declared in the schema
+--
+-- The default is 1 so that a schema manager still running the old code during
+-- a rolling deploy goes on marking the rows it writes as swapped. The current
+-- code always writes 2, and reads both.
+ALTER TABLE graph_schema_manager.edge_schemas
+    ADD COLUMN cardinality_version smallint NOT NULL DEFAULT 1
+        CHECK (cardinality_version IN (1, 2));
@@ -2,6 +2,7 @@ use grapl_config::PostgresClient;
 use rust_proto::graplinc::grapl::common::v1beta1::types::{
     EdgeName,
     NodeType,
+    PropertyName,
 };
 use sqlx::{
     Postgres,
@@ -13,7 +14,9 @@ use crate::{
     config::SchemaDbConfig,
     db::models::{
         GetEdgeSchemaRequestRow,
+        GetPropertySchemaRequestRow,
         StoredEdgeCardinality,
+        CARDINALITY_VERSION,
     },
 };
 
@@ -56,7 +59,8 @@ impl SchemaDbClient {
             r#"select
                 reverse_edge_name,
                 forward_edge_cardinality as "forward_edge_cardinality: StoredEdgeCardinality",
-                reverse_edge_cardinality as "reverse_edge_cardinality: StoredEdgeCardinality"
+                reverse_edge_cardinality as "reverse_edge_cardinality: StoredEdgeCardinality",
+                cardinality_version
              FROM graph_schema_manager.edge_schemas
              WHERE
                  tenant_id = $1 AND
@@ -73,6 +77,32 @@ impl SchemaDbClient {
         .await
     }
 
+    pub async fn get_property_schema(
+        &self,
+        tenant_id: uuid::Uuid,
+        node_type: NodeType,
+        property_name: PropertyName,
+    ) -> Result<GetPropertySchemaRequestRow, sqlx::Error> {
+        sqlx::query_as!(
+            GetPropertySchemaRequestRow,
+            r#"select
+                property_type as "property_type: StoredPropertyType"
+             FROM graph_schema_manager.property_schemas
+             WHERE
+                 tenant_id = $1 AND
+                 node_type = $2 AND
+                 property_name = $3
+             ORDER BY schema_version DESC
+             LIMIT 1;
+                 "#,
+            tenant_id,
+            node_type.value,
+            property_name.value,
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
     pub async fn insert_node_identity_algorithm(
         &self,
         txn: &mut Txn<'_>,
@@ -261,9 +291,10 @@ impl SchemaDbClient {
                 forward_edge_name,
                 reverse_edge_name,
                 forward_edge_cardinality,
-                reverse_edge_cardinality
+                reverse_edge_cardinality,
+                cardinality_version
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
             tenant_id,
             node_type_name,
@@ -272,6 +303,7 @@ impl SchemaDbClient {
             reverse_edge_name,
             forward_edge_cardinality as StoredEdgeCardinality,
             reverse_edge_cardinality as StoredEdgeCardinality,
+            CARDINALITY_VERSION,
         )
         .execute(&mut *txn)
         .await?;
@@ -285,9 +317,10 @@ impl SchemaDbClient {
                 forward_edge_name,
                 reverse_edge_name,
                 forward_edge_cardinality,
-                reverse_edge_cardinality
+                reverse_edge_cardinality,
+                cardinality_version
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
             tenant_id,
             node_type_name,
@@ -296,6 +329,7 @@ impl SchemaDbClient {
             forward_edge_name,
             reverse_edge_cardinality as StoredEdgeCardinality,
             forward_edge_cardinality as StoredEdgeCardinality,
+            CARDINALITY_VERSION,
         )
         .execute(&mut *txn)
         .await?;
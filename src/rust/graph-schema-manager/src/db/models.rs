@@ -1,4 +1,7 @@
-use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::EdgeCardinality;
+use rust_proto::graplinc::grapl::api::graph_schema_manager::v1beta1::messages::{
+    EdgeCardinality,
+    PropertyType,
+};
 
 #[derive(sqlx::Type, Copy, Clone, Debug)]
 #[sqlx(type_name = "edge_cardinality")]
@@ -7,11 +10,34 @@ pub enum StoredEdgeCardinality {
     ToMany,
 }
 
+/// Edge schema rows with this `cardinality_version` have the forward and
+/// reverse cardinalities of every edge whose cardinalities differ swapped.
+/// These were written before the edge-cardinality-version migration, or by a
+/// schema manager which predates it.
+const SWAPPED_CARDINALITY_VERSION: i16 = 1;
+
+/// The `cardinality_version` of the edge schema rows written by this schema
+/// manager, which store cardinalities as declared.
+pub const CARDINALITY_VERSION: i16 = 2;
+
 #[derive(sqlx::Type, Clone, Debug)]
 pub struct GetEdgeSchemaRequestRow {
     pub reverse_edge_name: String,
     pub forward_edge_cardinality: StoredEdgeCardinality,
     pub reverse_edge_cardinality: StoredEdgeCardinality,
+    pub cardinality_version: i16,
+}
+
+impl GetEdgeSchemaRequestRow {
+    /// The edge's cardinality and its reverse's, as declared in the schema,
+    /// whichever way around the row stores them.
+    pub fn declared_cardinalities(&self) -> (StoredEdgeCardinality, StoredEdgeCardinality) {
+        if self.cardinality_version == SWAPPED_CARDINALITY_VERSION {
+            (self.reverse_edge_cardinality, self.forward_edge_cardinality)
+        } else {
+            (self.forward_edge_cardinality, self.reverse_edge_cardinality)
+        }
+    }
 }
 
 impl From<StoredEdgeCardinality> for EdgeCardinality {
@@ -33,6 +59,29 @@ pub enum StoredPropertyType {
     ImmutableU64,
     MaxU64,
     MinU64,
+    MutableString,
+    MutableF64,
+}
+
+#[derive(sqlx::Type, Clone, Debug)]
+pub struct GetPropertySchemaRequestRow {
+    pub property_type: StoredPropertyType,
+}
+
+impl From<StoredPropertyType> for PropertyType {
+    fn from(t: StoredPropertyType) -> Self {
+        match t {
+            StoredPropertyType::ImmutableString => PropertyType::ImmutableString,
+            StoredPropertyType::ImmutableI64 => PropertyType::ImmutableI64,
+            StoredPropertyType::MaxI64 => PropertyType::MaxI64,
+            StoredPropertyType::MinI64 => PropertyType::MinI64,
+            StoredPropertyType::ImmutableU64 => PropertyType::ImmutableU64,
+            StoredPropertyType::MaxU64 => PropertyType::MaxU64,
+            StoredPropertyType::MinU64 => PropertyType::MinU64,
+            StoredPropertyType::MutableString => PropertyType::MutableString,
+            StoredPropertyType::MutableF64 => PropertyType::MutableF64,
+        }
+    }
 }
 
 #[derive(sqlx::Type, Clone, Debug)]
@@ -86,3 +135,36 @@ struct EdgeSchemaRow {
     forward_edge_cardinality: StoredEdgeCardinality,
     reverse_edge_cardinality: StoredEdgeCardinality,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        forward_edge_cardinality: StoredEdgeCardinality,
+        reverse_edge_cardinality: StoredEdgeCardinality,
+        cardinality_version: i16,
+    ) -> GetEdgeSchemaRequestRow {
+        GetEdgeSchemaRequestRow {
+            reverse_edge_name: "executed_as_processes".to_owned(),
+            forward_edge_cardinality,
+            reverse_edge_cardinality,
+            cardinality_version,
+        }
+    }
+
+    #[test]
+    fn test_declared_cardinalities() {
+        use StoredEdgeCardinality::*;
+
+        // `binary_file: File` whose reverse is `executed_as_processes: [Process]`
+        let swapped = row(ToMany, ToOne, SWAPPED_CARDINALITY_VERSION);
+        assert!(matches!(swapped.declared_cardinalities(), (ToOne, ToMany)));
+
+        let declared = row(ToOne, ToMany, CARDINALITY_VERSION);
+        assert!(matches!(declared.declared_cardinalities(), (ToOne, ToMany)));
+
+        let same = row(ToMany, ToMany, SWAPPED_CARDINALITY_VERSION);
+        assert!(matches!(same.declared_cardinalities(), (ToMany, ToMany)));
+    }
+}
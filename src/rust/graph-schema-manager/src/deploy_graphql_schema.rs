@@ -352,20 +352,37 @@ fn get_predicate_type_name(
         (PredicateType::String, ConflictResolution::Immutable) => {
             StoredPropertyType::ImmutableString
         }
+        (PredicateType::String, ConflictResolution::Mutable) => StoredPropertyType::MutableString,
         (PredicateType::I64, ConflictResolution::Immutable) => StoredPropertyType::ImmutableI64,
         (PredicateType::I64, ConflictResolution::IncrementOnly) => StoredPropertyType::MaxI64,
         (PredicateType::I64, ConflictResolution::DecrementOnly) => StoredPropertyType::MinI64,
         (PredicateType::U64, ConflictResolution::Immutable) => StoredPropertyType::ImmutableU64,
         (PredicateType::U64, ConflictResolution::IncrementOnly) => StoredPropertyType::MaxU64,
         (PredicateType::U64, ConflictResolution::DecrementOnly) => StoredPropertyType::MinU64,
+        (PredicateType::F64, ConflictResolution::Mutable) => StoredPropertyType::MutableF64,
         (PredicateType::String, ConflictResolution::IncrementOnly) => {
             return Err(DeployGraphqlError::InvalidSchema(
-                "String can only be ImmutableString. Got IncrementOnly",
+                "String can only be Immutable or Mutable. Got IncrementOnly",
             ));
         }
         (PredicateType::String, ConflictResolution::DecrementOnly) => {
             return Err(DeployGraphqlError::InvalidSchema(
-                "String can only be ImmutableString. Got DecrementOnly",
+                "String can only be Immutable or Mutable. Got DecrementOnly",
+            ));
+        }
+        (PredicateType::I64, ConflictResolution::Mutable) => {
+            return Err(DeployGraphqlError::InvalidSchema(
+                "Int can only be Immutable, IncrementOnly or DecrementOnly. Got Mutable",
+            ));
+        }
+        (PredicateType::U64, ConflictResolution::Mutable) => {
+            return Err(DeployGraphqlError::InvalidSchema(
+                "UInt can only be Immutable, IncrementOnly or DecrementOnly. Got Mutable",
+            ));
+        }
+        (PredicateType::F64, _) => {
+            return Err(DeployGraphqlError::InvalidSchema(
+                "Float can only be Mutable",
             ));
         }
     };
@@ -6,6 +6,8 @@ use rust_proto::{
                 DeploySchemaResponse,
                 GetEdgeSchemaRequest,
                 GetEdgeSchemaResponse,
+                GetPropertySchemaRequest,
+                GetPropertySchemaResponse,
                 SchemaType,
             },
             server::GraphSchemaManagerApi,
@@ -32,6 +34,8 @@ pub enum GraphSchemaManagerServiceError {
     DeployGraphqlError(#[from] DeployGraphqlError),
     #[error("GetEdgeSchema sqlx error {0}")]
     GetEdgeSchemaSqlxError(sqlx::Error),
+    #[error("GetPropertySchema sqlx error {0}")]
+    GetPropertySchemaSqlxError(sqlx::Error),
     #[error("Invalid ReverseEdgeName: {0}")]
     InvalidReverseEdgeName(SerDeError),
 }
@@ -48,9 +52,18 @@ impl From<GraphSchemaManagerServiceError> for Status {
             GraphSchemaManagerServiceError::DeployGraphqlError(e) => {
                 Status::invalid_argument(format!("DeployGraphqlError - {}", e))
             }
+            GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError(sqlx::Error::RowNotFound) => {
+                Status::not_found("No such edge in the tenant's schema")
+            }
             GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError(e) => {
                 Status::internal(format!("SqlError during deployment - {}", e))
             }
+            GraphSchemaManagerServiceError::GetPropertySchemaSqlxError(
+                sqlx::Error::RowNotFound,
+            ) => Status::not_found("No such property in the tenant's schema"),
+            GraphSchemaManagerServiceError::GetPropertySchemaSqlxError(e) => {
+                Status::internal(format!("SqlError during property lookup - {}", e))
+            }
             GraphSchemaManagerServiceError::InvalidReverseEdgeName(name) => {
                 Status::internal(format!("InvalidReverseEdgeName - {}", name))
             }
@@ -103,11 +116,33 @@ impl GraphSchemaManagerApi for GraphSchemaManager {
             .await
             .map_err(GraphSchemaManagerServiceError::GetEdgeSchemaSqlxError)?;
 
+        let (cardinality, reverse_cardinality) = response.declared_cardinalities();
         Ok(GetEdgeSchemaResponse {
             reverse_edge_name: EdgeName::try_from(response.reverse_edge_name)
                 .map_err(GraphSchemaManagerServiceError::InvalidReverseEdgeName)?,
-            cardinality: response.forward_edge_cardinality.into(),
-            reverse_cardinality: response.reverse_edge_cardinality.into(),
+            cardinality: cardinality.into(),
+            reverse_cardinality: reverse_cardinality.into(),
+        })
+    }
+
+    async fn get_property_schema(
+        &self,
+        request: GetPropertySchemaRequest,
+    ) -> Result<GetPropertySchemaResponse, Self::Error> {
+        let GetPropertySchemaRequest {
+            tenant_id,
+            node_type,
+            property_name,
+        } = request;
+
+        let response = self
+            .db_client
+            .get_property_schema(tenant_id, node_type, property_name)
+            .await
+            .map_err(GraphSchemaManagerServiceError::GetPropertySchemaSqlxError)?;
+
+        Ok(GetPropertySchemaResponse {
+            property_type: response.property_type.into(),
         })
     }
 }
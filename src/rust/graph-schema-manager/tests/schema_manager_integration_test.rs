@@ -11,7 +11,11 @@ use rust_proto::{
         },
         common::v1beta1::types as common_api,
     },
-    protocol::service_client::ConnectWithConfig,
+    protocol::{
+        error::GrpcClientError,
+        service_client::ConnectWithConfig,
+        status::Code,
+    },
 };
 
 pub fn get_example_graphql_schema() -> Result<Bytes, std::io::Error> {
@@ -49,5 +53,74 @@ async fn test_deploy_schema() -> eyre::Result<()> {
         .await?;
 
     assert_eq!(edge_schema.reverse_edge_name.value, "executed_as_processes");
+    assert_eq!(edge_schema.cardinality, sm_api::EdgeCardinality::ToOne);
+    assert_eq!(
+        edge_schema.reverse_cardinality,
+        sm_api::EdgeCardinality::ToMany
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_property_schema() -> eyre::Result<()> {
+    let client_config = GraphSchemaManagerClientConfig::parse();
+    let mut client = GraphSchemaManagerClient::connect_with_config(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    client
+        .deploy_schema(sm_api::DeploySchemaRequest {
+            tenant_id,
+            schema: get_example_graphql_schema()?,
+            schema_type: sm_api::SchemaType::GraphqlV0,
+            schema_version: 0,
+        })
+        .await?;
+
+    for (node_type, property_name, expected_type) in [
+        (
+            "Process",
+            "process_name",
+            sm_api::PropertyType::ImmutableString,
+        ),
+        ("Process", "process_id", sm_api::PropertyType::ImmutableU64),
+        ("Process", "last_seen_at", sm_api::PropertyType::MaxU64),
+        (
+            "Process",
+            "current_user",
+            sm_api::PropertyType::MutableString,
+        ),
+        ("File", "entropy", sm_api::PropertyType::MutableF64),
+    ] {
+        let property_schema = client
+            .get_property_schema(sm_api::GetPropertySchemaRequest {
+                tenant_id,
+                node_type: common_api::NodeType {
+                    value: node_type.to_string(),
+                },
+                property_name: common_api::PropertyName {
+                    value: property_name.to_string(),
+                },
+            })
+            .await?;
+        assert_eq!(property_schema.property_type, expected_type);
+    }
+
+    let undeclared = client
+        .get_property_schema(sm_api::GetPropertySchemaRequest {
+            tenant_id,
+            node_type: common_api::NodeType {
+                value: "Process".to_string(),
+            },
+            property_name: common_api::PropertyName {
+                value: "file_path".to_string(),
+            },
+        })
+        .await;
+    match undeclared {
+        Err(GrpcClientError::ErrorStatus(status)) => assert_eq!(status.code(), Code::NotFound),
+        other => panic!("expected NotFound, got {other:?}"),
+    }
+
     Ok(())
 }
@@ -8,10 +8,12 @@ pub const TERMINATE_TIME: &'static str = "terminate_time";
 pub const IMMUTABLE: &'static str = "immutable";
 pub const INCREMENT_ONLY: &'static str = "increment_only";
 pub const DECREMENT_ONLY: &'static str = "decrement_only";
+pub const MUTABLE: &'static str = "mutable";
 
 pub const STRING: &'static str = "String";
 pub const INT: &'static str = "Int";
 pub const UINT: &'static str = "UInt";
+pub const FLOAT: &'static str = "Float";
 
 pub const SESSION_ALGORITHM: &'static str = "session";
 pub const STATIC_ALGORITHM: &'static str = "static";
@@ -16,11 +16,14 @@ pub enum EdgeRel {
 
 // Python code generation for EdgeRel
 impl EdgeRel {
+    /// Whether the edge points to at most one node. The first half of each
+    /// variant's name is the forward relationship, e.g. `OneToMany` is a ToOne
+    /// edge whose reverse is ToMany.
     pub fn to_one(&self) -> bool {
         match self {
             Self::OneToOne => true,
-            Self::ManyToOne => true,
-            Self::OneToMany => false,
+            Self::OneToMany => true,
+            Self::ManyToOne => false,
             Self::ManyToMany => false,
         }
     }
@@ -28,8 +31,8 @@ impl EdgeRel {
     pub fn to_many(&self) -> bool {
         match self {
             Self::OneToOne => false,
-            Self::ManyToOne => false,
-            Self::OneToMany => true,
+            Self::OneToMany => false,
+            Self::ManyToOne => true,
             Self::ManyToMany => true,
         }
     }
@@ -102,3 +105,53 @@ impl TryFrom<&Field<'static, String>> for EdgeRel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge_rel(field_type: &str, reverse_relationship: &str) -> EdgeRel {
+        let schema = format!(
+            r#"
+            type Process @grapl(identity_algorithm: "session") {{
+                edge: {field_type} @edge(reverse: "reverse_edge", reverse_relationship: "{reverse_relationship}"),
+            }}
+            "#
+        );
+        let document = graphql_parser::parse_schema::<String>(&schema)
+            .unwrap()
+            .into_static();
+        let field = document
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                graphql_parser::schema::Definition::TypeDefinition(
+                    graphql_parser::schema::TypeDefinition::Object(object),
+                ) => object.fields.first().cloned(),
+                _ => None,
+            })
+            .unwrap();
+        EdgeRel::try_from(&field).unwrap()
+    }
+
+    #[test]
+    fn test_edge_rel_cardinality() {
+        // `binary_file: File` whose reverse is `executed_as_processes: [Process]`
+        let one_to_many = edge_rel("File", "ToMany");
+        assert!(matches!(one_to_many, EdgeRel::OneToMany));
+        assert!(one_to_many.to_one());
+        assert!(!one_to_many.to_many());
+        assert!(one_to_many.reverse().to_many());
+
+        let many_to_one = edge_rel("[Process]", "ToOne");
+        assert!(matches!(many_to_one, EdgeRel::ManyToOne));
+        assert!(many_to_one.to_many());
+        assert!(!many_to_one.to_one());
+        assert!(many_to_one.reverse().to_one());
+
+        assert!(edge_rel("File", "ToOne").to_one());
+        assert!(edge_rel("File!", "ToOne").reverse().to_one());
+        assert!(edge_rel("[File]", "ToMany").to_many());
+        assert!(edge_rel("[File]", "ToMany").reverse().to_many());
+    }
+}
@@ -77,6 +77,7 @@ impl NodePredicate {
             PredicateType::String => self.generate_python_str_comparisons(),
             PredicateType::I64 => self.generate_python_int_comparisons(),
             PredicateType::U64 => self.generate_python_int_comparisons(),
+            PredicateType::F64 => unreachable!("float predicates aren't generated"),
         }
     }
 
@@ -109,6 +110,7 @@ impl NodePredicate {
                 query_def += "                lt=lt,\n";
                 query_def += "                le=le,\n";
             }
+            PredicateType::F64 => unreachable!("float predicates aren't generated"),
         }
         query_def += "            )\n";
         query_def += "        )\n";
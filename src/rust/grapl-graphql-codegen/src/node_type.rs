@@ -18,6 +18,7 @@ use crate::{
     field_type::FieldType,
     identification_algorithm::IdentificationAlgorithm,
     node_predicate::NodePredicate,
+    predicate_type::PredicateType,
 };
 
 // Python Queryable generation
@@ -119,6 +120,13 @@ impl NodeType {
         Ok(())
     }
 
+    /// The predicates exposed to analyzers, see `PredicateType::F64`
+    fn python_predicates(&self) -> impl Iterator<Item = &NodePredicate> {
+        self.predicates
+            .iter()
+            .filter(|predicate| !matches!(predicate.predicate_type, PredicateType::F64))
+    }
+
     pub fn generate_python_code(&self) -> String {
         let mut pycode = String::with_capacity(256);
 
@@ -178,7 +186,7 @@ impl NodeType {
                 v, q
             );
 
-        for predicate in self.predicates.iter() {
+        for predicate in self.python_predicates() {
             queryable.push_str(&predicate.generate_python_query_def());
             queryable.push('\n');
         }
@@ -222,7 +230,7 @@ impl NodeType {
         viewable += "        graph_client: Any,\n";
         viewable += "        node_types: Set[str],\n";
 
-        for predicate in self.predicates.iter() {
+        for predicate in self.python_predicates() {
             let parameter = generate_parameter_from_predicate(predicate);
             viewable = viewable + "        " + &parameter + "\n";
         }
@@ -236,7 +244,7 @@ impl NodeType {
         viewable += "    ) -> None:\n";
         viewable += "        super().__init__(uid, node_key, graph_client, node_types, **kwargs)\n";
 
-        for predicate in self.predicates.iter() {
+        for predicate in self.python_predicates() {
             let predicate = generate_set_predicate_from_predicate(predicate);
             viewable = viewable + "        " + &predicate + "\n";
         }
@@ -257,7 +265,7 @@ impl NodeType {
     pub fn generate_viewable_get_methods(&self) -> String {
         let mut get_methods = String::with_capacity(512);
 
-        for predicate in self.predicates.iter() {
+        for predicate in self.python_predicates() {
             tracing::trace!(
                 message="Generating Python Viewable predicate get methods",
                 node_type=?self.type_name,
@@ -357,7 +365,7 @@ impl NodeType {
             + r#"_properties() -> Dict[str, grapl_analyzerlib.node_types.PropType]:"#
             + "\n";
         def = def + r#"    return {"# + "\n";
-        for predicate in self.predicates.iter() {
+        for predicate in self.python_predicates() {
             let predicate_name = format!(r#""{}""#, &predicate.predicate_name);
             let prop_primitive_t = predicate.predicate_type.into_python_prop_primitive();
             def = def + &format!("        {}: {},\n", predicate_name, prop_primitive_t);
@@ -7,6 +7,7 @@ use graphql_parser::schema::Type;
 
 use crate::{
     constants::{
+        FLOAT,
         INT,
         STRING,
         UINT,
@@ -20,6 +21,9 @@ pub enum PredicateType {
     String,
     I64,
     U64,
+    /// Only stored as a mutable property, which analyzers can't query, so
+    /// float predicates are left out of the generated Python
+    F64,
 }
 
 // Python code generation
@@ -29,6 +33,7 @@ impl PredicateType {
             PredicateType::String => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Str, False)",
             PredicateType::I64 => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Int, False)",
             PredicateType::U64 => "grapl_analyzerlib.node_types.PropType(grapl_analyzerlib.node_types.PropPrimitive.Int, False)",
+            PredicateType::F64 => unreachable!("float predicates aren't generated"),
         }.to_string()
     }
 
@@ -37,6 +42,7 @@ impl PredicateType {
             PredicateType::String => "str",
             PredicateType::I64 => "int",
             PredicateType::U64 => "int",
+            PredicateType::F64 => unreachable!("float predicates aren't generated"),
         }
         .to_string()
     }
@@ -46,6 +52,7 @@ impl PredicateType {
             PredicateType::String => "StrOrNot",
             PredicateType::I64 => "IntOrNot",
             PredicateType::U64 => "IntOrNot",
+            PredicateType::F64 => unreachable!("float predicates aren't generated"),
         }
         .to_string()
     }
@@ -62,6 +69,7 @@ impl TryFrom<&Type<'static, String>> for PredicateType {
                     STRING => Ok(PredicateType::String),
                     INT => Ok(PredicateType::I64),
                     UINT => Ok(PredicateType::U64),
+                    FLOAT => Ok(PredicateType::F64),
                     // todo: error
                     unsupported => panic!("Unsupported type: {}", unsupported),
                 }
@@ -0,0 +1,552 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use bytes::Bytes;
+use futures::{
+    pin_mut,
+    StreamExt,
+};
+use kafka::{
+    config::{
+        ConsumerConfig,
+        ProducerConfig,
+    },
+    BytesConsumer,
+    BytesProducer,
+    ConfigurationError,
+    ConsumerError,
+    ProducerError,
+};
+use rust_proto::{
+    graplinc::grapl::pipeline::v1beta1::{
+        Envelope,
+        FailedEnvelope,
+        OpaqueMessage,
+    },
+    SerDe,
+    SerDeError,
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct KafkaReplayConfig {
+    #[clap(flatten)]
+    pub consumer_config: ConsumerConfig,
+
+    #[clap(long, env = "REPLAY_TENANT_ID")]
+    pub tenant_id: Option<uuid::Uuid>,
+
+    #[clap(long, env = "REPLAY_EVENT_SOURCE_ID")]
+    pub event_source_id: Option<uuid::Uuid>,
+
+    #[clap(long, env = "REPLAY_TRACE_ID")]
+    pub trace_id: Option<uuid::Uuid>,
+
+    /// Stop scanning once no message has arrived for this many milliseconds
+    #[clap(long, env = "REPLAY_IDLE_TIMEOUT_MS", default_value = "5000")]
+    pub idle_timeout_ms: u64,
+
+    /// Give up waiting for the first message after this many milliseconds.
+    /// This is longer than the idle timeout since the consumer has to join
+    /// its group and be assigned partitions before anything arrives.
+    #[clap(long, env = "REPLAY_FIRST_MESSAGE_TIMEOUT_MS", default_value = "30000")]
+    pub first_message_timeout_ms: u64,
+
+    /// Republish the matching envelopes to their origin topic, rather than
+    /// only listing them
+    #[clap(long)]
+    pub replay: bool,
+
+    /// Scan a service's retry topic of `Envelope`s, rather than a failed topic
+    /// of `FailedEnvelope`s
+    #[clap(long)]
+    pub retry_topic: bool,
+
+    /// The topic to replay a retry topic's envelopes to. Defaults to the retry
+    /// topic without its "-retry" suffix, i.e. its service's consumer topic.
+    #[clap(long, env = "REPLAY_ORIGIN_TOPIC")]
+    pub origin_topic: Option<String>,
+}
+
+impl KafkaReplayConfig {
+    pub fn scan_timeouts(&self) -> ScanTimeouts {
+        ScanTimeouts {
+            first_message: Duration::from_millis(self.first_message_timeout_ms),
+            idle: Duration::from_millis(self.idle_timeout_ms),
+        }
+    }
+
+    pub fn filter(&self) -> EnvelopeFilter {
+        EnvelopeFilter {
+            tenant_id: self.tenant_id,
+            event_source_id: self.event_source_id,
+            trace_id: self.trace_id,
+        }
+    }
+
+    /// The topic the retry topic's envelopes are replayed to
+    pub fn retry_origin_topic(&self) -> Result<String, KafkaReplayError> {
+        match &self.origin_topic {
+            Some(origin_topic) => Ok(origin_topic.clone()),
+            None => retry_origin_topic(&self.consumer_config.topic)
+                .map(str::to_owned)
+                .ok_or_else(|| {
+                    KafkaReplayError::UnknownOriginTopic(self.consumer_config.topic.clone())
+                }),
+        }
+    }
+}
+
+/// How long a scan waits for messages before deciding it's read the whole
+/// topic
+#[derive(Clone, Copy, Debug)]
+pub struct ScanTimeouts {
+    /// How long to wait for the first message, which includes the time taken
+    /// to join the consumer group and be assigned partitions
+    pub first_message: Duration,
+    /// How long to wait for each message after the first
+    pub idle: Duration,
+}
+
+impl ScanTimeouts {
+    /// The timeout for the next message, given how many have been read
+    pub fn next_message(&self, read: usize) -> Duration {
+        if read == 0 {
+            self.first_message
+        } else {
+            self.idle
+        }
+    }
+}
+
+/// A service's retry topic is named after the topic it consumes, e.g.
+/// "raw-logs-retry" is retried back onto "raw-logs".
+pub fn retry_origin_topic(retry_topic: &str) -> Option<&str> {
+    retry_topic
+        .strip_suffix("-retry")
+        .filter(|origin_topic| !origin_topic.is_empty())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum KafkaReplayError {
+    #[error("kafka configuration error {0}")]
+    Configuration(#[from] ConfigurationError),
+
+    #[error("error consuming failed envelopes {0}")]
+    Consumer(#[from] ConsumerError),
+
+    #[error("error republishing envelope {0}")]
+    Producer(#[from] ProducerError),
+
+    #[error("can't tell which topic {0} retries, set the origin topic explicitly")]
+    UnknownOriginTopic(String),
+}
+
+/// Selects failed or retried envelopes by their metadata. Unset fields match
+/// anything.
+#[derive(Clone, Debug, Default)]
+pub struct EnvelopeFilter {
+    pub tenant_id: Option<uuid::Uuid>,
+    pub event_source_id: Option<uuid::Uuid>,
+    pub trace_id: Option<uuid::Uuid>,
+}
+
+impl EnvelopeFilter {
+    pub fn matches(&self, failed_envelope: &FailedEnvelope) -> bool {
+        self.matches_ids(
+            failed_envelope.tenant_id(),
+            failed_envelope.event_source_id(),
+            failed_envelope.trace_id(),
+        )
+    }
+
+    pub fn matches_retried(&self, retried_envelope: &RetriedEnvelope) -> bool {
+        self.matches_ids(
+            retried_envelope.tenant_id(),
+            retried_envelope.event_source_id(),
+            retried_envelope.trace_id(),
+        )
+    }
+
+    fn matches_ids(
+        &self,
+        tenant_id: uuid::Uuid,
+        event_source_id: uuid::Uuid,
+        trace_id: uuid::Uuid,
+    ) -> bool {
+        self.tenant_id.map_or(true, |id| id == tenant_id)
+            && self
+                .event_source_id
+                .map_or(true, |id| id == event_source_id)
+            && self.trace_id.map_or(true, |id| id == trace_id)
+    }
+}
+
+/// An envelope on a retry topic. Its inner message is never decoded, so that
+/// retry topics of any message type can be scanned, and it's replayed as the
+/// exact bytes it was consumed as.
+#[derive(Clone, Debug)]
+pub struct RetriedEnvelope {
+    envelope: Envelope<OpaqueMessage>,
+    serialized: Bytes,
+}
+
+impl RetriedEnvelope {
+    pub fn decode(serialized: Bytes) -> Result<Self, SerDeError> {
+        Ok(Self {
+            envelope: Envelope::deserialize(serialized.clone())?,
+            serialized,
+        })
+    }
+
+    pub fn tenant_id(&self) -> uuid::Uuid {
+        self.envelope.tenant_id()
+    }
+
+    pub fn trace_id(&self) -> uuid::Uuid {
+        self.envelope.trace_id()
+    }
+
+    pub fn event_source_id(&self) -> uuid::Uuid {
+        self.envelope.event_source_id()
+    }
+
+    pub fn retry_count(&self) -> u32 {
+        self.envelope.retry_count()
+    }
+
+    pub fn last_updated_time(&self) -> SystemTime {
+        self.envelope.last_updated_time()
+    }
+
+    /// The serialized Envelope, as it was consumed from the retry topic
+    pub fn envelope(self) -> Bytes {
+        self.serialized
+    }
+}
+
+/// Lists the envelopes on a failed topic and republishes them to the topics
+/// they originally failed on, or does the same for a retry topic and the topic
+/// its service consumes.
+///
+/// This doesn't use the KafkaTopicScanner, which only scans `Envelope`s
+/// published after it starts and primes itself by publishing to the topic it
+/// scans. The replayer instead reads a failed or retry topic from its earliest
+/// offset and must never publish to it.
+///
+/// The replayer never commits offsets, so each run reads the whole failed
+/// topic from the beginning, and its consumer group is forgotten by the broker
+/// once it's left empty. Runs sharing a consumer group at the same time would
+/// split the topic's partitions between them, so each concurrent run needs a
+/// group name of its own. Since nothing is ever removed from the failed topic,
+/// replaying the same envelopes twice will publish them twice. Likewise, the
+/// envelopes on a retry topic are still republished by its retry service, so
+/// replaying one of them publishes a second copy.
+pub struct KafkaReplayer {
+    consumer: BytesConsumer,
+    producer_config: ProducerConfig,
+    producers: HashMap<String, BytesProducer>,
+}
+
+impl KafkaReplayer {
+    pub fn new(consumer_config: ConsumerConfig) -> Result<Self, KafkaReplayError> {
+        Ok(Self {
+            consumer: BytesConsumer::from_earliest(consumer_config.clone())?,
+            producer_config: consumer_config.into(),
+            producers: HashMap::new(),
+        })
+    }
+
+    /// Consume the failed topic until no message has arrived for the idle
+    /// timeout, or the first message hasn't arrived in time, returning every failed envelope matching the filter.
+    /// Messages which can't be decoded as a FailedEnvelope are skipped.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn scan(
+        &self,
+        filter: &EnvelopeFilter,
+        timeouts: ScanTimeouts,
+    ) -> Result<Vec<FailedEnvelope>, KafkaReplayError> {
+        self.scan_with(
+            timeouts,
+            FailedEnvelope::deserialize,
+            |failed_envelope| filter.matches(failed_envelope),
+            "failed envelope",
+        )
+        .await
+    }
+
+    /// Consume the retry topic until no message has arrived for the idle
+    /// timeout, or the first message hasn't arrived in time, returning every
+    /// envelope matching the filter. Messages which can't be decoded as an
+    /// Envelope are skipped.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn scan_retry_topic(
+        &self,
+        filter: &EnvelopeFilter,
+        timeouts: ScanTimeouts,
+    ) -> Result<Vec<RetriedEnvelope>, KafkaReplayError> {
+        self.scan_with(
+            timeouts,
+            RetriedEnvelope::decode,
+            |retried_envelope| filter.matches_retried(retried_envelope),
+            "envelope",
+        )
+        .await
+    }
+
+    async fn scan_with<E>(
+        &self,
+        timeouts: ScanTimeouts,
+        decode: impl Fn(Bytes) -> Result<E, SerDeError>,
+        matches: impl Fn(&E) -> bool,
+        kind: &str,
+    ) -> Result<Vec<E>, KafkaReplayError> {
+        let stream = self.consumer.stream();
+        pin_mut!(stream);
+
+        let mut read = 0;
+        let mut matching = vec![];
+        while let Ok(Some(result)) =
+            tokio::time::timeout(timeouts.next_message(read), stream.next()).await
+        {
+            read += 1;
+            match decode(result?) {
+                Ok(envelope) => {
+                    if matches(&envelope) {
+                        matching.push(envelope);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        message = "skipping message which can't be decoded",
+                        expected =% kind,
+                        reason =% e,
+                    );
+                }
+            }
+        }
+
+        if read == 0 {
+            tracing::warn!(
+                message = "scan read no messages, the topic may be empty or its partitions were not assigned in time",
+                first_message_timeout_ms = timeouts.first_message.as_millis() as u64,
+            );
+        }
+
+        Ok(matching)
+    }
+
+    /// Republish the original envelope of each failed envelope to its origin
+    /// topic. The envelopes keep their retry counts, so an envelope which fails
+    /// yet again will go straight back to the failed topic rather than being
+    /// retried.
+    #[tracing::instrument(skip(self, failed_envelopes), err)]
+    pub async fn replay(
+        &mut self,
+        failed_envelopes: Vec<FailedEnvelope>,
+    ) -> Result<(), KafkaReplayError> {
+        for failed_envelope in failed_envelopes {
+            let origin_topic = failed_envelope.origin_topic().to_owned();
+            let trace_id = failed_envelope.trace_id();
+            self.producer(&origin_topic)?
+                .send(failed_envelope.envelope())
+                .await?;
+
+            tracing::info!(
+                message = "replayed envelope",
+                origin_topic =% origin_topic,
+                trace_id =% trace_id,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Republish each envelope from the retry topic to `origin_topic`. Like
+    /// the retry service itself, this passes the envelopes along unchanged, so
+    /// they keep their retry counts.
+    #[tracing::instrument(skip(self, retried_envelopes), err)]
+    pub async fn replay_retried(
+        &mut self,
+        retried_envelopes: Vec<RetriedEnvelope>,
+        origin_topic: &str,
+    ) -> Result<(), KafkaReplayError> {
+        for retried_envelope in retried_envelopes {
+            let trace_id = retried_envelope.trace_id();
+            self.producer(origin_topic)?
+                .send(retried_envelope.envelope())
+                .await?;
+
+            tracing::info!(
+                message = "replayed envelope",
+                origin_topic =% origin_topic,
+                trace_id =% trace_id,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn producer(&mut self, topic: &str) -> Result<&BytesProducer, KafkaReplayError> {
+        if !self.producers.contains_key(topic) {
+            let producer = BytesProducer::new(ProducerConfig {
+                topic: topic.to_owned(),
+                ..self.producer_config.clone()
+            })?;
+            self.producers.insert(topic.to_owned(), producer);
+        }
+
+        Ok(&self.producers[topic])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::pipeline::v1beta1::RawLog;
+
+    use super::*;
+
+    fn failed_envelope(
+        tenant_id: uuid::Uuid,
+        trace_id: uuid::Uuid,
+        event_source_id: uuid::Uuid,
+    ) -> FailedEnvelope {
+        FailedEnvelope::new(
+            Envelope::new(
+                tenant_id,
+                trace_id,
+                event_source_id,
+                RawLog::new(Bytes::from("log")),
+            ),
+            "raw-logs".to_owned(),
+            "failed".to_owned(),
+        )
+        .expect("failed envelope")
+    }
+
+    #[test]
+    fn test_first_message_gets_its_own_timeout() {
+        let timeouts = ScanTimeouts {
+            first_message: Duration::from_secs(30),
+            idle: Duration::from_secs(5),
+        };
+        assert_eq!(timeouts.next_message(0), Duration::from_secs(30));
+        assert_eq!(timeouts.next_message(1), Duration::from_secs(5));
+        assert_eq!(timeouts.next_message(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let failed_envelope = failed_envelope(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        assert!(EnvelopeFilter::default().matches(&failed_envelope));
+    }
+
+    #[test]
+    fn test_filter_matches_every_set_field() {
+        let tenant_id = uuid::Uuid::new_v4();
+        let trace_id = uuid::Uuid::new_v4();
+        let event_source_id = uuid::Uuid::new_v4();
+        let failed_envelope = failed_envelope(tenant_id, trace_id, event_source_id);
+
+        let filter = EnvelopeFilter {
+            tenant_id: Some(tenant_id),
+            event_source_id: Some(event_source_id),
+            trace_id: Some(trace_id),
+        };
+        assert!(filter.matches(&failed_envelope));
+
+        let partial_filter = EnvelopeFilter {
+            tenant_id: Some(tenant_id),
+            ..Default::default()
+        };
+        assert!(partial_filter.matches(&failed_envelope));
+    }
+
+    #[test]
+    fn test_filter_rejects_any_mismatched_field() {
+        let tenant_id = uuid::Uuid::new_v4();
+        let trace_id = uuid::Uuid::new_v4();
+        let event_source_id = uuid::Uuid::new_v4();
+        let failed_envelope = failed_envelope(tenant_id, trace_id, event_source_id);
+
+        let filter = EnvelopeFilter {
+            tenant_id: Some(tenant_id),
+            event_source_id: Some(event_source_id),
+            trace_id: Some(trace_id),
+        };
+        let mismatches = [
+            EnvelopeFilter {
+                tenant_id: Some(uuid::Uuid::new_v4()),
+                ..filter
+            },
+            EnvelopeFilter {
+                event_source_id: Some(uuid::Uuid::new_v4()),
+                ..filter
+            },
+            EnvelopeFilter {
+                trace_id: Some(uuid::Uuid::new_v4()),
+                ..filter
+            },
+        ];
+        for mismatch in mismatches {
+            assert!(!mismatch.matches(&failed_envelope), "{mismatch:?}");
+        }
+    }
+
+    #[test]
+    fn test_retried_envelope_decodes_without_its_inner_message_type() {
+        let tenant_id = uuid::Uuid::new_v4();
+        let trace_id = uuid::Uuid::new_v4();
+        let event_source_id = uuid::Uuid::new_v4();
+        let mut envelope = Envelope::new(
+            tenant_id,
+            trace_id,
+            event_source_id,
+            RawLog::new(Bytes::from("log")),
+        );
+        envelope.increment_retry_count();
+        envelope.increment_retry_count();
+        let serialized = envelope.serialize().expect("serialized envelope");
+
+        let retried_envelope =
+            RetriedEnvelope::decode(serialized.clone()).expect("retried envelope");
+        assert_eq!(retried_envelope.tenant_id(), tenant_id);
+        assert_eq!(retried_envelope.trace_id(), trace_id);
+        assert_eq!(retried_envelope.event_source_id(), event_source_id);
+        assert_eq!(retried_envelope.retry_count(), 2);
+
+        let filter = EnvelopeFilter {
+            tenant_id: Some(tenant_id),
+            event_source_id: Some(event_source_id),
+            trace_id: Some(trace_id),
+        };
+        assert!(filter.matches_retried(&retried_envelope));
+        let mismatch = EnvelopeFilter {
+            trace_id: Some(uuid::Uuid::new_v4()),
+            ..filter
+        };
+        assert!(!mismatch.matches_retried(&retried_envelope));
+
+        // It's replayed exactly as it was consumed
+        assert_eq!(retried_envelope.envelope(), serialized);
+    }
+
+    #[test]
+    fn test_retry_origin_topic() {
+        assert_eq!(retry_origin_topic("raw-logs-retry"), Some("raw-logs"));
+        assert_eq!(
+            retry_origin_topic("merged-graphs-retry"),
+            Some("merged-graphs")
+        );
+        assert_eq!(retry_origin_topic("raw-logs-failed"), None);
+        assert_eq!(retry_origin_topic("-retry"), None);
+    }
+}
@@ -0,0 +1,90 @@
+use clap::Parser;
+use grapl_tracing::setup_tracing;
+use kafka::format_iso8601;
+use kafka_replay::{
+    KafkaReplayConfig,
+    KafkaReplayer,
+};
+
+const SERVICE_NAME: &'static str = "kafka-replay";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = KafkaReplayConfig::parse();
+    let filter = config.filter();
+    let timeouts = config.scan_timeouts();
+    if config.retry_topic {
+        // Fail before scanning, rather than after, if there's nowhere to replay to
+        let origin_topic = if config.replay {
+            Some(config.retry_origin_topic()?)
+        } else {
+            None
+        };
+        tracing::info!(
+            message = "scanning retry topic",
+            topic =% config.consumer_config.topic,
+            filter =? filter,
+        );
+
+        let mut replayer = KafkaReplayer::new(config.consumer_config)?;
+        let retried_envelopes = replayer.scan_retry_topic(&filter, timeouts).await?;
+
+        for retried_envelope in retried_envelopes.iter() {
+            println!(
+                "{}\ttenant_id={}\ttrace_id={}\tevent_source_id={}\tretry_count={}",
+                format_iso8601(retried_envelope.last_updated_time()),
+                retried_envelope.tenant_id(),
+                retried_envelope.trace_id(),
+                retried_envelope.event_source_id(),
+                retried_envelope.retry_count(),
+            );
+        }
+        tracing::info!(
+            message = "found retried envelopes",
+            count = retried_envelopes.len(),
+        );
+
+        if let Some(origin_topic) = origin_topic {
+            replayer
+                .replay_retried(retried_envelopes, &origin_topic)
+                .await?;
+            tracing::info!(message = "replayed retried envelopes");
+        }
+
+        return Ok(());
+    }
+
+    tracing::info!(
+        message = "scanning failed topic",
+        topic =% config.consumer_config.topic,
+        filter =? filter,
+    );
+
+    let mut replayer = KafkaReplayer::new(config.consumer_config)?;
+    let failed_envelopes = replayer.scan(&filter, timeouts).await?;
+
+    for failed_envelope in failed_envelopes.iter() {
+        println!(
+            "{}\ttenant_id={}\ttrace_id={}\tevent_source_id={}\tretry_count={}\torigin_topic={}\terror_reason={}",
+            format_iso8601(failed_envelope.failed_time()),
+            failed_envelope.tenant_id(),
+            failed_envelope.trace_id(),
+            failed_envelope.event_source_id(),
+            failed_envelope.retry_count(),
+            failed_envelope.origin_topic(),
+            failed_envelope.error_reason(),
+        );
+    }
+    tracing::info!(
+        message = "found failed envelopes",
+        count = failed_envelopes.len(),
+    );
+
+    if config.replay {
+        replayer.replay(failed_envelopes).await?;
+        tracing::info!(message = "replayed failed envelopes");
+    }
+
+    Ok(())
+}
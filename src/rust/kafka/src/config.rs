@@ -92,4 +92,25 @@ pub struct RetryProducerConfig {
     pub sasl_password: secrecy::SecretString,
     #[clap(long, env = "KAFKA_RETRY_TOPIC")]
     pub topic: String,
+    /// Messages which have already been retried this many times are sent to
+    /// the failed topic instead of the retry topic.
+    #[clap(long, env = "KAFKA_MAX_RETRIES", default_value = "10")]
+    pub max_retries: u32,
+}
+
+#[derive(clap::Parser, Clone, Debug)]
+pub struct DeadLetterProducerConfig {
+    #[clap(long, env = "KAFKA_BOOTSTRAP_SERVERS")]
+    pub bootstrap_servers: String,
+    #[clap(long, env = "KAFKA_SASL_USERNAME")]
+    pub sasl_username: String,
+    #[clap(long, env = "KAFKA_SASL_PASSWORD")]
+    pub sasl_password: secrecy::SecretString,
+    /// Defaults to the consumer topic with a "-failed" suffix.
+    #[clap(long, env = "KAFKA_FAILED_TOPIC")]
+    pub topic: Option<String>,
+    /// The topic failed messages were originally consumed from, recorded
+    /// alongside each failed message so that it may be replayed.
+    #[clap(long, env = "KAFKA_CONSUMER_TOPIC")]
+    pub origin_topic: String,
 }
@@ -21,6 +21,7 @@ use chrono::{
 };
 use config::{
     ConsumerConfig,
+    DeadLetterProducerConfig,
     ProducerConfig,
     RetryConsumerConfig,
     RetryProducerConfig,
@@ -50,7 +51,10 @@ use rdkafka::{
     Message,
 };
 use rust_proto::{
-    graplinc::grapl::pipeline::v1beta1::Envelope,
+    graplinc::grapl::pipeline::v1beta1::{
+        Envelope,
+        FailedEnvelope,
+    },
     SerDe,
     SerDeError,
 };
@@ -228,16 +232,62 @@ impl BytesProducer {
     }
 }
 
+/// A dead letter producer publishes messages which could not be processed to
+/// a service's failed topic, along with the reason they failed and the topic
+/// they were consumed from.
+#[derive(Clone)]
+pub struct DeadLetterProducer<T>
+where
+    T: SerDe,
+{
+    producer: BytesProducer,
+    origin_topic: String,
+    _t: PhantomData<T>,
+}
+
+impl<T: SerDe> DeadLetterProducer<T> {
+    pub fn new(config: DeadLetterProducerConfig) -> Result<Self, ConfigurationError> {
+        let topic = config
+            .topic
+            .unwrap_or_else(|| format!("{}-failed", config.origin_topic));
+        Ok(Self {
+            producer: BytesProducer::new(ProducerConfig {
+                bootstrap_servers: config.bootstrap_servers,
+                sasl_username: config.sasl_username,
+                sasl_password: config.sasl_password,
+                topic,
+            })?,
+            origin_topic: config.origin_topic,
+            _t: PhantomData,
+        })
+    }
+
+    #[tracing::instrument(err, skip(self))]
+    pub async fn send(&self, msg: Envelope<T>, error_reason: String) -> Result<(), ProducerError> {
+        let failed_envelope = FailedEnvelope::new(msg, self.origin_topic.clone(), error_reason)?;
+        self.producer.send(failed_envelope.serialize()?).await
+    }
+}
+
+/// A retry producer publishes messages to a service's retry topic, from which
+/// they'll eventually be sent back to the service's main topic. Once a message
+/// has been retried `max_retries` times it is given up on and sent to the
+/// service's failed topic instead.
 #[derive(Clone)]
 pub struct RetryProducer<T>
 where
     T: SerDe,
 {
     producer: Producer<T>,
+    dead_letter_producer: DeadLetterProducer<T>,
+    max_retries: u32,
 }
 
 impl<T: SerDe> RetryProducer<T> {
-    pub fn new(config: RetryProducerConfig) -> Result<Self, ConfigurationError> {
+    pub fn new(
+        config: RetryProducerConfig,
+        dead_letter_config: DeadLetterProducerConfig,
+    ) -> Result<Self, ConfigurationError> {
         Ok(Self {
             producer: Producer::new(ProducerConfig {
                 bootstrap_servers: config.bootstrap_servers,
@@ -245,13 +295,46 @@ impl<T: SerDe> RetryProducer<T> {
                 sasl_password: config.sasl_password,
                 topic: config.topic,
             })?,
+            dead_letter_producer: DeadLetterProducer::new(dead_letter_config)?,
+            max_retries: config.max_retries,
         })
     }
 
     #[tracing::instrument(err, skip(self))]
-    pub async fn send(&self, mut msg: Envelope<T>) -> Result<(), ProducerError> {
+    pub async fn send(&self, msg: Envelope<T>, error_reason: String) -> Result<(), ProducerError> {
+        match route_retry(msg, self.max_retries) {
+            RetryRoute::Retry(msg) => self.producer.send(msg).await,
+            RetryRoute::Failed(msg) => {
+                tracing::warn!(
+                    message = "message exceeded max retries, sending to failed topic",
+                    retry_count = msg.retry_count(),
+                    max_retries = self.max_retries,
+                    error_reason =% error_reason,
+                );
+                self.dead_letter_producer.send(msg, error_reason).await
+            }
+        }
+    }
+}
+
+/// Where a message which failed to process is sent next.
+#[derive(Debug, PartialEq)]
+enum RetryRoute<T>
+where
+    T: SerDe,
+{
+    /// Back through the retry topic, with its retry count incremented
+    Retry(Envelope<T>),
+    /// To the failed topic, as is
+    Failed(Envelope<T>),
+}
+
+fn route_retry<T: SerDe>(mut msg: Envelope<T>, max_retries: u32) -> RetryRoute<T> {
+    if msg.retry_count() >= max_retries {
+        RetryRoute::Failed(msg)
+    } else {
         msg.increment_retry_count();
-        self.producer.send(msg).await
+        RetryRoute::Retry(msg)
     }
 }
 
@@ -264,12 +347,13 @@ fn consumer(
     sasl_username: String,
     sasl_password: secrecy::SecretString,
     consumer_group_name: String,
+    auto_offset_reset: &str,
 ) -> Result<StreamConsumer, ConfigurationError> {
     configure(bootstrap_servers, sasl_username, sasl_password)
         .set("group.id", consumer_group_name)
         .set("enable.auto.commit", "false")
         .set("enable.auto.offset.store", "true")
-        .set("auto.offset.reset", "latest")
+        .set("auto.offset.reset", auto_offset_reset)
         .set("session.timeout.ms", "45000")
         .create()
         .map_err(|e| ConfigurationError::ConsumerCreateFailed(e))
@@ -313,6 +397,7 @@ impl<T: SerDe> Consumer<T> {
             config.sasl_username,
             config.sasl_password,
             config.consumer_group_name,
+            "latest",
         )?;
 
         // the .subscribe(..) call must be fully-qualified here because the
@@ -377,6 +462,7 @@ impl BytesConsumer {
             config.sasl_username,
             config.sasl_password,
             config.consumer_group_name,
+            "latest",
         )?;
 
         // the .subscribe(..) call must be fully-qualified here because the
@@ -391,6 +477,31 @@ impl BytesConsumer {
         })
     }
 
+    /// Constructs a consumer which reads the topic from its earliest available
+    /// offset and imposes no delay. This is for tools which need to inspect
+    /// every message currently on a topic, e.g. a service's failed topic,
+    /// rather than only the messages published after they start.
+    pub fn from_earliest(config: ConsumerConfig) -> Result<Self, ConfigurationError> {
+        let consumer = consumer(
+            config.bootstrap_servers,
+            config.sasl_username,
+            config.sasl_password,
+            config.consumer_group_name,
+            "earliest",
+        )?;
+
+        // the .subscribe(..) call must be fully-qualified here because the
+        // Consumer name is shadowed in this crate
+        if let Err(e) = rdkafka::consumer::Consumer::subscribe(&consumer, &[&config.topic]) {
+            return Err(ConfigurationError::SubscriptionFailed(e));
+        }
+
+        Ok(Self {
+            consumer,
+            delay_ms: 0,
+        })
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn stream(&self) -> impl Stream<Item = Result<Bytes, ConsumerError>> + '_ {
         self.consumer.stream().then(move |res| async move {
@@ -592,3 +703,65 @@ impl RetryProcessor {
             .then(move |result| async { result.and_then(|_| Ok(self.consumer.commit()?)) })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::pipeline::v1beta1::RawLog;
+
+    use super::*;
+
+    fn envelope() -> Envelope<RawLog> {
+        Envelope::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            RawLog::new(Bytes::from("log")),
+        )
+    }
+
+    #[test]
+    fn test_messages_are_retried_max_retries_times() {
+        let max_retries = 3;
+        let mut msg = envelope();
+        let mut retries = 0;
+        let failed = loop {
+            match route_retry(msg, max_retries) {
+                RetryRoute::Retry(retried) => {
+                    retries += 1;
+                    assert_eq!(retried.retry_count(), retries);
+                    msg = retried;
+                }
+                RetryRoute::Failed(failed) => break failed,
+            }
+        };
+
+        assert_eq!(retries, max_retries);
+        assert_eq!(failed.retry_count(), max_retries);
+    }
+
+    #[test]
+    fn test_zero_max_retries_sends_straight_to_failed() {
+        let msg = envelope();
+        assert_eq!(route_retry(msg.clone(), 0), RetryRoute::Failed(msg));
+    }
+
+    #[test]
+    fn test_failed_envelope_can_be_replayed() -> Result<(), SerDeError> {
+        let msg = envelope();
+        let failed_envelope = FailedEnvelope::new(
+            msg.clone(),
+            "raw-logs".to_owned(),
+            "plugin failed".to_owned(),
+        )?;
+        let failed_envelope = FailedEnvelope::deserialize(failed_envelope.serialize()?)?;
+
+        assert_eq!(failed_envelope.origin_topic(), "raw-logs");
+        assert_eq!(failed_envelope.error_reason(), "plugin failed");
+        assert_eq!(failed_envelope.tenant_id(), msg.tenant_id());
+        assert_eq!(
+            Envelope::<RawLog>::deserialize(failed_envelope.envelope())?,
+            msg
+        );
+        Ok(())
+    }
+}
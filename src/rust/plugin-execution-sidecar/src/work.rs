@@ -4,5 +4,8 @@ pub use plugin_work_processor::{
     Workload,
 };
 
+mod analyzer_work_processor;
+pub use analyzer_work_processor::AnalyzerWorkProcessor;
+
 mod generator_work_processor;
 pub use generator_work_processor::GeneratorWorkProcessor;
@@ -0,0 +1,561 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use clap::Parser;
+use rust_proto::{
+    client_factory::services::GraphSchemaManagerClientConfig,
+    graplinc::grapl::{
+        api::{
+            graph::v1beta1::{
+                MergedGraph,
+                Property,
+            },
+            graph_schema_manager::v1beta1::{
+                client::GraphSchemaManagerClient,
+                messages::GetEdgeSchemaRequest,
+            },
+            plugin_sdk::analyzers::v1beta1::{
+                client::AnalyzerServiceClient,
+                messages::{
+                    EdgeUpdate,
+                    ExecutionResult,
+                    Int64PropertyUpdate,
+                    RunAnalyzerRequest,
+                    StringPropertyUpdate,
+                    UInt64PropertyUpdate,
+                    Update,
+                },
+            },
+            plugin_work_queue::v1beta1::{
+                AcknowledgeAnalyzerRequest,
+                ExecutionJob,
+                GetExecuteAnalyzerRequest,
+                GetExecuteAnalyzerResponse,
+                PluginWorkQueueServiceClient,
+            },
+        },
+        common::v1beta1::types::{
+            EdgeName,
+            NodeType,
+            PropertyName,
+            Uid,
+        },
+    },
+    protocol::{
+        error::GrpcClientError,
+        service_client::ConnectWithConfig,
+        status::Code,
+    },
+    SerDe,
+};
+use uuid::Uuid;
+
+use super::{
+    plugin_work_processor::{
+        PluginWorkProcessorError,
+        RequestId,
+        Workload,
+    },
+    PluginWorkProcessor,
+};
+use crate::{
+    config::PluginExecutorConfig,
+    sidecar_client::analyzer_client::get_analyzer_client,
+};
+
+impl Workload for GetExecuteAnalyzerResponse {
+    fn request_id(&self) -> i64 {
+        self.request_id()
+    }
+
+    fn maybe_job(self) -> Option<ExecutionJob> {
+        self.execution_job()
+    }
+}
+
+/// The reverse of each edge declared in the schema, keyed by the node type
+/// it's declared on and its name.
+type ReverseEdgeNames = HashMap<(NodeType, EdgeName), EdgeName>;
+
+pub struct AnalyzerWorkProcessor {
+    analyzer_service_client: AnalyzerServiceClient,
+    graph_schema_manager_client: GraphSchemaManagerClient,
+}
+
+impl AnalyzerWorkProcessor {
+    pub async fn new(config: &PluginExecutorConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let analyzer_service_client = get_analyzer_client(config.plugin_id).await?;
+        let graph_schema_manager_client =
+            GraphSchemaManagerClient::connect_with_config(GraphSchemaManagerClientConfig::parse())
+                .await?;
+        Ok(AnalyzerWorkProcessor {
+            analyzer_service_client,
+            graph_schema_manager_client,
+        })
+    }
+
+    /// Look up the reverse of every edge in the graph which is declared on
+    /// its source node's type. Edges which aren't are the reverse of one
+    /// which is, and are left out.
+    async fn resolve_reverse_edge_names(
+        &mut self,
+        tenant_id: Uuid,
+        merged_graph: &MergedGraph,
+    ) -> Result<ReverseEdgeNames, PluginWorkProcessorError> {
+        let mut reverse_edge_names = ReverseEdgeNames::new();
+        let mut undeclared = HashSet::new();
+
+        for edge in merged_graph
+            .edges
+            .values()
+            .flat_map(|edge_list| edge_list.edges.iter())
+        {
+            let node_type = match merged_graph.nodes.get(&edge.from_node_key) {
+                Some(node) => NodeType {
+                    value: node.node_type.clone(),
+                },
+                None => continue,
+            };
+            let key = (node_type, parse_edge_name(&edge.edge_name)?);
+            if reverse_edge_names.contains_key(&key) || undeclared.contains(&key) {
+                continue;
+            }
+
+            let (node_type, edge_name) = key.clone();
+            match self
+                .graph_schema_manager_client
+                .get_edge_schema(GetEdgeSchemaRequest {
+                    tenant_id,
+                    node_type,
+                    edge_name,
+                })
+                .await
+            {
+                Ok(response) => {
+                    reverse_edge_names.insert(key, response.reverse_edge_name);
+                }
+                Err(GrpcClientError::ErrorStatus(status)) if status.code() == Code::NotFound => {
+                    undeclared.insert(key);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(reverse_edge_names)
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginWorkProcessor for AnalyzerWorkProcessor {
+    type Work = GetExecuteAnalyzerResponse;
+    type ProducedMessage = Vec<ExecutionResult>;
+
+    async fn get_work(
+        &self,
+        config: &PluginExecutorConfig,
+        pwq_client: &mut PluginWorkQueueServiceClient,
+    ) -> Result<Self::Work, PluginWorkProcessorError> {
+        let plugin_id = config.plugin_id;
+        let response = pwq_client
+            .get_execute_analyzer(GetExecuteAnalyzerRequest { plugin_id })
+            .await?;
+        let request_id = response.request_id();
+
+        let response_retval = response.clone();
+
+        if let Some(execution_job) = response.execution_job() {
+            let tenant_id = execution_job.tenant_id();
+            let trace_id = execution_job.trace_id();
+            let event_source_id = execution_job.event_source_id();
+
+            tracing::debug!(
+                message = "retrieved execution job",
+                tenant_id =% tenant_id,
+                trace_id =% trace_id,
+                event_source_id =% event_source_id,
+                plugin_id =% plugin_id,
+                request_id =? request_id,
+            );
+        } else {
+            tracing::debug!(
+                message = "found no execution jobs",
+                plugin_id =% plugin_id,
+            );
+        }
+
+        Ok(response_retval)
+    }
+
+    async fn ack_work(
+        &self,
+        config: &PluginExecutorConfig,
+        pwq_client: &mut PluginWorkQueueServiceClient,
+        process_result: Result<Self::ProducedMessage, PluginWorkProcessorError>,
+        request_id: RequestId,
+        tenant_id: Uuid,
+        trace_id: Uuid,
+        event_source_id: Uuid,
+    ) -> Result<(), PluginWorkProcessorError> {
+        let plugin_id = config.plugin_id;
+
+        tracing::debug!(
+            message = "acknowledging analyzer work",
+            tenant_id =% tenant_id,
+            trace_id =% trace_id,
+            event_source_id =% event_source_id,
+            plugin_id =% plugin_id,
+            request_id =? request_id,
+        );
+
+        // Execution hits ride along with the ack; plugin-work-queue publishes
+        // them to the analyzer-executions topic.
+        let (success, execution_hits) = match process_result {
+            Ok(execution_results) => (
+                true,
+                execution_results
+                    .into_iter()
+                    .filter_map(|execution_result| match execution_result {
+                        ExecutionResult::ExecutionHit(hit) => Some(hit),
+                        ExecutionResult::ExecutionMiss(_) => None,
+                    })
+                    .collect(),
+            ),
+            Err(_) => (false, vec![]),
+        };
+
+        let ack_request = AcknowledgeAnalyzerRequest::new(
+            request_id,
+            success,
+            execution_hits,
+            plugin_id,
+            tenant_id,
+            trace_id,
+            event_source_id,
+        );
+        pwq_client.acknowledge_analyzer(ack_request).await?;
+        Ok(())
+    }
+
+    async fn process_job(
+        &mut self,
+        _config: &PluginExecutorConfig,
+        job: ExecutionJob,
+    ) -> Result<Self::ProducedMessage, PluginWorkProcessorError> {
+        let tenant_id = job.tenant_id();
+        let merged_graph = MergedGraph::deserialize(job.data()).map_err(|e| {
+            PluginWorkProcessorError::ProcessingJobFailed(format!(
+                "unable to deserialize MergedGraph: {e}"
+            ))
+        })?;
+
+        let reverse_edge_names = self
+            .resolve_reverse_edge_names(tenant_id, &merged_graph)
+            .await?;
+        let updates = updates_from_merged_graph(merged_graph, &reverse_edge_names)?;
+        let mut execution_results = Vec::with_capacity(updates.len());
+        for update in updates {
+            let run_analyzer_response = self
+                .analyzer_service_client
+                .run_analyzer(RunAnalyzerRequest { tenant_id, update })
+                .await?;
+
+            if let ExecutionResult::ExecutionHit(ref hit) = run_analyzer_response.execution_result {
+                tracing::info!(
+                    message = "analyzer execution hit",
+                    tenant_id =% tenant_id,
+                    analyzer_name =% hit.analyzer_name.value,
+                    score =% hit.score,
+                );
+            }
+
+            execution_results.push(run_analyzer_response.execution_result);
+        }
+
+        Ok(execution_results)
+    }
+}
+
+/// Break a MergedGraph down into the individual property and edge updates an
+/// analyzer is executed against. The merged graph carries both directions of
+/// each edge, but only one update is made for the pair.
+///
+/// Float properties are skipped: analyzers can't declare them, since the graph
+/// query service can't filter on them, so there's no update kind for them.
+fn updates_from_merged_graph(
+    merged_graph: MergedGraph,
+    reverse_edge_names: &ReverseEdgeNames,
+) -> Result<Vec<Update>, PluginWorkProcessorError> {
+    let mut updates = vec![];
+
+    for node in merged_graph.nodes.values() {
+        let uid = parse_uid(node.uid)?;
+        for (property_name, node_property) in node.properties.iter() {
+            let property_name = PropertyName::new_unchecked(property_name.clone());
+            let update = match node_property.property {
+                Property::ImmutableStrProp(_) | Property::MutableStrProp(_) => {
+                    Update::StringProperty(StringPropertyUpdate { uid, property_name })
+                }
+                Property::IncrementOnlyUintProp(_)
+                | Property::DecrementOnlyUintProp(_)
+                | Property::ImmutableUintProp(_) => {
+                    Update::Uint64Property(UInt64PropertyUpdate { uid, property_name })
+                }
+                Property::IncrementOnlyIntProp(_)
+                | Property::DecrementOnlyIntProp(_)
+                | Property::ImmutableIntProp(_) => {
+                    Update::Int64Property(Int64PropertyUpdate { uid, property_name })
+                }
+                Property::MutableF64Prop(_) => {
+                    tracing::debug!(
+                        message = "skipping float property update",
+                        uid =% uid.as_u64(),
+                        property_name =% property_name.value,
+                    );
+                    continue;
+                }
+            };
+            updates.push(update);
+        }
+    }
+
+    // Each edge is recorded in both directions, so that its reverse is
+    // skipped whichever of the two comes first
+    let mut seen_edges = HashSet::new();
+    let mut undeclared_edges = vec![];
+    for edge in merged_graph
+        .edges
+        .values()
+        .flat_map(|edge_list| edge_list.edges.iter())
+    {
+        let src_uid = parse_uid_str(&edge.from_uid)?;
+        let dst_uid = parse_uid_str(&edge.to_uid)?;
+        let forward_edge_name = parse_edge_name(&edge.edge_name)?;
+
+        let reverse_edge_name = merged_graph
+            .nodes
+            .get(&edge.from_node_key)
+            .and_then(|node| {
+                reverse_edge_names.get(&(
+                    NodeType {
+                        value: node.node_type.clone(),
+                    },
+                    forward_edge_name.clone(),
+                ))
+            });
+        let reverse_edge_name = match reverse_edge_name {
+            Some(reverse_edge_name) => reverse_edge_name.clone(),
+            None => {
+                undeclared_edges.push((src_uid, dst_uid, forward_edge_name));
+                continue;
+            }
+        };
+
+        if !seen_edges.insert((src_uid, dst_uid, forward_edge_name.clone())) {
+            continue;
+        }
+        seen_edges.insert((dst_uid, src_uid, reverse_edge_name.clone()));
+
+        updates.push(Update::Edge(EdgeUpdate {
+            src_uid,
+            dst_uid,
+            forward_edge_name,
+            reverse_edge_name,
+        }));
+    }
+
+    for edge in undeclared_edges {
+        if !seen_edges.contains(&edge) {
+            let (src_uid, dst_uid, edge_name) = edge;
+            tracing::warn!(
+                message = "edge is not declared in either direction, skipping edge update",
+                src_uid =% src_uid.as_u64(),
+                dst_uid =% dst_uid.as_u64(),
+                edge_name =% edge_name,
+            );
+        }
+    }
+
+    Ok(updates)
+}
+
+fn parse_uid(uid: u64) -> Result<Uid, PluginWorkProcessorError> {
+    Uid::from_u64(uid).ok_or_else(|| {
+        PluginWorkProcessorError::ProcessingJobFailed("MergedGraph contained a zero uid".to_owned())
+    })
+}
+
+fn parse_uid_str(uid: &str) -> Result<Uid, PluginWorkProcessorError> {
+    let uid = uid.parse::<u64>().map_err(|e| {
+        PluginWorkProcessorError::ProcessingJobFailed(format!("invalid uid {uid}: {e}"))
+    })?;
+    parse_uid(uid)
+}
+
+fn parse_edge_name(edge_name: &str) -> Result<EdgeName, PluginWorkProcessorError> {
+    EdgeName::try_from(edge_name).map_err(|e| {
+        PluginWorkProcessorError::ProcessingJobFailed(format!("invalid edge name {edge_name}: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_proto::graplinc::grapl::api::graph::v1beta1::{
+        ImmutableStrProp,
+        MergedEdge,
+        MergedEdgeList,
+        MergedNode,
+        MutableF64Prop,
+        NodeProperty,
+    };
+
+    use super::*;
+
+    fn node(uid: u64, node_type: &str) -> MergedNode {
+        MergedNode {
+            properties: HashMap::new(),
+            uid,
+            node_key: format!("node-{uid}"),
+            node_type: node_type.to_owned(),
+        }
+    }
+
+    fn add_edge(merged_graph: &mut MergedGraph, from: &MergedNode, to: &MergedNode, name: &str) {
+        merged_graph
+            .edges
+            .entry(from.node_key.clone())
+            .or_insert_with(|| MergedEdgeList { edges: vec![] })
+            .edges
+            .push(MergedEdge {
+                from_uid: from.uid.to_string(),
+                from_node_key: from.node_key.clone(),
+                to_uid: to.uid.to_string(),
+                to_node_key: to.node_key.clone(),
+                edge_name: name.to_owned(),
+            });
+    }
+
+    fn reverse_edge_names(edges: &[(&str, &str, &str)]) -> ReverseEdgeNames {
+        edges
+            .iter()
+            .map(|(node_type, edge_name, reverse_edge_name)| {
+                (
+                    (
+                        NodeType {
+                            value: (*node_type).to_owned(),
+                        },
+                        EdgeName::try_from(*edge_name).unwrap(),
+                    ),
+                    EdgeName::try_from(*reverse_edge_name).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn edge_update(src_uid: u64, dst_uid: u64, forward: &str, reverse: &str) -> Update {
+        Update::Edge(EdgeUpdate {
+            src_uid: Uid::from_u64(src_uid).unwrap(),
+            dst_uid: Uid::from_u64(dst_uid).unwrap(),
+            forward_edge_name: EdgeName::try_from(forward).unwrap(),
+            reverse_edge_name: EdgeName::try_from(reverse).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_one_update_per_edge() {
+        let parent = node(1, "Process");
+        let child = node(2, "Process");
+        let mut merged_graph = MergedGraph::new();
+        add_edge(&mut merged_graph, &parent, &child, "children");
+        add_edge(&mut merged_graph, &child, &parent, "parent");
+        merged_graph.nodes.insert(parent.node_key.clone(), parent);
+        merged_graph.nodes.insert(child.node_key.clone(), child);
+
+        let updates = updates_from_merged_graph(
+            merged_graph,
+            &reverse_edge_names(&[("Process", "children", "parent")]),
+        )
+        .unwrap();
+        assert_eq!(updates, vec![edge_update(1, 2, "children", "parent")]);
+    }
+
+    #[test]
+    fn test_reverse_names_come_from_the_schema() {
+        let process = node(1, "Process");
+        let file = node(2, "File");
+        let mut merged_graph = MergedGraph::new();
+        add_edge(&mut merged_graph, &process, &file, "binary_file");
+        add_edge(&mut merged_graph, &process, &file, "created_file");
+        add_edge(&mut merged_graph, &file, &process, "created_by_process");
+        add_edge(&mut merged_graph, &file, &process, "executed_as_processes");
+        merged_graph.nodes.insert(process.node_key.clone(), process);
+        merged_graph.nodes.insert(file.node_key.clone(), file);
+
+        let updates: HashSet<_> = updates_from_merged_graph(
+            merged_graph,
+            &reverse_edge_names(&[
+                ("Process", "binary_file", "executed_as_processes"),
+                ("Process", "created_file", "created_by_process"),
+            ]),
+        )
+        .unwrap()
+        .into_iter()
+        .collect();
+        assert_eq!(
+            updates,
+            HashSet::from([
+                edge_update(1, 2, "binary_file", "executed_as_processes"),
+                edge_update(1, 2, "created_file", "created_by_process"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_edges_declared_in_both_directions() {
+        let first = node(1, "Process");
+        let second = node(2, "Process");
+        let mut merged_graph = MergedGraph::new();
+        add_edge(&mut merged_graph, &first, &second, "peer");
+        add_edge(&mut merged_graph, &second, &first, "peer");
+        merged_graph.nodes.insert(first.node_key.clone(), first);
+        merged_graph.nodes.insert(second.node_key.clone(), second);
+
+        let updates = updates_from_merged_graph(
+            merged_graph,
+            &reverse_edge_names(&[("Process", "peer", "peer")]),
+        )
+        .unwrap();
+        assert_eq!(updates.len(), 1);
+    }
+
+    #[test]
+    fn test_property_updates() {
+        let mut process = node(1, "Process");
+        process.properties.insert(
+            "process_name".to_owned(),
+            NodeProperty {
+                property: Property::ImmutableStrProp(ImmutableStrProp {
+                    prop: "chrome.exe".to_owned(),
+                }),
+            },
+        );
+        process.properties.insert(
+            "entropy".to_owned(),
+            NodeProperty {
+                property: Property::MutableF64Prop(MutableF64Prop { prop: 0.5 }),
+            },
+        );
+        let mut merged_graph = MergedGraph::new();
+        merged_graph.nodes.insert(process.node_key.clone(), process);
+
+        let updates = updates_from_merged_graph(merged_graph, &ReverseEdgeNames::new()).unwrap();
+        assert_eq!(
+            updates,
+            vec![Update::StringProperty(StringPropertyUpdate {
+                uid: Uid::from_u64(1).unwrap(),
+                property_name: PropertyName::new_unchecked("process_name".to_owned()),
+            })]
+        );
+    }
+}
@@ -4,7 +4,6 @@ use rust_proto::{
         PluginWorkQueueServiceClient,
     },
     protocol::error::GrpcClientError,
-    SerDe,
 };
 use uuid::Uuid;
 
@@ -41,7 +40,7 @@ pub trait Workload {
 #[async_trait::async_trait]
 pub trait PluginWorkProcessor {
     type Work: Workload;
-    type ProducedMessage: SerDe;
+    type ProducedMessage: Send;
 
     async fn get_work(
         &self,
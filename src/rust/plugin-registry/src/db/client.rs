@@ -126,6 +126,30 @@ impl PluginRegistryDbClient {
         .await
     }
 
+    #[tracing::instrument(skip(self), err)]
+    pub async fn list_plugins_for_tenant(
+        &self,
+        tenant_id: &uuid::Uuid,
+    ) -> Result<Vec<PluginRow>, sqlx::Error> {
+        sqlx::query_as!(
+            PluginRow,
+            r"
+            SELECT
+                plugin_id,
+                tenant_id,
+                display_name,
+                plugin_type,
+                artifact_s3_key,
+                event_source_id
+            FROM plugins
+            WHERE tenant_id = $1;
+            ",
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
     #[tracing::instrument(skip(self), err)]
     pub async fn get_plugin_deployment(
         &self,
@@ -248,4 +272,38 @@ impl PluginRegistryDbClient {
         .await
         .map(|_| ())
     }
+
+    /// Deletes every plugin belonging to the tenant, along with the
+    /// deployment history of those plugins.
+    #[tracing::instrument(skip(self), err)]
+    pub async fn delete_plugins_for_tenant(
+        &self,
+        tenant_id: &uuid::Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query!(
+            r"
+            DELETE FROM plugin_deployment
+            WHERE plugin_id IN (
+                SELECT plugin_id FROM plugins WHERE tenant_id = $1
+            );
+            ",
+            tenant_id,
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r"
+            DELETE FROM plugins
+            WHERE tenant_id = $1;
+            ",
+            tenant_id,
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
 }
@@ -2,6 +2,7 @@ use rusoto_s3::{
     AbortMultipartUploadError,
     CompleteMultipartUploadError,
     CreateMultipartUploadError,
+    DeleteObjectError,
     GetObjectError,
     UploadPartError,
 };
@@ -35,6 +36,8 @@ pub enum PluginRegistryServiceError {
     S3PutObjectError(#[from] S3PutError),
     #[error(transparent)]
     S3GetObjectError(#[from] rusoto_core::RusotoError<GetObjectError>),
+    #[error(transparent)]
+    S3DeleteObjectError(#[from] rusoto_core::RusotoError<DeleteObjectError>),
     #[error("EmptyObject")]
     EmptyObject,
     #[error(transparent)]
@@ -76,6 +79,7 @@ impl From<PluginRegistryServiceError> for Status {
             Error::SqlxError(_) => Status::unknown("Failed to operate on postgres"),
             Error::S3PutObjectError(_) => Status::unknown("Failed to put s3 object"),
             Error::S3GetObjectError(_) => Status::unknown("Failed to get s3 object"),
+            Error::S3DeleteObjectError(_) => Status::unknown("Failed to delete s3 object"),
             Error::EmptyObject => Status::unknown("S3 Object was unexpectedly empty"),
             Error::IoError(_) => Status::unknown("IoError"),
             Error::SerDeError(_) => Status::invalid_argument("Unable to deserialize message"),
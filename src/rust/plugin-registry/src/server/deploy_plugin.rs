@@ -191,6 +191,31 @@ pub async fn teardown_plugin(
     Ok(())
 }
 
+/// Deletes the plugin's Nomad job if its latest deployment is still active.
+/// Plugins that were never deployed, or have already been torn down, are left
+/// alone.
+pub async fn remove_plugin_job(
+    client: &NomadClient,
+    db_client: &PluginRegistryDbClient,
+    plugin: &PluginRow,
+) -> Result<(), PluginRegistryServiceError> {
+    let deployed = match db_client.get_plugin_deployment(&plugin.plugin_id).await {
+        Ok(plugin_deployment_row) => plugin_deployment_row.deployed,
+        Err(sqlx::Error::RowNotFound) => false,
+        Err(e) => return Err(e.into()),
+    };
+
+    if deployed {
+        let job_name = plugin_nomad_job::job_name();
+        let namespace_name = plugin_nomad_job::namespace_name(&plugin.plugin_id);
+        client
+            .delete_job(job_name.to_owned(), Some(namespace_name))
+            .await?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,11 +9,17 @@ use grapl_config::{
     env_helpers::FromEnv,
     PostgresClient,
 };
-use rusoto_s3::S3Client;
+use rusoto_s3::{
+    DeleteObjectRequest,
+    S3Client,
+    S3,
+};
 use rust_proto::{
     graplinc::grapl::api::plugin_registry::v1beta1::{
         CreatePluginRequest,
         CreatePluginResponse,
+        DeletePluginsForTenantRequest,
+        DeletePluginsForTenantResponse,
         DeployPluginRequest,
         DeployPluginResponse,
         GetAnalyzersForTenantRequest,
@@ -323,6 +329,41 @@ impl PluginRegistryApi for PluginRegistry {
         Ok(TearDownPluginResponse {})
     }
 
+    #[tracing::instrument(skip(self, request), err)]
+    async fn delete_plugins_for_tenant(
+        &self,
+        request: DeletePluginsForTenantRequest,
+    ) -> Result<DeletePluginsForTenantResponse, Self::Error> {
+        let tenant_id = request.tenant_id();
+        let plugin_rows = self.db_client.list_plugins_for_tenant(&tenant_id).await?;
+
+        // The rows are deleted last, so that a partially-completed purge can
+        // simply be retried.
+        for plugin_row in plugin_rows.iter() {
+            deploy_plugin::remove_plugin_job(&self.nomad_client, &self.db_client, plugin_row)
+                .await?;
+
+            self.s3
+                .delete_object(DeleteObjectRequest {
+                    bucket: self.config.bucket_name.clone(),
+                    key: plugin_row.artifact_s3_key.clone(),
+                    expected_bucket_owner: Some(self.config.bucket_aws_account_id.clone()),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        self.db_client.delete_plugins_for_tenant(&tenant_id).await?;
+
+        tracing::info!(
+            message = "deleted plugins for tenant",
+            tenant_id =% tenant_id,
+            plugin_count = plugin_rows.len(),
+        );
+
+        Ok(DeletePluginsForTenantResponse {})
+    }
+
     #[tracing::instrument(skip(self, request), err)]
     async fn get_generators_for_event_source(
         &self,
@@ -0,0 +1,96 @@
+#![cfg(feature = "integration_tests")]
+
+use bytes::Bytes;
+use clap::Parser;
+use rust_proto::{
+    client_factory::services::PluginRegistryClientConfig,
+    graplinc::grapl::api::plugin_registry::v1beta1::{
+        DeletePluginsForTenantRequest,
+        GetPluginRequest,
+        ListPluginsRequest,
+        PluginMetadata,
+        PluginRegistryServiceClient,
+        PluginType,
+    },
+    protocol::{
+        error::GrpcClientError,
+        service_client::ConnectWithConfig,
+        status::Code,
+    },
+};
+
+async fn create_plugin(
+    client: &mut PluginRegistryServiceClient,
+    tenant_id: uuid::Uuid,
+    plugin_type: PluginType,
+) -> eyre::Result<uuid::Uuid> {
+    let artifact = Bytes::from("fake");
+    let event_source_id = match plugin_type {
+        PluginType::Generator => Some(uuid::Uuid::new_v4()),
+        PluginType::Analyzer => None,
+    };
+    let metadata = PluginMetadata::new(
+        tenant_id,
+        "plugin".to_string(),
+        plugin_type,
+        event_source_id,
+    );
+
+    let response = client
+        .create_plugin(
+            metadata,
+            futures::stream::once(async move { artifact.clone() }),
+        )
+        .await?;
+    Ok(response.plugin_id())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_delete_plugins_for_tenant() -> eyre::Result<()> {
+    let client_config = PluginRegistryClientConfig::parse();
+    let mut client = PluginRegistryServiceClient::connect_with_config(client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let other_tenant_id = uuid::Uuid::new_v4();
+
+    let generator_id = create_plugin(&mut client, tenant_id, PluginType::Generator).await?;
+    let analyzer_id = create_plugin(&mut client, tenant_id, PluginType::Analyzer).await?;
+    let other_generator_id =
+        create_plugin(&mut client, other_tenant_id, PluginType::Generator).await?;
+
+    client
+        .delete_plugins_for_tenant(DeletePluginsForTenantRequest::new(tenant_id))
+        .await?;
+
+    for plugin_id in [generator_id, analyzer_id] {
+        match client
+            .get_plugin(GetPluginRequest::new(plugin_id, tenant_id))
+            .await
+        {
+            Err(GrpcClientError::ErrorStatus(status)) => {
+                assert_eq!(status.code(), Code::NotFound, "{status}")
+            }
+            other => panic!("expected NotFound, got {other:?}"),
+        }
+    }
+
+    for plugin_type in [PluginType::Generator, PluginType::Analyzer] {
+        let plugins = client
+            .list_plugins(ListPluginsRequest::new(tenant_id, plugin_type))
+            .await?
+            .plugins();
+        assert!(plugins.is_empty());
+    }
+
+    // Another tenant's plugins are left alone
+    client
+        .get_plugin(GetPluginRequest::new(other_generator_id, other_tenant_id))
+        .await?;
+
+    // Deleting them again is a no-op
+    client
+        .delete_plugins_for_tenant(DeletePluginsForTenantRequest::new(tenant_id))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use rust_proto::{
+    graplinc::grapl::api::{
+        graph_query_service::v1beta1::client::GraphQueryClient,
+        plugin_sdk::analyzers::v1beta1::{
+            messages::{
+                ExecutionResult,
+                RunAnalyzerRequest,
+                RunAnalyzerResponse,
+                Update,
+            },
+            server::AnalyzerApi,
+        },
+    },
+    protocol::status::Status,
+};
+
+/// Implement this trait to define an Analyzer's detection logic. The SDK takes
+/// care of serving it over gRPC and of handing it a client it can use to query
+/// the tenant's graph.
+#[async_trait::async_trait]
+pub trait Analyzer {
+    type Error: Into<Status>;
+
+    /// Examine a single update to the tenant's graph, querying the graph for
+    /// any further context, and report whether it matched.
+    async fn analyze(
+        &self,
+        tenant_id: uuid::Uuid,
+        update: Update,
+        graph_query_client: GraphQueryClient,
+    ) -> Result<ExecutionResult, Self::Error>;
+}
+
+/// Adapts an Analyzer to the AnalyzerApi expected by the AnalyzerServer.
+pub(crate) struct AnalyzerApiImpl<A>
+where
+    A: Analyzer + Send + Sync + 'static,
+{
+    analyzer: A,
+    graph_query_client: GraphQueryClient,
+}
+
+impl<A> AnalyzerApiImpl<A>
+where
+    A: Analyzer + Send + Sync + 'static,
+{
+    pub(crate) fn new(analyzer: A, graph_query_client: GraphQueryClient) -> Self {
+        Self {
+            analyzer,
+            graph_query_client,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> AnalyzerApi for AnalyzerApiImpl<A>
+where
+    A: Analyzer + Send + Sync + 'static,
+{
+    type Error = A::Error;
+
+    #[tracing::instrument(skip(self, request))]
+    async fn run_analyzer(
+        &self,
+        request: RunAnalyzerRequest,
+    ) -> Result<RunAnalyzerResponse, Self::Error> {
+        let execution_result = self
+            .analyzer
+            .analyze(
+                request.tenant_id,
+                request.update,
+                self.graph_query_client.clone(),
+            )
+            .await?;
+
+        Ok(RunAnalyzerResponse { execution_result })
+    }
+}
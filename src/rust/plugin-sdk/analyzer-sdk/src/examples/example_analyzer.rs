@@ -0,0 +1,125 @@
+/// This Analyzer shows a very basic way to build an Analyzer, and will also
+/// help inform Grapl Engineers of ways to simplify this API before we ship to
+/// customers.
+use std::time::SystemTime;
+
+use analyzer_sdk::{
+    analyzer::Analyzer,
+    server::{
+        self,
+        AnalyzerServiceConfig,
+    },
+};
+use grapl_tracing::setup_tracing;
+use rust_proto::{
+    graplinc::grapl::{
+        api::{
+            graph_query_service::v1beta1::{
+                client::{
+                    GraphQueryClient,
+                    GraphQueryClientError,
+                },
+                messages::{
+                    GraphQuery,
+                    MaybeMatchWithUid,
+                    NodePropertyQuery,
+                    QueryGraphWithUidRequest,
+                    StringCmp,
+                },
+            },
+            plugin_sdk::analyzers::v1beta1::messages::{
+                AnalyzerName,
+                ExecutionHit,
+                ExecutionMiss,
+                ExecutionResult,
+                Update,
+            },
+        },
+        common::v1beta1::types::{
+            NodeType,
+            PropertyName,
+        },
+    },
+    protocol::status::Status,
+};
+const SERVICE_NAME: &'static str = "example-analyzer";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = AnalyzerServiceConfig::from_env_vars();
+    let analyzer = ExampleAnalyzer {};
+    server::exec_service(analyzer, config).await
+}
+
+/// An example, silly error class
+#[derive(thiserror::Error, Debug)]
+pub enum ExampleAnalyzerError {
+    #[error("GraphQueryClientError {0}")]
+    GraphQueryClientError(#[from] GraphQueryClientError),
+}
+
+impl From<ExampleAnalyzerError> for Status {
+    fn from(e: ExampleAnalyzerError) -> Self {
+        Status::unknown(e.to_string())
+    }
+}
+
+/// Matches any Process named "svchost.exe"
+pub struct ExampleAnalyzer {}
+
+#[async_trait::async_trait]
+impl Analyzer for ExampleAnalyzer {
+    type Error = ExampleAnalyzerError;
+
+    #[tracing::instrument(skip(self, update, graph_query_client), err)]
+    async fn analyze(
+        &self,
+        tenant_id: uuid::Uuid,
+        update: Update,
+        mut graph_query_client: GraphQueryClient,
+    ) -> Result<ExecutionResult, Self::Error> {
+        let process_name = PropertyName::new_unchecked("process_name".to_owned());
+        let node_uid = match update {
+            Update::StringProperty(update) if update.property_name == process_name => update.uid,
+            _ => return Ok(ExecutionResult::ExecutionMiss(ExecutionMiss {})),
+        };
+
+        let mut process_query =
+            NodePropertyQuery::new(NodeType::try_from("Process").expect("invalid node type"));
+        process_query.with_string_filters(process_name, vec![StringCmp::eq("svchost.exe", false)]);
+        let mut graph_query = GraphQuery {
+            root_query_id: process_query.query_id,
+            node_property_queries: Default::default(),
+            edge_filters: Default::default(),
+            edge_map: Default::default(),
+            or_edge_filters: Default::default(),
+            negated_edge_filters: Default::default(),
+        };
+        graph_query.merge_node(process_query);
+
+        let response = graph_query_client
+            .query_graph_with_uid(QueryGraphWithUidRequest {
+                tenant_id,
+                node_uid,
+                graph_query,
+            })
+            .await?;
+
+        match response.maybe_match {
+            MaybeMatchWithUid::Matched(matched) => {
+                Ok(ExecutionResult::ExecutionHit(ExecutionHit {
+                    graph_view: matched.matched_graph,
+                    lens_refs: vec![],
+                    analyzer_name: AnalyzerName {
+                        value: SERVICE_NAME.to_owned(),
+                    },
+                    time_of_match: SystemTime::now(),
+                    idempotency_key: matched.root_uid.as_u64(),
+                    score: 1,
+                }))
+            }
+            MaybeMatchWithUid::Missed(_) => Ok(ExecutionResult::ExecutionMiss(ExecutionMiss {})),
+        }
+    }
+}
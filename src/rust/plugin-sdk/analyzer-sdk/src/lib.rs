@@ -0,0 +1,7 @@
+pub mod analyzer;
+pub mod server;
+
+#[cfg(feature = "test_utils")]
+pub mod test_utils {
+    pub mod test_ctx;
+}
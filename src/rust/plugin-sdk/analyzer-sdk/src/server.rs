@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use clap::Parser;
+use rust_proto::{
+    client_factory::services::GraphQueryClientConfig,
+    graplinc::grapl::api::{
+        graph_query_service::v1beta1::client::GraphQueryClient,
+        plugin_sdk::analyzers::v1beta1::server::AnalyzerServer,
+    },
+    protocol::{
+        healthcheck::HealthcheckStatus,
+        service_client::ConnectWithConfig,
+    },
+};
+use tokio::net::TcpListener;
+
+use crate::analyzer::{
+    Analyzer,
+    AnalyzerApiImpl,
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct AnalyzerServiceConfig {
+    #[clap(long, env = "PLUGIN_BIND_ADDRESS")]
+    pub bind_address: std::net::SocketAddr,
+
+    #[clap(flatten)]
+    pub graph_query_client_config: GraphQueryClientConfig,
+}
+impl AnalyzerServiceConfig {
+    /// An alias for clap::parse, so that consumers don't need to
+    /// declare a dependency on clap
+    pub fn from_env_vars() -> Self {
+        Self::parse()
+    }
+}
+
+pub async fn exec_service(
+    analyzer: impl Analyzer + Send + Sync + 'static,
+    config: AnalyzerServiceConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let graph_query_client =
+        GraphQueryClient::connect_with_config(config.graph_query_client_config).await?;
+
+    let healthcheck_polling_interval_ms = 5000; // TODO: un-hardcode
+    let (server, _shutdown_tx) = AnalyzerServer::new(
+        AnalyzerApiImpl::new(analyzer, graph_query_client),
+        TcpListener::bind(config.bind_address.clone()).await?,
+        || async { Ok(HealthcheckStatus::Serving) }, // FIXME: this is garbage
+        Duration::from_millis(healthcheck_polling_interval_ms),
+    );
+    tracing::info!(
+        message = "starting gRPC server",
+        socket_address = %config.bind_address,
+    );
+
+    Ok(server.serve().await?)
+}
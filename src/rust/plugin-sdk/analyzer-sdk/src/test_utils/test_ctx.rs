@@ -0,0 +1,140 @@
+use rust_proto::{
+    client_factory::services::AnalyzerClientConfig,
+    graplinc::{
+        common::v1beta1::Duration,
+        grapl::api::{
+            graph_query_service::v1beta1::client::GraphQueryClient,
+            plugin_sdk::analyzers::v1beta1::{
+                client::AnalyzerServiceClient,
+                server::AnalyzerServer,
+            },
+        },
+    },
+    protocol::{
+        error::ServeError,
+        healthcheck::{
+            client::HealthcheckClient,
+            HealthcheckStatus,
+        },
+        service_client::ConnectWithConfig,
+    },
+};
+use test_context::{
+    futures::channel::oneshot::Sender,
+    AsyncTestContext,
+};
+use tokio::{
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+use crate::analyzer::{
+    Analyzer,
+    AnalyzerApiImpl,
+};
+
+/*
+This mirrors generator-sdk's GeneratorTestContext; see there for why the
+Internals indirection exists. The only difference is that an Analyzer also
+needs a GraphQueryClient, which the test supplies alongside the Analyzer.
+*/
+
+struct AnalyzerTestContextInternals {
+    client: AnalyzerServiceClient,
+    server_handle: JoinHandle<Result<(), ServeError>>,
+    shutdown_tx: Sender<()>,
+}
+impl AnalyzerTestContextInternals {
+    async fn new(
+        analyzer: impl Analyzer + Send + Sync + 'static,
+        graph_query_client: GraphQueryClient,
+    ) -> Self {
+        // binding the tcp listener on port 0 tells the operating system to
+        // reserve an unused, ephemeral port
+        let tcp_listener = TcpListener::bind("0.0.0.0:0")
+            .await
+            .expect("failed to bind tcp listener");
+
+        // determine the actual port which was bound
+        let socket_address = tcp_listener
+            .local_addr()
+            .expect("failed to obtain socket address");
+
+        // construct an http URI clients can use to connect to server bound to
+        // the port.
+        let endpoint = format!("http://{}:{}", socket_address.ip(), socket_address.port());
+
+        let (server, shutdown_tx) = AnalyzerServer::new(
+            AnalyzerApiImpl::new(analyzer, graph_query_client),
+            tcp_listener,
+            || async { Ok(HealthcheckStatus::Serving) },
+            Duration::from_millis(50),
+        );
+
+        let service_name = server.service_name();
+
+        let server_handle = tokio::task::spawn(server.serve());
+
+        HealthcheckClient::wait_until_healthy(
+            endpoint.clone(),
+            service_name,
+            Duration::from_millis(250),
+            Duration::from_millis(10),
+        )
+        .await
+        .expect("Analyzer never reported healthy");
+
+        let client_config = AnalyzerClientConfig {
+            analyzer_client_address: endpoint,
+        };
+        let client = AnalyzerServiceClient::connect_with_config(client_config)
+            .await
+            .unwrap();
+
+        AnalyzerTestContextInternals {
+            client,
+            server_handle,
+            shutdown_tx,
+        }
+    }
+
+    async fn teardown(self) {
+        self.shutdown_tx
+            .send(())
+            .expect("failed to shutdown server");
+        self.server_handle
+            .await
+            .expect("failed to join server task")
+            .expect("server configuration failed");
+    }
+}
+
+pub struct AnalyzerTestContext {
+    internals: Option<AnalyzerTestContextInternals>,
+}
+
+#[async_trait::async_trait]
+impl AsyncTestContext for AnalyzerTestContext {
+    async fn setup() -> Self {
+        Self { internals: None }
+    }
+    async fn teardown(self) {
+        if let Some(i) = self.internals {
+            i.teardown().await;
+        }
+    }
+}
+impl AnalyzerTestContext {
+    pub async fn get_client(
+        &mut self,
+        analyzer: impl Analyzer + Send + Sync + 'static,
+        graph_query_client: GraphQueryClient,
+    ) -> AnalyzerServiceClient {
+        if let None = self.internals {
+            self.internals =
+                Some(AnalyzerTestContextInternals::new(analyzer, graph_query_client).await)
+        }
+
+        self.internals.as_ref().expect("internals").client.clone()
+    }
+}
@@ -330,4 +330,37 @@ impl PsqlQueue {
         .await?;
         Ok(())
     }
+
+    /// Deletes every generator and analyzer execution belonging to the
+    /// tenant, whatever its current status.
+    #[instrument(skip(self), err)]
+    pub async fn delete_executions_for_tenant(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<(), PsqlQueueError> {
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM plugin_work_queue.generator_plugin_executions
+                WHERE tenant_id = $1
+            "#,
+            tenant_id,
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM plugin_work_queue.analyzer_plugin_executions
+                WHERE tenant_id = $1
+            "#,
+            tenant_id,
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
 }
@@ -9,6 +9,7 @@ use rust_proto::{
     graplinc::grapl::{
         api::{
             graph::v1beta1::GraphDescription,
+            plugin_sdk::analyzers::v1beta1::messages::ExecutionHit,
             plugin_work_queue::{
                 v1beta1,
                 v1beta1::{
@@ -70,15 +71,18 @@ impl From<PluginWorkQueueError> for Status {
 pub struct PluginWorkQueue {
     queue: PsqlQueue,
     generator_producer: Producer<GraphDescription>,
+    analyzer_producer: Producer<ExecutionHit>,
 }
 
 impl PluginWorkQueue {
     pub async fn try_from(configs: &ConfigUnion) -> Result<Self, PluginWorkQueueInitError> {
         let psql_queue = PsqlQueue::init_with_config(configs.db_config.clone()).await?;
         let generator_producer = Producer::new(configs.generator_producer_config.clone())?;
+        let analyzer_producer = Producer::new(configs.analyzer_producer_config.clone())?;
         Ok(Self {
             queue: psql_queue,
             generator_producer,
+            analyzer_producer,
         })
     }
 }
@@ -280,6 +284,7 @@ impl PluginWorkQueueApi for PluginWorkQueue {
         let tenant_id = request.tenant_id();
         let trace_id = request.trace_id();
         let event_source_id = request.event_source_id();
+        let request_id = request.request_id();
         let plugin_id = request.plugin_id();
 
         let status = match request.success() {
@@ -287,6 +292,26 @@ impl PluginWorkQueueApi for PluginWorkQueue {
             false => psql_queue::Status::Failed,
         };
 
+        for execution_hit in request.execution_hits() {
+            tracing::debug!(
+                message = "publishing analyzer execution hit",
+                tenant_id =% tenant_id,
+                trace_id =% trace_id,
+                event_source_id =% event_source_id,
+                plugin_id =% plugin_id,
+                analyzer_name =% execution_hit.analyzer_name.value,
+            );
+
+            self.analyzer_producer
+                .send(Envelope::new(
+                    tenant_id,
+                    trace_id,
+                    event_source_id,
+                    execution_hit,
+                ))
+                .await?;
+        }
+
         tracing::debug!(
             message = "acknowledging analyzer execution",
             tenant_id =% tenant_id,
@@ -296,11 +321,25 @@ impl PluginWorkQueueApi for PluginWorkQueue {
             status =? status,
         );
 
-        self.queue
-            .ack_analyzer(request.request_id().into(), status)
-            .await?;
+        self.queue.ack_analyzer(request_id.into(), status).await?;
         Ok(v1beta1::AcknowledgeAnalyzerResponse {})
     }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn delete_executions_for_tenant(
+        &self,
+        request: v1beta1::DeleteExecutionsForTenantRequest,
+    ) -> Result<v1beta1::DeleteExecutionsForTenantResponse, PluginWorkQueueError> {
+        let tenant_id = request.tenant_id;
+
+        tracing::info!(
+            message = "deleting executions for tenant",
+            tenant_id =% tenant_id,
+        );
+
+        self.queue.delete_executions_for_tenant(tenant_id).await?;
+        Ok(v1beta1::DeleteExecutionsForTenantResponse {})
+    }
 }
 
 pub async fn exec_service(configs: ConfigUnion) -> Result<(), Box<dyn std::error::Error>> {
@@ -6,6 +6,7 @@ use clap::Parser;
 use rust_proto::{
     client_factory::services::PluginWorkQueueClientConfig,
     graplinc::grapl::api::plugin_work_queue::v1beta1::{
+        DeleteExecutionsForTenantRequest,
         ExecutionJob,
         GetExecuteGeneratorRequest,
         PluginWorkQueueServiceClient,
@@ -163,3 +164,54 @@ async fn test_message_available_after_failure() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delete_executions_for_tenant() -> eyre::Result<()> {
+    let mut pwq_client =
+        PluginWorkQueueServiceClient::connect_with_config(PluginWorkQueueClientConfig::parse())
+            .await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let other_tenant_id = uuid::Uuid::new_v4();
+    let trace_id = uuid::Uuid::new_v4();
+    let event_source_id = uuid::Uuid::new_v4();
+    let (plugin_id, other_plugin_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+
+    let job = PushExecuteGeneratorRequest::new(
+        ExecutionJob::new("for tenant".into(), tenant_id, trace_id, event_source_id),
+        plugin_id,
+    );
+    let other_job = PushExecuteGeneratorRequest::new(
+        ExecutionJob::new(
+            "for other tenant".into(),
+            other_tenant_id,
+            trace_id,
+            event_source_id,
+        ),
+        other_plugin_id,
+    );
+
+    for request in [&job, &other_job] {
+        pwq_client.push_execute_generator(request.clone()).await?;
+    }
+
+    pwq_client
+        .delete_executions_for_tenant(DeleteExecutionsForTenantRequest { tenant_id })
+        .await?;
+
+    // The tenant's job is gone, but the other tenant's is still there
+    let retrieve_job = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id))
+        .await?;
+    assert_eq!(retrieve_job.execution_job(), None);
+
+    let retrieve_other_job = pwq_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(other_plugin_id))
+        .await?;
+    assert_eq!(
+        retrieve_other_job.execution_job(),
+        Some(other_job.execution_job())
+    );
+
+    Ok(())
+}
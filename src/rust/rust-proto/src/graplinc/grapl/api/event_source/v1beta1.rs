@@ -377,3 +377,68 @@ impl TryFrom<GetEventSourceResponse> for proto::GetEventSourceResponse {
         })
     }
 }
+
+//////////////////// DeleteEventSourcesForTenantRequest ////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteEventSourcesForTenantRequest {
+    pub tenant_id: Uuid,
+}
+
+impl ProtobufSerializable for DeleteEventSourcesForTenantRequest {
+    type ProtobufMessage = proto::DeleteEventSourcesForTenantRequest;
+}
+
+impl type_url::TypeUrl for DeleteEventSourcesForTenantRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.event_source.v1beta1.DeleteEventSourcesForTenantRequest";
+}
+
+impl TryFrom<proto::DeleteEventSourcesForTenantRequest> for DeleteEventSourcesForTenantRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::DeleteEventSourcesForTenantRequest) -> Result<Self, Self::Error> {
+        let tenant_id = value
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+
+        Ok(Self { tenant_id })
+    }
+}
+
+impl From<DeleteEventSourcesForTenantRequest> for proto::DeleteEventSourcesForTenantRequest {
+    fn from(value: DeleteEventSourcesForTenantRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+        }
+    }
+}
+
+//////////////////// DeleteEventSourcesForTenantResponse ////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteEventSourcesForTenantResponse {}
+
+impl ProtobufSerializable for DeleteEventSourcesForTenantResponse {
+    type ProtobufMessage = proto::DeleteEventSourcesForTenantResponse;
+}
+
+impl type_url::TypeUrl for DeleteEventSourcesForTenantResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.event_source.v1beta1.DeleteEventSourcesForTenantResponse";
+}
+
+impl TryFrom<proto::DeleteEventSourcesForTenantResponse> for DeleteEventSourcesForTenantResponse {
+    type Error = SerDeError;
+
+    fn try_from(_value: proto::DeleteEventSourcesForTenantResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<DeleteEventSourcesForTenantResponse> for proto::DeleteEventSourcesForTenantResponse {
+    fn from(_value: DeleteEventSourcesForTenantResponse) -> Self {
+        Self {}
+    }
+}
@@ -99,4 +99,19 @@ impl EventSourceServiceClient {
             RpcConfig::default(),
         )
     }
+
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn delete_event_sources_for_tenant(
+        &mut self,
+        request: native::DeleteEventSourcesForTenantRequest,
+    ) -> Result<native::DeleteEventSourcesForTenantResponse, EventSourceServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            delete_event_sources_for_tenant,
+            proto::DeleteEventSourcesForTenantRequest,
+            native::DeleteEventSourcesForTenantResponse,
+            RpcConfig::default(),
+        )
+    }
 }
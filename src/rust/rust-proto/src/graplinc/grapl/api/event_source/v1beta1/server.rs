@@ -66,6 +66,11 @@ pub trait EventSourceApi {
         &self,
         request: native::GetEventSourceRequest,
     ) -> Result<native::GetEventSourceResponse, Self::Error>;
+
+    async fn delete_event_sources_for_tenant(
+        &self,
+        request: native::DeleteEventSourcesForTenantRequest,
+    ) -> Result<native::DeleteEventSourcesForTenantResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -93,6 +98,13 @@ where
     ) -> Result<Response<proto::GetEventSourceResponse>, tonic::Status> {
         execute_rpc!(self, request, get_event_source)
     }
+
+    async fn delete_event_sources_for_tenant(
+        &self,
+        request: Request<proto::DeleteEventSourcesForTenantRequest>,
+    ) -> Result<Response<proto::DeleteEventSourcesForTenantResponse>, tonic::Status> {
+        execute_rpc!(self, request, delete_event_sources_for_tenant)
+    }
 }
 
 /**
@@ -21,6 +21,8 @@ use crate::{
         MergedEdgeList as MergedEdgeListProto,
         MergedGraph as MergedGraphProto,
         MergedNode as MergedNodeProto,
+        MutableF64Prop as MutableF64PropProto,
+        MutableStrProp as MutableStrPropProto,
         NodeDescription as NodeDescriptionProto,
         NodeProperty as NodePropertyProto,
         Session as SessionProto,
@@ -658,6 +660,133 @@ impl_from_for_unit!(
     &std::borrow::Cow<'_, str>
 );
 
+//
+// MutableStrProp
+//
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct MutableStrProp {
+    pub prop: String,
+}
+
+impl MutableStrProp {
+    pub fn as_inner(&self) -> &str {
+        self.prop.as_str()
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        self.prop = other_prop.prop.clone();
+    }
+}
+
+impl From<MutableStrPropProto> for MutableStrProp {
+    fn from(mutable_str_prop_proto: MutableStrPropProto) -> Self {
+        MutableStrProp {
+            prop: mutable_str_prop_proto.prop,
+        }
+    }
+}
+
+impl From<MutableStrProp> for MutableStrPropProto {
+    fn from(mutable_str_prop: MutableStrProp) -> Self {
+        MutableStrPropProto {
+            prop: mutable_str_prop.prop,
+        }
+    }
+}
+
+impl type_url::TypeUrl for MutableStrProp {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.MutableStrProp";
+}
+
+impl serde_impl::ProtobufSerializable for MutableStrProp {
+    type ProtobufMessage = MutableStrPropProto;
+}
+
+impl std::string::ToString for MutableStrProp {
+    fn to_string(&self) -> String {
+        self.prop.to_string()
+    }
+}
+
+impl_from_for_unit!(
+    MutableStrProp,
+    prop,
+    String,
+    &String,
+    &str,
+    &std::borrow::Cow<'_, str>
+);
+
+//
+// MutableF64Prop
+//
+
+/// Compared bit for bit rather than numerically, so that properties (and the
+/// nodes holding them) can still be `Eq`, and a NaN equals itself.
+#[derive(Copy, Debug, Clone)]
+pub struct MutableF64Prop {
+    pub prop: f64,
+}
+
+impl MutableF64Prop {
+    pub fn as_inner(&self) -> f64 {
+        self.prop
+    }
+
+    pub fn merge_property(&mut self, other_prop: &Self) {
+        self.prop = other_prop.prop;
+    }
+}
+
+impl PartialEq for MutableF64Prop {
+    fn eq(&self, other: &Self) -> bool {
+        self.prop.to_bits() == other.prop.to_bits()
+    }
+}
+
+impl Eq for MutableF64Prop {}
+
+impl std::hash::Hash for MutableF64Prop {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.prop.to_bits().hash(state)
+    }
+}
+
+impl From<MutableF64PropProto> for MutableF64Prop {
+    fn from(mutable_f64_prop_proto: MutableF64PropProto) -> Self {
+        MutableF64Prop {
+            prop: mutable_f64_prop_proto.prop,
+        }
+    }
+}
+
+impl From<MutableF64Prop> for MutableF64PropProto {
+    fn from(mutable_f64_prop: MutableF64Prop) -> Self {
+        MutableF64PropProto {
+            prop: mutable_f64_prop.prop,
+        }
+    }
+}
+
+impl type_url::TypeUrl for MutableF64Prop {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph.v1beta1.MutableF64Prop";
+}
+
+impl serde_impl::ProtobufSerializable for MutableF64Prop {
+    type ProtobufMessage = MutableF64PropProto;
+}
+
+impl std::string::ToString for MutableF64Prop {
+    fn to_string(&self) -> String {
+        self.prop.to_string()
+    }
+}
+
+impl_from_for_unit!(MutableF64Prop, prop, f64, f32, &f64, &f32);
+
 //
 // NodeProperty
 //
@@ -671,6 +800,8 @@ pub enum Property {
     DecrementOnlyIntProp(DecrementOnlyIntProp),
     ImmutableIntProp(ImmutableIntProp),
     ImmutableStrProp(ImmutableStrProp),
+    MutableStrProp(MutableStrProp),
+    MutableF64Prop(MutableF64Prop),
 }
 
 impl Property {
@@ -704,6 +835,14 @@ impl Property {
                 Property::ImmutableStrProp(ref mut self_prop),
                 Property::ImmutableStrProp(ref other_prop),
             ) => self_prop.merge_property(other_prop),
+            (
+                Property::MutableStrProp(ref mut self_prop),
+                Property::MutableStrProp(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
+            (
+                Property::MutableF64Prop(ref mut self_prop),
+                Property::MutableF64Prop(ref other_prop),
+            ) => self_prop.merge_property(other_prop),
             // technically we could improve type safety here by exhausting the combinations,
             // but I'm not going to type that all out right now
             // TODO: figure out what this comment means ^^
@@ -760,6 +899,18 @@ impl From<ImmutableStrProp> for Property {
     }
 }
 
+impl From<MutableStrProp> for Property {
+    fn from(p: MutableStrProp) -> Self {
+        Self::MutableStrProp(p)
+    }
+}
+
+impl From<MutableF64Prop> for Property {
+    fn from(p: MutableF64Prop) -> Self {
+        Self::MutableF64Prop(p)
+    }
+}
+
 impl std::string::ToString for Property {
     fn to_string(&self) -> String {
         match self {
@@ -778,6 +929,8 @@ impl std::string::ToString for Property {
             }
             Property::ImmutableIntProp(immutable_int_prop) => immutable_int_prop.to_string(),
             Property::ImmutableStrProp(immutable_str_prop) => immutable_str_prop.to_string(),
+            Property::MutableStrProp(mutable_str_prop) => mutable_str_prop.to_string(),
+            Property::MutableF64Prop(mutable_f64_prop) => mutable_f64_prop.to_string(),
         }
     }
 }
@@ -840,6 +993,20 @@ impl NodeProperty {
             _ => None,
         }
     }
+
+    pub fn as_mutable_str(&self) -> Option<&MutableStrProp> {
+        match self.property {
+            Property::MutableStrProp(ref prop) => Some(prop),
+            _ => None,
+        }
+    }
+
+    pub fn as_mutable_f64(&self) -> Option<MutableF64Prop> {
+        match self.property {
+            Property::MutableF64Prop(ref prop) => Some(prop.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<NodePropertyProto> for NodeProperty {
@@ -910,6 +1077,24 @@ impl TryFrom<NodePropertyProto> for NodeProperty {
                     immutable_str_prop_proto.into()
                 )
             }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::MutableStr(
+                    mutable_str_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::MutableStrProp(
+                    mutable_str_prop_proto.into()
+                )
+            }),
+            Some(
+                crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::MutableF64(
+                    mutable_f64_prop_proto
+                )
+            ) => Ok(NodeProperty {
+                property: Property::MutableF64Prop(
+                    mutable_f64_prop_proto.into()
+                )
+            }),
             None => Err(SerDeError::MissingField("property")),
         }
     }
@@ -967,6 +1152,20 @@ impl From<NodeProperty> for NodePropertyProto {
                     )
                 )
             },
+            Property::MutableStrProp(mutable_str_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::MutableStr(
+                        mutable_str_prop.into()
+                    )
+                )
+            },
+            Property::MutableF64Prop(mutable_f64_prop) => NodePropertyProto {
+                property: Some(
+                    crate::protobufs::graplinc::grapl::api::graph::v1beta1::node_property::Property::MutableF64(
+                        mutable_f64_prop.into()
+                    )
+                )
+            },
         }
     }
 }
@@ -998,6 +1197,8 @@ impl std::string::ToString for NodeProperty {
             }
             Property::ImmutableIntProp(immutable_int_prop) => immutable_int_prop.to_string(),
             Property::ImmutableStrProp(immutable_str_prop) => immutable_str_prop.to_string(),
+            Property::MutableStrProp(mutable_str_prop) => mutable_str_prop.to_string(),
+            Property::MutableF64Prop(mutable_f64_prop) => mutable_f64_prop.to_string(),
         }
     }
 }
@@ -2047,6 +2248,22 @@ pub mod test {
         }
     }
 
+    impl Arbitrary for MutableStrProp {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: String::arbitrary(g),
+            }
+        }
+    }
+
+    impl Arbitrary for MutableF64Prop {
+        fn arbitrary(g: &mut Gen) -> Self {
+            Self {
+                prop: f64::arbitrary(g),
+            }
+        }
+    }
+
     impl Arbitrary for Property {
         fn arbitrary(g: &mut Gen) -> Self {
             let props = &[
@@ -2057,6 +2274,8 @@ pub mod test {
                 Property::DecrementOnlyUintProp(DecrementOnlyUintProp::arbitrary(g)),
                 Property::ImmutableUintProp(ImmutableUintProp::arbitrary(g)),
                 Property::ImmutableStrProp(ImmutableStrProp::arbitrary(g)),
+                Property::MutableStrProp(MutableStrProp::arbitrary(g)),
+                Property::MutableF64Prop(MutableF64Prop::arbitrary(g)),
             ];
             g.choose(props).unwrap().clone()
         }
@@ -2098,6 +2317,8 @@ pub mod test {
             )),
             Property::ImmutableUintProp(ImmutableUintProp::from(hash(&[node_key, property_name]))),
             Property::ImmutableStrProp(ImmutableStrProp::from(s)),
+            Property::MutableStrProp(MutableStrProp::arbitrary(g)),
+            Property::MutableF64Prop(MutableF64Prop::arbitrary(g)),
         ];
         let p: Property = choice(node_key, props);
         p.into()
@@ -2147,6 +2368,20 @@ pub mod test {
         assert_eq!(original, x);
     }
 
+    #[quickcheck]
+    fn test_merge_mutable_str(mut x: MutableStrProp, y: MutableStrProp) {
+        init_test_env();
+        x.merge_property(&y);
+        assert_eq!(x, y);
+    }
+
+    #[quickcheck]
+    fn test_merge_mutable_f64(mut x: MutableF64Prop, y: MutableF64Prop) {
+        init_test_env();
+        x.merge_property(&y);
+        assert_eq!(x, y);
+    }
+
     #[quickcheck]
     fn test_merge_immutable_int(mut x: ImmutableIntProp, y: ImmutableIntProp) {
         init_test_env();
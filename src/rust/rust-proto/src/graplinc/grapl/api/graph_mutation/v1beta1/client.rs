@@ -94,4 +94,56 @@ impl GraphMutationClient {
             RpcConfig::default(),
         )
     }
+    pub async fn mutate_graph(
+        &mut self,
+        request: native::MutateGraphRequest,
+    ) -> Result<native::MutateGraphResponse, GraphMutationClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            mutate_graph,
+            proto::MutateGraphRequest,
+            native::MutateGraphResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn delete_node(
+        &mut self,
+        request: native::DeleteNodeRequest,
+    ) -> Result<native::DeleteNodeResponse, GraphMutationClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            delete_node,
+            proto::DeleteNodeRequest,
+            native::DeleteNodeResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn delete_edge(
+        &mut self,
+        request: native::DeleteEdgeRequest,
+    ) -> Result<native::DeleteEdgeResponse, GraphMutationClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            delete_edge,
+            proto::DeleteEdgeRequest,
+            native::DeleteEdgeResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn clear_property(
+        &mut self,
+        request: native::ClearPropertyRequest,
+    ) -> Result<native::ClearPropertyResponse, GraphMutationClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            clear_property,
+            proto::ClearPropertyRequest,
+            native::ClearPropertyResponse,
+            RpcConfig::default(),
+        )
+    }
 }
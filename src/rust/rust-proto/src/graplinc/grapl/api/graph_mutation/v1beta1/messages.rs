@@ -9,11 +9,24 @@ use crate::{
         },
     },
     protobufs::graplinc::grapl::api::graph_mutation::v1beta1::{
+        node_ref::Inner as NodeRefInnerProto,
+        BatchCreateEdge as BatchCreateEdgeProto,
+        BatchCreateNode as BatchCreateNodeProto,
+        BatchSetNodeProperty as BatchSetNodePropertyProto,
+        ClearPropertyRequest as ClearPropertyRequestProto,
+        ClearPropertyResponse as ClearPropertyResponseProto,
         CreateEdgeRequest as CreateEdgeRequestProto,
         CreateEdgeResponse as CreateEdgeResponseProto,
         CreateNodeRequest as CreateNodeRequestProto,
         CreateNodeResponse as CreateNodeResponseProto,
+        DeleteEdgeRequest as DeleteEdgeRequestProto,
+        DeleteEdgeResponse as DeleteEdgeResponseProto,
+        DeleteNodeRequest as DeleteNodeRequestProto,
+        DeleteNodeResponse as DeleteNodeResponseProto,
+        MutateGraphRequest as MutateGraphRequestProto,
+        MutateGraphResponse as MutateGraphResponseProto,
         MutationRedundancy as MutationRedundancyProto,
+        NodeRef as NodeRefProto,
         SetNodePropertyRequest as SetNodePropertyRequestProto,
         SetNodePropertyResponse as SetNodePropertyResponseProto,
     },
@@ -273,6 +286,461 @@ impl From<CreateNodeResponse> for CreateNodeResponseProto {
     }
 }
 
+/// A reference to a node from within a `MutateGraphRequest`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeRef {
+    /// A node that already exists
+    Uid(Uid),
+    /// A node created by the same request, as an index into its
+    /// `create_nodes`
+    CreatedNodeIndex(u32),
+}
+
+impl TryFrom<NodeRefProto> for NodeRef {
+    type Error = SerDeError;
+    fn try_from(proto: NodeRefProto) -> Result<Self, Self::Error> {
+        match proto.inner {
+            Some(NodeRefInnerProto::Uid(uid)) => Ok(NodeRef::Uid(uid.try_into()?)),
+            Some(NodeRefInnerProto::CreatedNodeIndex(index)) => {
+                Ok(NodeRef::CreatedNodeIndex(index))
+            }
+            None => Err(SerDeError::UnknownVariant("NodeRef.inner")),
+        }
+    }
+}
+
+impl From<NodeRef> for NodeRefProto {
+    fn from(value: NodeRef) -> Self {
+        let inner = match value {
+            NodeRef::Uid(uid) => NodeRefInnerProto::Uid(uid.into()),
+            NodeRef::CreatedNodeIndex(index) => NodeRefInnerProto::CreatedNodeIndex(index),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchCreateNode {
+    pub node_type: NodeType,
+}
+
+impl TryFrom<BatchCreateNodeProto> for BatchCreateNode {
+    type Error = SerDeError;
+    fn try_from(proto: BatchCreateNodeProto) -> Result<Self, Self::Error> {
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        Ok(Self { node_type })
+    }
+}
+
+impl From<BatchCreateNode> for BatchCreateNodeProto {
+    fn from(value: BatchCreateNode) -> Self {
+        Self {
+            node_type: Some(value.node_type.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSetNodeProperty {
+    pub node: NodeRef,
+    pub node_type: NodeType,
+    pub property_name: PropertyName,
+    pub property: NodeProperty,
+}
+
+impl TryFrom<BatchSetNodePropertyProto> for BatchSetNodeProperty {
+    type Error = SerDeError;
+    fn try_from(proto: BatchSetNodePropertyProto) -> Result<Self, Self::Error> {
+        let node = proto
+            .node
+            .ok_or(SerDeError::MissingField("node"))?
+            .try_into()?;
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        let property_name = proto
+            .property_name
+            .ok_or(SerDeError::MissingField("property_name"))?
+            .try_into()?;
+        let property = proto
+            .property
+            .ok_or(SerDeError::MissingField("property"))?
+            .try_into()?;
+        Ok(Self {
+            node,
+            node_type,
+            property_name,
+            property,
+        })
+    }
+}
+
+impl From<BatchSetNodeProperty> for BatchSetNodePropertyProto {
+    fn from(value: BatchSetNodeProperty) -> Self {
+        Self {
+            node: Some(value.node.into()),
+            node_type: Some(value.node_type.into()),
+            property_name: Some(value.property_name.into()),
+            property: Some(value.property.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchCreateEdge {
+    pub edge_name: EdgeName,
+    pub from: NodeRef,
+    pub to: NodeRef,
+    pub source_node_type: NodeType,
+}
+
+impl TryFrom<BatchCreateEdgeProto> for BatchCreateEdge {
+    type Error = SerDeError;
+    fn try_from(proto: BatchCreateEdgeProto) -> Result<Self, Self::Error> {
+        let edge_name = proto
+            .edge_name
+            .ok_or(SerDeError::MissingField("edge_name"))?
+            .try_into()?;
+        let from = proto
+            .from
+            .ok_or(SerDeError::MissingField("from"))?
+            .try_into()?;
+        let to = proto.to.ok_or(SerDeError::MissingField("to"))?.try_into()?;
+        let source_node_type = proto
+            .source_node_type
+            .ok_or(SerDeError::MissingField("source_node_type"))?
+            .try_into()?;
+        Ok(Self {
+            edge_name,
+            from,
+            to,
+            source_node_type,
+        })
+    }
+}
+
+impl From<BatchCreateEdge> for BatchCreateEdgeProto {
+    fn from(value: BatchCreateEdge) -> Self {
+        Self {
+            edge_name: Some(value.edge_name.into()),
+            from: Some(value.from.into()),
+            to: Some(value.to.into()),
+            source_node_type: Some(value.source_node_type.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutateGraphRequest {
+    pub tenant_id: uuid::Uuid,
+    pub create_nodes: Vec<BatchCreateNode>,
+    pub set_node_properties: Vec<BatchSetNodeProperty>,
+    pub create_edges: Vec<BatchCreateEdge>,
+}
+
+impl TryFrom<MutateGraphRequestProto> for MutateGraphRequest {
+    type Error = SerDeError;
+    fn try_from(proto: MutateGraphRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+        let create_nodes = proto
+            .create_nodes
+            .into_iter()
+            .map(BatchCreateNode::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let set_node_properties = proto
+            .set_node_properties
+            .into_iter()
+            .map(BatchSetNodeProperty::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let create_edges = proto
+            .create_edges
+            .into_iter()
+            .map(BatchCreateEdge::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            tenant_id,
+            create_nodes,
+            set_node_properties,
+            create_edges,
+        })
+    }
+}
+
+impl From<MutateGraphRequest> for MutateGraphRequestProto {
+    fn from(value: MutateGraphRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            create_nodes: value.create_nodes.into_iter().map(Into::into).collect(),
+            set_node_properties: value
+                .set_node_properties
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            create_edges: value.create_edges.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutateGraphResponse {
+    pub created_uids: Vec<Uid>,
+    pub set_node_property_redundancies: Vec<MutationRedundancy>,
+    pub create_edge_redundancies: Vec<MutationRedundancy>,
+}
+
+impl TryFrom<MutateGraphResponseProto> for MutateGraphResponse {
+    type Error = SerDeError;
+    fn try_from(proto: MutateGraphResponseProto) -> Result<Self, Self::Error> {
+        let created_uids = proto
+            .created_uids
+            .into_iter()
+            .map(Uid::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        // The generated accessors silently skip unknown values, which would
+        // misalign the redundancies with the request's items
+        let redundancies_from_proto = |redundancies: Vec<i32>| {
+            redundancies
+                .into_iter()
+                .map(|redundancy| {
+                    MutationRedundancyProto::from_i32(redundancy)
+                        .ok_or(SerDeError::UnknownVariant("MutationRedundancy"))?
+                        .try_into()
+                })
+                .collect::<Result<Vec<MutationRedundancy>, SerDeError>>()
+        };
+        let set_node_property_redundancies =
+            redundancies_from_proto(proto.set_node_property_redundancies)?;
+        let create_edge_redundancies = redundancies_from_proto(proto.create_edge_redundancies)?;
+        Ok(Self {
+            created_uids,
+            set_node_property_redundancies,
+            create_edge_redundancies,
+        })
+    }
+}
+
+impl From<MutateGraphResponse> for MutateGraphResponseProto {
+    fn from(value: MutateGraphResponse) -> Self {
+        let to_proto =
+            |redundancy: MutationRedundancy| MutationRedundancyProto::from(redundancy) as i32;
+        Self {
+            created_uids: value.created_uids.into_iter().map(Into::into).collect(),
+            set_node_property_redundancies: value
+                .set_node_property_redundancies
+                .into_iter()
+                .map(to_proto)
+                .collect(),
+            create_edge_redundancies: value
+                .create_edge_redundancies
+                .into_iter()
+                .map(to_proto)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteNodeRequest {
+    pub tenant_id: uuid::Uuid,
+    pub uid: Uid,
+    pub node_type: NodeType,
+}
+
+impl TryFrom<DeleteNodeRequestProto> for DeleteNodeRequest {
+    type Error = SerDeError;
+    fn try_from(proto: DeleteNodeRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+        let uid = proto
+            .uid
+            .ok_or(SerDeError::MissingField("uid"))?
+            .try_into()?;
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        Ok(Self {
+            tenant_id,
+            uid,
+            node_type,
+        })
+    }
+}
+
+impl From<DeleteNodeRequest> for DeleteNodeRequestProto {
+    fn from(value: DeleteNodeRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            uid: Some(value.uid.into()),
+            node_type: Some(value.node_type.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteNodeResponse {}
+
+impl TryFrom<DeleteNodeResponseProto> for DeleteNodeResponse {
+    type Error = SerDeError;
+    fn try_from(proto: DeleteNodeResponseProto) -> Result<Self, Self::Error> {
+        let DeleteNodeResponseProto {} = proto;
+        Ok(Self {})
+    }
+}
+
+impl From<DeleteNodeResponse> for DeleteNodeResponseProto {
+    fn from(value: DeleteNodeResponse) -> Self {
+        let DeleteNodeResponse {} = value;
+        Self {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteEdgeRequest {
+    pub edge_name: EdgeName,
+    pub tenant_id: uuid::Uuid,
+    pub from_uid: Uid,
+    pub to_uid: Uid,
+    pub source_node_type: NodeType,
+}
+
+impl TryFrom<DeleteEdgeRequestProto> for DeleteEdgeRequest {
+    type Error = SerDeError;
+    fn try_from(proto: DeleteEdgeRequestProto) -> Result<Self, Self::Error> {
+        let edge_name = proto
+            .edge_name
+            .ok_or(SerDeError::MissingField("edge_name"))?
+            .try_into()?;
+        let tenant_id = proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+        let from_uid = proto
+            .from_uid
+            .ok_or(SerDeError::MissingField("from_uid"))?
+            .try_into()?;
+        let to_uid = proto
+            .to_uid
+            .ok_or(SerDeError::MissingField("to_uid"))?
+            .try_into()?;
+        let source_node_type = proto
+            .source_node_type
+            .ok_or(SerDeError::MissingField("source_node_type"))?
+            .try_into()?;
+        Ok(Self {
+            edge_name,
+            tenant_id,
+            from_uid,
+            to_uid,
+            source_node_type,
+        })
+    }
+}
+
+impl From<DeleteEdgeRequest> for DeleteEdgeRequestProto {
+    fn from(value: DeleteEdgeRequest) -> Self {
+        Self {
+            edge_name: Some(value.edge_name.into()),
+            tenant_id: Some(value.tenant_id.into()),
+            from_uid: Some(value.from_uid.into()),
+            to_uid: Some(value.to_uid.into()),
+            source_node_type: Some(value.source_node_type.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteEdgeResponse {}
+
+impl TryFrom<DeleteEdgeResponseProto> for DeleteEdgeResponse {
+    type Error = SerDeError;
+    fn try_from(proto: DeleteEdgeResponseProto) -> Result<Self, Self::Error> {
+        let DeleteEdgeResponseProto {} = proto;
+        Ok(Self {})
+    }
+}
+
+impl From<DeleteEdgeResponse> for DeleteEdgeResponseProto {
+    fn from(value: DeleteEdgeResponse) -> Self {
+        let DeleteEdgeResponse {} = value;
+        Self {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearPropertyRequest {
+    pub tenant_id: uuid::Uuid,
+    pub uid: Uid,
+    pub node_type: NodeType,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<ClearPropertyRequestProto> for ClearPropertyRequest {
+    type Error = SerDeError;
+    fn try_from(proto: ClearPropertyRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?
+            .into();
+        let uid = proto
+            .uid
+            .ok_or(SerDeError::MissingField("uid"))?
+            .try_into()?;
+        let node_type = proto
+            .node_type
+            .ok_or(SerDeError::MissingField("node_type"))?
+            .try_into()?;
+        let property_name = proto
+            .property_name
+            .ok_or(SerDeError::MissingField("property_name"))?
+            .try_into()?;
+        Ok(Self {
+            tenant_id,
+            uid,
+            node_type,
+            property_name,
+        })
+    }
+}
+
+impl From<ClearPropertyRequest> for ClearPropertyRequestProto {
+    fn from(value: ClearPropertyRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            uid: Some(value.uid.into()),
+            node_type: Some(value.node_type.into()),
+            property_name: Some(value.property_name.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearPropertyResponse {}
+
+impl TryFrom<ClearPropertyResponseProto> for ClearPropertyResponse {
+    type Error = SerDeError;
+    fn try_from(proto: ClearPropertyResponseProto) -> Result<Self, Self::Error> {
+        let ClearPropertyResponseProto {} = proto;
+        Ok(Self {})
+    }
+}
+
+impl From<ClearPropertyResponse> for ClearPropertyResponseProto {
+    fn from(value: ClearPropertyResponse) -> Self {
+        let ClearPropertyResponse {} = value;
+        Self {}
+    }
+}
+
 impl serde_impl::ProtobufSerializable for SetNodePropertyRequest {
     type ProtobufMessage = SetNodePropertyRequestProto;
 }
@@ -327,3 +795,75 @@ impl type_url::TypeUrl for CreateNodeResponse {
         "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.CreateNodeResponse\
     ";
 }
+
+impl serde_impl::ProtobufSerializable for MutateGraphRequest {
+    type ProtobufMessage = MutateGraphRequestProto;
+}
+
+impl type_url::TypeUrl for MutateGraphRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.MutateGraphRequest";
+}
+
+impl serde_impl::ProtobufSerializable for MutateGraphResponse {
+    type ProtobufMessage = MutateGraphResponseProto;
+}
+
+impl type_url::TypeUrl for MutateGraphResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.MutateGraphResponse";
+}
+
+impl serde_impl::ProtobufSerializable for DeleteNodeRequest {
+    type ProtobufMessage = DeleteNodeRequestProto;
+}
+
+impl type_url::TypeUrl for DeleteNodeRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.DeleteNodeRequest";
+}
+
+impl serde_impl::ProtobufSerializable for DeleteNodeResponse {
+    type ProtobufMessage = DeleteNodeResponseProto;
+}
+
+impl type_url::TypeUrl for DeleteNodeResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.DeleteNodeResponse";
+}
+
+impl serde_impl::ProtobufSerializable for DeleteEdgeRequest {
+    type ProtobufMessage = DeleteEdgeRequestProto;
+}
+
+impl type_url::TypeUrl for DeleteEdgeRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.DeleteEdgeRequest";
+}
+
+impl serde_impl::ProtobufSerializable for DeleteEdgeResponse {
+    type ProtobufMessage = DeleteEdgeResponseProto;
+}
+
+impl type_url::TypeUrl for DeleteEdgeResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.DeleteEdgeResponse";
+}
+
+impl serde_impl::ProtobufSerializable for ClearPropertyRequest {
+    type ProtobufMessage = ClearPropertyRequestProto;
+}
+
+impl type_url::TypeUrl for ClearPropertyRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.ClearPropertyRequest";
+}
+
+impl serde_impl::ProtobufSerializable for ClearPropertyResponse {
+    type ProtobufMessage = ClearPropertyResponseProto;
+}
+
+impl type_url::TypeUrl for ClearPropertyResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_mutation.v1beta1.ClearPropertyResponse";
+}
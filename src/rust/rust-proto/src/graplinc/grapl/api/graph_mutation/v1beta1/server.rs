@@ -61,6 +61,22 @@ pub trait GraphMutationApi {
         &self,
         request: native::CreateEdgeRequest,
     ) -> Result<native::CreateEdgeResponse, Self::Error>;
+    async fn mutate_graph(
+        &self,
+        request: native::MutateGraphRequest,
+    ) -> Result<native::MutateGraphResponse, Self::Error>;
+    async fn delete_node(
+        &self,
+        request: native::DeleteNodeRequest,
+    ) -> Result<native::DeleteNodeResponse, Self::Error>;
+    async fn delete_edge(
+        &self,
+        request: native::DeleteEdgeRequest,
+    ) -> Result<native::DeleteEdgeResponse, Self::Error>;
+    async fn clear_property(
+        &self,
+        request: native::ClearPropertyRequest,
+    ) -> Result<native::ClearPropertyResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -91,6 +107,36 @@ where
     ) -> Result<tonic::Response<proto::CreateEdgeResponse>, tonic::Status> {
         execute_rpc!(self, request, create_edge)
     }
+    /// MutateGraph applies a batch of node creations, property updates and
+    /// edge creations, which may refer to the nodes the batch creates.
+    async fn mutate_graph(
+        &self,
+        request: tonic::Request<proto::MutateGraphRequest>,
+    ) -> Result<tonic::Response<proto::MutateGraphResponse>, tonic::Status> {
+        execute_rpc!(self, request, mutate_graph)
+    }
+    /// DeleteNode deletes a node, along with all of its properties and every
+    /// edge to or from it.
+    async fn delete_node(
+        &self,
+        request: tonic::Request<proto::DeleteNodeRequest>,
+    ) -> Result<tonic::Response<proto::DeleteNodeResponse>, tonic::Status> {
+        execute_rpc!(self, request, delete_node)
+    }
+    /// DeleteEdge deletes an edge, along with its reverse edge.
+    async fn delete_edge(
+        &self,
+        request: tonic::Request<proto::DeleteEdgeRequest>,
+    ) -> Result<tonic::Response<proto::DeleteEdgeResponse>, tonic::Status> {
+        execute_rpc!(self, request, delete_edge)
+    }
+    /// ClearProperty removes a property from a node.
+    async fn clear_property(
+        &self,
+        request: tonic::Request<proto::ClearPropertyRequest>,
+    ) -> Result<tonic::Response<proto::ClearPropertyResponse>, tonic::Status> {
+        execute_rpc!(self, request, clear_property)
+    }
 }
 
 /**
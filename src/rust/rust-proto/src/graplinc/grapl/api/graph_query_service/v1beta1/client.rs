@@ -81,4 +81,56 @@ impl GraphQueryClient {
             RpcConfig::default(),
         )
     }
+    pub async fn query_all_graphs_with_uid(
+        &mut self,
+        request: native::QueryAllGraphsWithUidRequest,
+    ) -> Result<native::QueryAllGraphsWithUidResponse, GraphQueryClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            query_all_graphs_with_uid,
+            proto::QueryAllGraphsWithUidRequest,
+            native::QueryAllGraphsWithUidResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn traverse_edge(
+        &mut self,
+        request: native::TraverseEdgeRequest,
+    ) -> Result<native::TraverseEdgeResponse, GraphQueryClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            traverse_edge,
+            proto::TraverseEdgeRequest,
+            native::TraverseEdgeResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn get_neighborhood(
+        &mut self,
+        request: native::GetNeighborhoodRequest,
+    ) -> Result<native::GetNeighborhoodResponse, GraphQueryClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_neighborhood,
+            proto::GetNeighborhoodRequest,
+            native::GetNeighborhoodResponse,
+            RpcConfig::default(),
+        )
+    }
+    pub async fn find_nodes(
+        &mut self,
+        request: native::FindNodesRequest,
+    ) -> Result<native::FindNodesResponse, GraphQueryClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            find_nodes,
+            proto::FindNodesRequest,
+            native::FindNodesResponse,
+            RpcConfig::default(),
+        )
+    }
 }
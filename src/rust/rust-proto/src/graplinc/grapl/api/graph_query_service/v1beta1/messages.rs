@@ -1,4 +1,7 @@
-use std::collections::hash_map::Entry;
+use std::{
+    collections::hash_map::Entry,
+    time::Duration,
+};
 
 use rustc_hash::{
     FxHashMap,
@@ -13,6 +16,8 @@ use crate::{
         Uid,
     },
     protobufs::graplinc::grapl::api::graph_query_service::v1beta1 as proto,
+    serde_impl::ProtobufSerializable,
+    type_url,
     SerDeError,
 };
 
@@ -107,7 +112,7 @@ impl TryFrom<proto::IntFilter> for IntFilter {
 impl From<IntFilter> for proto::IntFilter {
     fn from(value: IntFilter) -> proto::IntFilter {
         proto::IntFilter {
-            operation: value.operation as i32,
+            operation: proto::int_filter::Operation::from(value.operation) as i32,
             value: value.value,
             negated: value.negated,
         }
@@ -143,11 +148,37 @@ impl From<AndIntFilters> for proto::AndIntFilters {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl From<Vec<IntCmp>> for AndIntFilters {
+    fn from(cmps: Vec<IntCmp>) -> AndIntFilters {
+        AndIntFilters {
+            int_filters: cmps.into_iter().map(IntFilter::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct OrIntFilters {
     pub and_int_filters: Vec<AndIntFilters>,
 }
 
+impl OrIntFilters {
+    pub fn new() -> Self {
+        Self {
+            and_int_filters: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            and_int_filters: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, filters: AndIntFilters) {
+        self.and_int_filters.push(filters);
+    }
+}
+
 impl TryFrom<proto::OrIntFilters> for OrIntFilters {
     type Error = SerDeError;
     fn try_from(value: proto::OrIntFilters) -> Result<Self, Self::Error> {
@@ -171,11 +202,61 @@ impl From<OrIntFilters> for proto::OrIntFilters {
     }
 }
 
+// Higher level helper
+#[derive(Clone, Debug)]
+pub enum IntCmp {
+    Eq(i64, bool),
+    Lt(i64, bool),
+    Lte(i64, bool),
+    Gt(i64, bool),
+    Gte(i64, bool),
+    Has,
+}
+
+impl IntCmp {
+    pub fn eq(value: i64, negated: bool) -> Self {
+        IntCmp::Eq(value, negated)
+    }
+}
+
+impl From<&IntFilter> for IntCmp {
+    fn from(int_filter: &IntFilter) -> IntCmp {
+        let (value, negated) = (int_filter.value, int_filter.negated);
+        match int_filter.operation {
+            IntOperation::Has => IntCmp::Has,
+            IntOperation::Equal => IntCmp::Eq(value, negated),
+            IntOperation::LessThan => IntCmp::Lt(value, negated),
+            IntOperation::LessThanOrEqual => IntCmp::Lte(value, negated),
+            IntOperation::GreaterThan => IntCmp::Gt(value, negated),
+            IntOperation::GreaterThanOrEqual => IntCmp::Gte(value, negated),
+        }
+    }
+}
+
+impl From<IntCmp> for IntFilter {
+    fn from(int_cmp: IntCmp) -> IntFilter {
+        let (operation, value, negated) = match int_cmp {
+            IntCmp::Has => (IntOperation::Has, 0, false),
+            IntCmp::Eq(value, negated) => (IntOperation::Equal, value, negated),
+            IntCmp::Lt(value, negated) => (IntOperation::LessThan, value, negated),
+            IntCmp::Lte(value, negated) => (IntOperation::LessThanOrEqual, value, negated),
+            IntCmp::Gt(value, negated) => (IntOperation::GreaterThan, value, negated),
+            IntCmp::Gte(value, negated) => (IntOperation::GreaterThanOrEqual, value, negated),
+        };
+        IntFilter {
+            operation,
+            value,
+            negated,
+        }
+    }
+}
+
 // Higher level helper
 #[derive(Clone, Debug)]
 pub enum StrCmp<'a> {
     Eq(&'a str, bool),
     Contains(&'a str, bool),
+    Regex(&'a str, bool),
     Has,
 }
 
@@ -196,8 +277,7 @@ impl<'a> From<&'a StringFilter> for StrCmp<'a> {
                 StrCmp::Contains(string_filter.value.as_str(), string_filter.negated)
             }
             StringOperation::Regex => {
-                // todo: We don't currently support Regex, but we should
-                unimplemented!()
+                StrCmp::Regex(string_filter.value.as_str(), string_filter.negated)
             }
         }
     }
@@ -208,6 +288,7 @@ impl<'a> From<&'a StringFilter> for StrCmp<'a> {
 pub enum StringCmp {
     Eq(String, bool),
     Contains(String, bool),
+    Regex(String, bool),
     Has,
 }
 
@@ -225,10 +306,7 @@ impl From<StringFilter> for StringCmp {
             StringOperation::Contains => {
                 StringCmp::Contains(string_filter.value, string_filter.negated)
             }
-            StringOperation::Regex => {
-                // todo: We don't currently support Regex, but we should
-                unimplemented!()
-            }
+            StringOperation::Regex => StringCmp::Regex(string_filter.value, string_filter.negated),
         }
     }
 }
@@ -251,6 +329,11 @@ impl From<StringCmp> for StringFilter {
                 value,
                 negated,
             },
+            StringCmp::Regex(value, negated) => StringFilter {
+                operation: StringOperation::Regex,
+                value,
+                negated,
+            },
         }
     }
 }
@@ -314,7 +397,7 @@ impl TryFrom<proto::StringFilter> for StringFilter {
 impl From<StringFilter> for proto::StringFilter {
     fn from(value: StringFilter) -> proto::StringFilter {
         proto::StringFilter {
-            operation: value.operation as i32,
+            operation: proto::string_filter::Operation::from(value.operation) as i32,
             value: value.value,
             negated: value.negated,
         }
@@ -468,7 +551,7 @@ impl TryFrom<proto::UidFilter> for UidFilter {
 impl From<UidFilter> for proto::UidFilter {
     fn from(value: UidFilter) -> proto::UidFilter {
         proto::UidFilter {
-            operation: value.operation as i32,
+            operation: proto::uid_filter::Operation::from(value.operation) as i32,
             value: Some(value.value.into()),
         }
     }
@@ -526,7 +609,24 @@ impl NodePropertyQuery {
     pub fn merge(&mut self, other: Self) {
         debug_assert_eq!(self.query_id, other.query_id);
         debug_assert_eq!(self.node_type, other.node_type);
+        self.int_filters.extend(other.int_filters);
         self.string_filters.extend(other.string_filters);
+        self.uid_filters
+            .uid_filters
+            .extend(other.uid_filters.uid_filters);
+    }
+
+    pub fn with_int_filters(
+        &mut self,
+        property_name: PropertyName,
+        filters: impl Into<AndIntFilters>,
+    ) -> &mut Self {
+        let filters = filters.into();
+        self.int_filters
+            .entry(property_name)
+            .or_insert_with(|| OrIntFilters::with_capacity(1))
+            .push(filters);
+        self
     }
 
     pub fn with_string_filters(
@@ -541,6 +641,11 @@ impl NodePropertyQuery {
             .push(filters);
         self
     }
+
+    pub fn with_uid_filter(&mut self, uid_filter: UidFilter) -> &mut Self {
+        self.uid_filters.uid_filters.push(uid_filter);
+        self
+    }
 }
 
 impl TryFrom<proto::NodePropertyQuery> for NodePropertyQuery {
@@ -626,12 +731,63 @@ impl From<NodePropertyQuery> for proto::NodePropertyQuery {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EdgeFilter {
+    pub edge_name: EdgeName,
+    pub neighbor_query_id: QueryId,
+}
+
+impl TryFrom<proto::EdgeFilter> for EdgeFilter {
+    type Error = SerDeError;
+    fn try_from(value: proto::EdgeFilter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            edge_name: value
+                .edge_name
+                .ok_or(SerDeError::MissingField("edge_name"))?
+                .try_into()?,
+            neighbor_query_id: value
+                .neighbor_query_id
+                .ok_or(SerDeError::MissingField("neighbor_query_id"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<EdgeFilter> for proto::EdgeFilter {
+    fn from(value: EdgeFilter) -> Self {
+        Self {
+            edge_name: Some(value.edge_name.into()),
+            neighbor_query_id: Some(value.neighbor_query_id.into()),
+        }
+    }
+}
+
+/// A group of edges out of a node query, at least one of which must match
+#[derive(Debug, Clone, Default)]
+pub struct OrEdgeFilters {
+    pub edge_filters: Vec<EdgeFilter>,
+}
+
+impl OrEdgeFilters {
+    pub fn new() -> Self {
+        Self {
+            edge_filters: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, edge_filter: EdgeFilter) {
+        self.edge_filters.push(edge_filter);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GraphQuery {
     pub root_query_id: QueryId,
     pub node_property_queries: FxHashMap<QueryId, NodePropertyQuery>,
     pub edge_filters: FxHashMap<(QueryId, EdgeName), FxHashSet<QueryId>>,
     pub edge_map: FxHashMap<EdgeName, EdgeName>,
+    pub or_edge_filters: FxHashMap<QueryId, Vec<OrEdgeFilters>>,
+    pub negated_edge_filters: FxHashMap<QueryId, Vec<EdgeFilter>>,
 }
 
 impl GraphQuery {
@@ -730,12 +886,112 @@ impl TryFrom<proto::GraphQuery> for GraphQuery {
             edge_map.insert(forward_edge_name, reverse_edge_name);
         }
 
-        Ok(Self {
+        let mut or_edge_filters: FxHashMap<QueryId, Vec<OrEdgeFilters>> = FxHashMap::default();
+        for or_edge_filters_entry in value.or_edge_filters {
+            let query_id = or_edge_filters_entry
+                .query_id
+                .ok_or_else(|| SerDeError::MissingField("query_id"))?
+                .try_into()?;
+            let edge_filters = or_edge_filters_entry
+                .edge_filters
+                .into_iter()
+                .map(EdgeFilter::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            or_edge_filters
+                .entry(query_id)
+                .or_default()
+                .push(OrEdgeFilters { edge_filters });
+        }
+
+        let mut negated_edge_filters: FxHashMap<QueryId, Vec<EdgeFilter>> = FxHashMap::default();
+        for negated_edge_filter_entry in value.negated_edge_filters {
+            let query_id = negated_edge_filter_entry
+                .query_id
+                .ok_or_else(|| SerDeError::MissingField("query_id"))?
+                .try_into()?;
+            let edge_filter = negated_edge_filter_entry
+                .edge_filter
+                .ok_or_else(|| SerDeError::MissingField("edge_filter"))?
+                .try_into()?;
+            negated_edge_filters
+                .entry(query_id)
+                .or_default()
+                .push(edge_filter);
+        }
+
+        let graph_query = Self {
             root_query_id,
             node_property_queries,
             edge_filters,
             edge_map,
-        })
+            or_edge_filters,
+            negated_edge_filters,
+        };
+        graph_query.validate()?;
+        Ok(graph_query)
+    }
+}
+
+impl GraphQuery {
+    /// Checks that every query id the graph query refers to has a node
+    /// property query, and that every edge it filters on has a reverse edge in
+    /// the edge map, so that a query from a client can't refer to anything
+    /// that isn't there.
+    fn validate(&self) -> Result<(), SerDeError> {
+        let check_query_id = |field_name: &'static str, query_id: &QueryId| {
+            if self.node_property_queries.contains_key(query_id) {
+                Ok(())
+            } else {
+                Err(SerDeError::InvalidField {
+                    field_name,
+                    assertion: format!(
+                        "query id {} must be in node_property_queries",
+                        query_id.value
+                    ),
+                })
+            }
+        };
+        let check_edge_name = |field_name: &'static str, edge_name: &EdgeName| {
+            if self.edge_map.contains_key(edge_name) {
+                Ok(())
+            } else {
+                Err(SerDeError::InvalidField {
+                    field_name,
+                    assertion: format!("edge {edge_name} must be in edge_map"),
+                })
+            }
+        };
+
+        check_query_id("root_query_id", &self.root_query_id)?;
+
+        for ((query_id, edge_name), neighbor_query_ids) in self.edge_filters.iter() {
+            check_query_id("edge_filters", query_id)?;
+            check_edge_name("edge_filters", edge_name)?;
+            for neighbor_query_id in neighbor_query_ids {
+                check_query_id("edge_filters", neighbor_query_id)?;
+            }
+        }
+
+        for (query_id, or_edge_filters) in self.or_edge_filters.iter() {
+            check_query_id("or_edge_filters", query_id)?;
+            for edge_filter in or_edge_filters
+                .iter()
+                .flat_map(|or_edge_filters| or_edge_filters.edge_filters.iter())
+            {
+                check_edge_name("or_edge_filters", &edge_filter.edge_name)?;
+                check_query_id("or_edge_filters", &edge_filter.neighbor_query_id)?;
+            }
+        }
+
+        for (query_id, edge_filters) in self.negated_edge_filters.iter() {
+            check_query_id("negated_edge_filters", query_id)?;
+            for edge_filter in edge_filters {
+                check_edge_name("negated_edge_filters", &edge_filter.edge_name)?;
+                check_query_id("negated_edge_filters", &edge_filter.neighbor_query_id)?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -774,16 +1030,49 @@ impl From<GraphQuery> for proto::GraphQuery {
                 })
                 .collect(),
         });
+
+        let or_edge_filters = value
+            .or_edge_filters
+            .into_iter()
+            .flat_map(|(query_id, or_edge_filters)| {
+                or_edge_filters
+                    .into_iter()
+                    .map(move |or_edge_filters| proto::OrEdgeFiltersEntry {
+                        query_id: Some(query_id.into()),
+                        edge_filters: or_edge_filters
+                            .edge_filters
+                            .into_iter()
+                            .map(proto::EdgeFilter::from)
+                            .collect(),
+                    })
+            })
+            .collect();
+
+        let negated_edge_filters = value
+            .negated_edge_filters
+            .into_iter()
+            .flat_map(|(query_id, edge_filters)| {
+                edge_filters
+                    .into_iter()
+                    .map(move |edge_filter| proto::NegatedEdgeFilterEntry {
+                        query_id: Some(query_id.into()),
+                        edge_filter: Some(edge_filter.into()),
+                    })
+            })
+            .collect();
+
         Self {
             root_query_id,
             node_property_queries,
             edge_filters,
             edge_map,
+            or_edge_filters,
+            negated_edge_filters,
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct StringProperties {
     pub prop_map: FxHashMap<PropertyName, String>,
 }
@@ -831,19 +1120,186 @@ impl From<StringProperties> for proto::StringProperties {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Int64Properties {
+    pub prop_map: FxHashMap<PropertyName, i64>,
+}
+
+impl Int64Properties {
+    pub fn merge(&mut self, other: Self) {
+        self.prop_map.extend(other.prop_map);
+    }
+
+    pub fn add_int64_property(&mut self, property_name: PropertyName, value: i64) {
+        self.prop_map.insert(property_name, value);
+    }
+}
+
+impl TryFrom<proto::Int64Properties> for Int64Properties {
+    type Error = SerDeError;
+    fn try_from(value: proto::Int64Properties) -> Result<Self, Self::Error> {
+        let mut prop_map = FxHashMap::default();
+        prop_map.reserve(value.properties.len());
+
+        for int64_property in value.properties {
+            let property_name = int64_property
+                .property_name
+                .ok_or_else(|| SerDeError::MissingField("property_name"))?;
+            prop_map.insert(property_name.try_into()?, int64_property.property_value);
+        }
+
+        Ok(Self { prop_map })
+    }
+}
+
+impl From<Int64Properties> for proto::Int64Properties {
+    fn from(value: Int64Properties) -> Self {
+        let props_as_vec: Vec<proto::Int64Property> = value
+            .prop_map
+            .into_iter()
+            .map(|(k, v)| proto::Int64Property {
+                property_name: Some(k.into()),
+                property_value: v,
+            })
+            .collect();
+        proto::Int64Properties {
+            properties: props_as_vec,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UInt64Properties {
+    pub prop_map: FxHashMap<PropertyName, u64>,
+}
+
+impl UInt64Properties {
+    pub fn merge(&mut self, other: Self) {
+        self.prop_map.extend(other.prop_map);
+    }
+
+    pub fn add_uint64_property(&mut self, property_name: PropertyName, value: u64) {
+        self.prop_map.insert(property_name, value);
+    }
+}
+
+impl TryFrom<proto::UInt64Properties> for UInt64Properties {
+    type Error = SerDeError;
+    fn try_from(value: proto::UInt64Properties) -> Result<Self, Self::Error> {
+        let mut prop_map = FxHashMap::default();
+        prop_map.reserve(value.properties.len());
+
+        for uint64_property in value.properties {
+            let property_name = uint64_property
+                .property_name
+                .ok_or_else(|| SerDeError::MissingField("property_name"))?;
+            prop_map.insert(property_name.try_into()?, uint64_property.property_value);
+        }
+
+        Ok(Self { prop_map })
+    }
+}
+
+impl From<UInt64Properties> for proto::UInt64Properties {
+    fn from(value: UInt64Properties) -> Self {
+        let props_as_vec: Vec<proto::UInt64Property> = value
+            .prop_map
+            .into_iter()
+            .map(|(k, v)| proto::UInt64Property {
+                property_name: Some(k.into()),
+                property_value: v,
+            })
+            .collect();
+        proto::UInt64Properties {
+            properties: props_as_vec,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Float64Properties {
+    pub prop_map: FxHashMap<PropertyName, f64>,
+}
+
+impl Float64Properties {
+    pub fn merge(&mut self, other: Self) {
+        self.prop_map.extend(other.prop_map);
+    }
+
+    pub fn add_float64_property(&mut self, property_name: PropertyName, value: f64) {
+        self.prop_map.insert(property_name, value);
+    }
+}
+
+// Compared bit for bit, like `MutableF64Prop`, so that views can be `Eq`
+impl PartialEq for Float64Properties {
+    fn eq(&self, other: &Self) -> bool {
+        self.prop_map.len() == other.prop_map.len()
+            && self.prop_map.iter().all(|(property_name, value)| {
+                other
+                    .prop_map
+                    .get(property_name)
+                    .map_or(false, |other_value| {
+                        value.to_bits() == other_value.to_bits()
+                    })
+            })
+    }
+}
+
+impl Eq for Float64Properties {}
+
+impl TryFrom<proto::Float64Properties> for Float64Properties {
+    type Error = SerDeError;
+    fn try_from(value: proto::Float64Properties) -> Result<Self, Self::Error> {
+        let mut prop_map = FxHashMap::default();
+        prop_map.reserve(value.properties.len());
+
+        for float64_property in value.properties {
+            let property_name = float64_property
+                .property_name
+                .ok_or_else(|| SerDeError::MissingField("property_name"))?;
+            prop_map.insert(property_name.try_into()?, float64_property.property_value);
+        }
+
+        Ok(Self { prop_map })
+    }
+}
+
+impl From<Float64Properties> for proto::Float64Properties {
+    fn from(value: Float64Properties) -> Self {
+        let props_as_vec: Vec<proto::Float64Property> = value
+            .prop_map
+            .into_iter()
+            .map(|(k, v)| proto::Float64Property {
+                property_name: Some(k.into()),
+                property_value: v,
+            })
+            .collect();
+        proto::Float64Properties {
+            properties: props_as_vec,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NodePropertiesView {
     pub uid: Uid,
     pub node_type: NodeType,
     pub string_properties: StringProperties,
+    pub int64_properties: Int64Properties,
+    pub uint64_properties: UInt64Properties,
+    pub float64_properties: Float64Properties,
 }
 
 impl NodePropertiesView {
-    pub fn new(uid: Uid, node_type: NodeType, string_properties: StringProperties) -> Self {
+    pub fn new(uid: Uid, node_type: NodeType) -> Self {
         Self {
             uid,
             node_type,
-            string_properties,
+            string_properties: Default::default(),
+            int64_properties: Default::default(),
+            uint64_properties: Default::default(),
+            float64_properties: Default::default(),
         }
     }
 
@@ -851,12 +1307,30 @@ impl NodePropertiesView {
         debug_assert_eq!(self.uid, other.uid);
         debug_assert_eq!(self.node_type, other.node_type);
         self.string_properties.merge(other.string_properties);
+        self.int64_properties.merge(other.int64_properties);
+        self.uint64_properties.merge(other.uint64_properties);
+        self.float64_properties.merge(other.float64_properties);
     }
 
     pub fn add_string_property(&mut self, property_name: PropertyName, value: String) {
         self.string_properties
             .add_string_property(property_name, value);
     }
+
+    pub fn add_int64_property(&mut self, property_name: PropertyName, value: i64) {
+        self.int64_properties
+            .add_int64_property(property_name, value);
+    }
+
+    pub fn add_uint64_property(&mut self, property_name: PropertyName, value: u64) {
+        self.uint64_properties
+            .add_uint64_property(property_name, value);
+    }
+
+    pub fn add_float64_property(&mut self, property_name: PropertyName, value: f64) {
+        self.float64_properties
+            .add_float64_property(property_name, value);
+    }
 }
 
 impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
@@ -868,6 +1342,14 @@ impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
 
         let string_properties = StringProperties::try_from(proto_string_properties)?;
 
+        // Views from senders that predate the numeric properties don't have
+        // them, which is the same as having none
+        let int64_properties = value.int64_properties.unwrap_or_default().try_into()?;
+
+        let uint64_properties = value.uint64_properties.unwrap_or_default().try_into()?;
+
+        let float64_properties = value.float64_properties.unwrap_or_default().try_into()?;
+
         Ok(Self {
             uid: value
                 .uid
@@ -878,6 +1360,9 @@ impl TryFrom<proto::NodePropertiesView> for NodePropertiesView {
                 .ok_or(SerDeError::MissingField("node_type"))?
                 .try_into()?,
             string_properties,
+            int64_properties,
+            uint64_properties,
+            float64_properties,
         })
     }
 }
@@ -890,6 +1375,9 @@ impl From<NodePropertiesView> for proto::NodePropertiesView {
             uid: Some(value.uid.into()),
             node_type: Some(value.node_type.into()),
             string_properties: Some(string_properties),
+            int64_properties: Some(value.int64_properties.into()),
+            uint64_properties: Some(value.uint64_properties.into()),
+            float64_properties: Some(value.float64_properties.into()),
         }
     }
 }
@@ -1049,7 +1537,7 @@ impl From<EdgeViewMap> for proto::EdgeViewMap {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct GraphView {
     pub nodes: FxHashMap<Uid, NodePropertiesView>,
     pub edges: FxHashMap<(Uid, EdgeName), FxHashSet<Uid>>,
@@ -1059,7 +1547,7 @@ impl GraphView {
     pub fn new_node(&mut self, uid: Uid, node_type: NodeType) -> &mut NodePropertiesView {
         self.nodes
             .entry(uid)
-            .or_insert_with(|| NodePropertiesView::new(uid, node_type, Default::default()))
+            .or_insert_with(|| NodePropertiesView::new(uid, node_type))
     }
 
     pub fn add_node(&mut self, node: NodePropertiesView) {
@@ -1149,6 +1637,15 @@ impl From<GraphView> for proto::GraphView {
     }
 }
 
+impl type_url::TypeUrl for GraphView {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.GraphView";
+}
+
+impl ProtobufSerializable for GraphView {
+    type ProtobufMessage = proto::GraphView;
+}
+
 #[derive(Debug, Clone)]
 pub struct QueryGraphWithUidRequest {
     pub tenant_id: uuid::Uuid,
@@ -1354,3 +1851,561 @@ impl From<QueryGraphFromUidResponse> for proto::QueryGraphFromUidResponse {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct QueryAllGraphsWithUidRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_uid: Uid,
+    pub graph_query: GraphQuery,
+    pub max_results: u32,
+}
+
+impl TryFrom<proto::QueryAllGraphsWithUidRequest> for QueryAllGraphsWithUidRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::QueryAllGraphsWithUidRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            graph_query: value
+                .graph_query
+                .ok_or(SerDeError::MissingField("graph_query"))?
+                .try_into()?,
+            max_results: value.max_results,
+        })
+    }
+}
+
+impl From<QueryAllGraphsWithUidRequest> for proto::QueryAllGraphsWithUidRequest {
+    fn from(value: QueryAllGraphsWithUidRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_uid: Some(value.node_uid.into()),
+            graph_query: Some(value.graph_query.into()),
+            max_results: value.max_results,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryAllGraphsWithUidResponse {
+    pub matches: Vec<MatchedGraphWithUid>,
+    pub truncated: bool,
+}
+
+impl TryFrom<proto::QueryAllGraphsWithUidResponse> for QueryAllGraphsWithUidResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::QueryAllGraphsWithUidResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            matches: value
+                .matches
+                .into_iter()
+                .map(MatchedGraphWithUid::try_from)
+                .collect::<Result<_, _>>()?,
+            truncated: value.truncated,
+        })
+    }
+}
+
+impl From<QueryAllGraphsWithUidResponse> for proto::QueryAllGraphsWithUidResponse {
+    fn from(value: QueryAllGraphsWithUidResponse) -> Self {
+        Self {
+            matches: value.matches.into_iter().map(Into::into).collect(),
+            truncated: value.truncated,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraverseEdgeRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_uid: Uid,
+    pub edge_name: EdgeName,
+    pub max_depth: u32,
+    pub max_nodes: u32,
+    pub timeout: Duration,
+}
+
+impl TryFrom<proto::TraverseEdgeRequest> for TraverseEdgeRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::TraverseEdgeRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            edge_name: value
+                .edge_name
+                .ok_or(SerDeError::MissingField("edge_name"))?
+                .try_into()?,
+            max_depth: value.max_depth,
+            max_nodes: value.max_nodes,
+            timeout: value
+                .timeout
+                .ok_or(SerDeError::MissingField("timeout"))?
+                .into(),
+        })
+    }
+}
+
+impl From<TraverseEdgeRequest> for proto::TraverseEdgeRequest {
+    fn from(value: TraverseEdgeRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_uid: Some(value.node_uid.into()),
+            edge_name: Some(value.edge_name.into()),
+            max_depth: value.max_depth,
+            max_nodes: value.max_nodes,
+            timeout: Some(value.timeout.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraverseEdgeResponse {
+    pub graph: GraphView,
+    pub truncated: bool,
+}
+
+impl TryFrom<proto::TraverseEdgeResponse> for TraverseEdgeResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::TraverseEdgeResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            graph: value
+                .graph
+                .ok_or(SerDeError::MissingField("graph"))?
+                .try_into()?,
+            truncated: value.truncated,
+        })
+    }
+}
+
+impl From<TraverseEdgeResponse> for proto::TraverseEdgeResponse {
+    fn from(value: TraverseEdgeResponse) -> Self {
+        Self {
+            graph: Some(value.graph.into()),
+            truncated: value.truncated,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetNeighborhoodRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_uid: Uid,
+    pub include_neighbors: bool,
+    pub max_edges: u32,
+}
+
+impl TryFrom<proto::GetNeighborhoodRequest> for GetNeighborhoodRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::GetNeighborhoodRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_uid: value
+                .node_uid
+                .ok_or(SerDeError::MissingField("node_uid"))?
+                .try_into()?,
+            include_neighbors: value.include_neighbors,
+            max_edges: value.max_edges,
+        })
+    }
+}
+
+impl From<GetNeighborhoodRequest> for proto::GetNeighborhoodRequest {
+    fn from(value: GetNeighborhoodRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_uid: Some(value.node_uid.into()),
+            include_neighbors: value.include_neighbors,
+            max_edges: value.max_edges,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GetNeighborhoodResponse {
+    pub graph: GraphView,
+    pub truncated: bool,
+}
+
+impl TryFrom<proto::GetNeighborhoodResponse> for GetNeighborhoodResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::GetNeighborhoodResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            graph: value
+                .graph
+                .ok_or(SerDeError::MissingField("graph"))?
+                .try_into()?,
+            truncated: value.truncated,
+        })
+    }
+}
+
+impl From<GetNeighborhoodResponse> for proto::GetNeighborhoodResponse {
+    fn from(value: GetNeighborhoodResponse) -> Self {
+        Self {
+            graph: Some(value.graph.into()),
+            truncated: value.truncated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyValueFilter {
+    StringEqual(String),
+    StringPrefix(String),
+    Int64Equal(i64),
+    UInt64Equal(u64),
+}
+
+impl TryFrom<proto::PropertyValueFilter> for PropertyValueFilter {
+    type Error = SerDeError;
+    fn try_from(value_proto: proto::PropertyValueFilter) -> Result<Self, Self::Error> {
+        match value_proto.inner {
+            Some(proto::property_value_filter::Inner::StringEqual(value)) => {
+                Ok(PropertyValueFilter::StringEqual(value))
+            }
+            Some(proto::property_value_filter::Inner::StringPrefix(value)) => {
+                Ok(PropertyValueFilter::StringPrefix(value))
+            }
+            Some(proto::property_value_filter::Inner::Int64Equal(value)) => {
+                Ok(PropertyValueFilter::Int64Equal(value))
+            }
+            Some(proto::property_value_filter::Inner::Uint64Equal(value)) => {
+                Ok(PropertyValueFilter::UInt64Equal(value))
+            }
+            None => Err(SerDeError::UnknownVariant("PropertyValueFilter")),
+        }
+    }
+}
+
+impl From<PropertyValueFilter> for proto::PropertyValueFilter {
+    fn from(value: PropertyValueFilter) -> Self {
+        let inner = match value {
+            PropertyValueFilter::StringEqual(value) => {
+                proto::property_value_filter::Inner::StringEqual(value)
+            }
+            PropertyValueFilter::StringPrefix(value) => {
+                proto::property_value_filter::Inner::StringPrefix(value)
+            }
+            PropertyValueFilter::Int64Equal(value) => {
+                proto::property_value_filter::Inner::Int64Equal(value)
+            }
+            PropertyValueFilter::UInt64Equal(value) => {
+                proto::property_value_filter::Inner::Uint64Equal(value)
+            }
+        };
+        proto::PropertyValueFilter { inner: Some(inner) }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FindNodesRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+    pub property_name: PropertyName,
+    pub filter: PropertyValueFilter,
+    pub max_results: u32,
+}
+
+impl TryFrom<proto::FindNodesRequest> for FindNodesRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::FindNodesRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: value
+                .tenant_id
+                .ok_or(SerDeError::MissingField("tenant_id"))?
+                .into(),
+            node_type: value
+                .node_type
+                .ok_or(SerDeError::MissingField("node_type"))?
+                .try_into()?,
+            property_name: value
+                .property_name
+                .ok_or(SerDeError::MissingField("property_name"))?
+                .try_into()?,
+            filter: value
+                .filter
+                .ok_or(SerDeError::MissingField("filter"))?
+                .try_into()?,
+            max_results: value.max_results,
+        })
+    }
+}
+
+impl From<FindNodesRequest> for proto::FindNodesRequest {
+    fn from(value: FindNodesRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+            node_type: Some(value.node_type.into()),
+            property_name: Some(value.property_name.into()),
+            filter: Some(value.filter.into()),
+            max_results: value.max_results,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FindNodesResponse {
+    pub uids: Vec<Uid>,
+    pub truncated: bool,
+}
+
+impl TryFrom<proto::FindNodesResponse> for FindNodesResponse {
+    type Error = SerDeError;
+    fn try_from(value: proto::FindNodesResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uids: value
+                .uids
+                .into_iter()
+                .map(Uid::try_from)
+                .collect::<Result<_, _>>()?,
+            truncated: value.truncated,
+        })
+    }
+}
+
+impl From<FindNodesResponse> for proto::FindNodesResponse {
+    fn from(value: FindNodesResponse) -> Self {
+        Self {
+            uids: value.uids.into_iter().map(Into::into).collect(),
+            truncated: value.truncated,
+        }
+    }
+}
+
+/// Which of the server's query limits a query ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryLimit {
+    NodesVisited,
+    RoundTrips,
+    Deadline,
+}
+
+impl TryFrom<proto::query_budget_exceeded::Limit> for QueryLimit {
+    type Error = SerDeError;
+    fn try_from(value_proto: proto::query_budget_exceeded::Limit) -> Result<Self, Self::Error> {
+        match value_proto {
+            proto::query_budget_exceeded::Limit::Unspecified => {
+                Err(SerDeError::UnknownVariant("QueryLimit"))
+            }
+            proto::query_budget_exceeded::Limit::NodesVisited => Ok(Self::NodesVisited),
+            proto::query_budget_exceeded::Limit::RoundTrips => Ok(Self::RoundTrips),
+            proto::query_budget_exceeded::Limit::Deadline => Ok(Self::Deadline),
+        }
+    }
+}
+
+impl From<QueryLimit> for proto::query_budget_exceeded::Limit {
+    fn from(value: QueryLimit) -> Self {
+        match value {
+            QueryLimit::NodesVisited => proto::query_budget_exceeded::Limit::NodesVisited,
+            QueryLimit::RoundTrips => proto::query_budget_exceeded::Limit::RoundTrips,
+            QueryLimit::Deadline => proto::query_budget_exceeded::Limit::Deadline,
+        }
+    }
+}
+
+/// The details of the ResourceExhausted status a query gets when it runs into
+/// one of the server's query limits, serialized into the status' details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryBudgetExceeded {
+    pub limit: QueryLimit,
+    pub nodes_visited: u64,
+    pub round_trips: u64,
+    pub elapsed: Duration,
+}
+
+impl TryFrom<proto::QueryBudgetExceeded> for QueryBudgetExceeded {
+    type Error = SerDeError;
+    fn try_from(value: proto::QueryBudgetExceeded) -> Result<Self, Self::Error> {
+        Ok(Self {
+            limit: value.limit().try_into()?,
+            nodes_visited: value.nodes_visited,
+            round_trips: value.round_trips,
+            elapsed: value
+                .elapsed
+                .ok_or(SerDeError::MissingField("elapsed"))?
+                .into(),
+        })
+    }
+}
+
+impl From<QueryBudgetExceeded> for proto::QueryBudgetExceeded {
+    fn from(value: QueryBudgetExceeded) -> Self {
+        Self {
+            limit: proto::query_budget_exceeded::Limit::from(value.limit) as i32,
+            nodes_visited: value.nodes_visited,
+            round_trips: value.round_trips,
+            elapsed: Some(value.elapsed.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for QueryBudgetExceeded {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_query_service.v1beta1.QueryBudgetExceeded";
+}
+
+impl ProtobufSerializable for QueryBudgetExceeded {
+    type ProtobufMessage = proto::QueryBudgetExceeded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerDe;
+
+    #[test]
+    fn test_node_properties_view_without_numeric_properties() {
+        let uid = Uid::from_u64(1).unwrap();
+        let node_type = NodeType::try_from("Process").unwrap();
+        let view = proto::NodePropertiesView {
+            uid: Some(uid.into()),
+            node_type: Some(node_type.clone().into()),
+            string_properties: Some(proto::StringProperties::default()),
+            int64_properties: None,
+            uint64_properties: None,
+            float64_properties: None,
+        };
+
+        let view = NodePropertiesView::try_from(view).expect("expected the view to decode");
+        assert_eq!(view, NodePropertiesView::new(uid, node_type));
+    }
+
+    fn graph_query() -> GraphQuery {
+        let root_query_id = QueryId { value: 1 };
+        let neighbor_query_id = QueryId { value: 2 };
+        let forward_edge_name = EdgeName::try_from("children").unwrap();
+        let reverse_edge_name = EdgeName::try_from("parent").unwrap();
+
+        let mut graph_query = GraphQuery {
+            root_query_id,
+            node_property_queries: FxHashMap::default(),
+            edge_filters: FxHashMap::default(),
+            edge_map: FxHashMap::default(),
+            or_edge_filters: FxHashMap::default(),
+            negated_edge_filters: FxHashMap::default(),
+        };
+        let node_type = NodeType::try_from("Process").unwrap();
+        graph_query.add_node(root_query_id, node_type.clone());
+        graph_query.add_node(neighbor_query_id, node_type);
+        graph_query.edge_filters.insert(
+            (root_query_id, forward_edge_name.clone()),
+            FxHashSet::from_iter([neighbor_query_id]),
+        );
+        graph_query.edge_filters.insert(
+            (neighbor_query_id, reverse_edge_name.clone()),
+            FxHashSet::from_iter([root_query_id]),
+        );
+        graph_query
+            .edge_map
+            .insert(forward_edge_name.clone(), reverse_edge_name.clone());
+        graph_query
+            .edge_map
+            .insert(reverse_edge_name, forward_edge_name);
+        graph_query
+    }
+
+    fn decode(graph_query: GraphQuery) -> Result<GraphQuery, SerDeError> {
+        GraphQuery::try_from(proto::GraphQuery::from(graph_query))
+    }
+
+    #[test]
+    fn test_query_budget_exceeded_round_trips() {
+        let exceeded = QueryBudgetExceeded {
+            limit: QueryLimit::RoundTrips,
+            nodes_visited: 12,
+            round_trips: 34,
+            elapsed: Duration::from_millis(56),
+        };
+
+        let serialized = exceeded.clone().serialize().expect("serialize");
+        let deserialized = QueryBudgetExceeded::deserialize(serialized).expect("deserialize");
+        assert_eq!(deserialized, exceeded);
+    }
+
+    #[test]
+    fn test_graph_query_decodes() {
+        let decoded = decode(graph_query());
+        assert!(decoded.is_ok(), "{decoded:?}");
+    }
+
+    #[test]
+    fn test_graph_query_rejects_dangling_neighbor_query_ids() {
+        let dangling_query_id = QueryId { value: 3 };
+        let edge_filter = EdgeFilter {
+            edge_name: EdgeName::try_from("children").unwrap(),
+            neighbor_query_id: dangling_query_id,
+        };
+
+        let mut with_edge = graph_query();
+        with_edge.edge_filters.insert(
+            (with_edge.root_query_id, edge_filter.edge_name.clone()),
+            FxHashSet::from_iter([dangling_query_id]),
+        );
+
+        let mut with_or_edge = graph_query();
+        with_or_edge.or_edge_filters.insert(
+            with_or_edge.root_query_id,
+            vec![OrEdgeFilters {
+                edge_filters: vec![edge_filter.clone()],
+            }],
+        );
+
+        let mut with_negated_edge = graph_query();
+        with_negated_edge
+            .negated_edge_filters
+            .insert(with_negated_edge.root_query_id, vec![edge_filter]);
+
+        let mut with_dangling_root = graph_query();
+        with_dangling_root.root_query_id = dangling_query_id;
+
+        for graph_query in [
+            with_edge,
+            with_or_edge,
+            with_negated_edge,
+            with_dangling_root,
+        ] {
+            match decode(graph_query) {
+                Err(SerDeError::InvalidField { .. }) => {}
+                other => panic!("expected an InvalidField error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph_query_rejects_edges_missing_from_edge_map() {
+        let mut graph_query = graph_query();
+        let neighbor_query_id = QueryId { value: 2 };
+        graph_query.negated_edge_filters.insert(
+            graph_query.root_query_id,
+            vec![EdgeFilter {
+                edge_name: EdgeName::try_from("created_file").unwrap(),
+                neighbor_query_id,
+            }],
+        );
+
+        match decode(graph_query) {
+            Err(SerDeError::InvalidField { .. }) => {}
+            other => panic!("expected an InvalidField error, got {other:?}"),
+        }
+    }
+}
@@ -22,20 +22,36 @@ use tonic::transport::{
 use crate::{
     execute_rpc,
     graplinc::grapl::api::graph_query_service::v1beta1::messages::{
+        FindNodesRequest,
+        FindNodesResponse,
+        GetNeighborhoodRequest,
+        GetNeighborhoodResponse,
+        QueryAllGraphsWithUidRequest,
+        QueryAllGraphsWithUidResponse,
         QueryGraphFromUidRequest,
         QueryGraphFromUidResponse,
         QueryGraphWithUidRequest,
         QueryGraphWithUidResponse,
+        TraverseEdgeRequest,
+        TraverseEdgeResponse,
     },
     protobufs::graplinc::grapl::api::graph_query_service::v1beta1::{
         graph_query_service_server::{
             GraphQueryService as GraphQueryServiceProto,
             GraphQueryServiceServer as GraphQueryServiceServerProto,
         },
+        FindNodesRequest as FindNodesRequestProto,
+        FindNodesResponse as FindNodesResponseProto,
+        GetNeighborhoodRequest as GetNeighborhoodRequestProto,
+        GetNeighborhoodResponse as GetNeighborhoodResponseProto,
+        QueryAllGraphsWithUidRequest as QueryAllGraphsWithUidRequestProto,
+        QueryAllGraphsWithUidResponse as QueryAllGraphsWithUidResponseProto,
         QueryGraphFromUidRequest as QueryGraphFromUidRequestProto,
         QueryGraphFromUidResponse as QueryGraphFromUidResponseProto,
         QueryGraphWithUidRequest as QueryGraphWithUidRequestProto,
         QueryGraphWithUidResponse as QueryGraphWithUidResponseProto,
+        TraverseEdgeRequest as TraverseEdgeRequestProto,
+        TraverseEdgeResponse as TraverseEdgeResponseProto,
     },
     protocol::{
         error::ServeError,
@@ -68,6 +84,20 @@ pub trait GraphQueryApi {
         &self,
         request: QueryGraphFromUidRequest,
     ) -> Result<QueryGraphFromUidResponse, Self::Error>;
+    async fn query_all_graphs_with_uid(
+        &self,
+        request: QueryAllGraphsWithUidRequest,
+    ) -> Result<QueryAllGraphsWithUidResponse, Self::Error>;
+    async fn traverse_edge(
+        &self,
+        request: TraverseEdgeRequest,
+    ) -> Result<TraverseEdgeResponse, Self::Error>;
+    async fn get_neighborhood(
+        &self,
+        request: GetNeighborhoodRequest,
+    ) -> Result<GetNeighborhoodResponse, Self::Error>;
+    async fn find_nodes(&self, request: FindNodesRequest)
+        -> Result<FindNodesResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -88,6 +118,34 @@ where
     ) -> Result<tonic::Response<QueryGraphFromUidResponseProto>, tonic::Status> {
         execute_rpc!(self, request, query_graph_from_uid)
     }
+
+    async fn query_all_graphs_with_uid(
+        &self,
+        request: tonic::Request<QueryAllGraphsWithUidRequestProto>,
+    ) -> Result<tonic::Response<QueryAllGraphsWithUidResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, query_all_graphs_with_uid)
+    }
+
+    async fn traverse_edge(
+        &self,
+        request: tonic::Request<TraverseEdgeRequestProto>,
+    ) -> Result<tonic::Response<TraverseEdgeResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, traverse_edge)
+    }
+
+    async fn get_neighborhood(
+        &self,
+        request: tonic::Request<GetNeighborhoodRequestProto>,
+    ) -> Result<tonic::Response<GetNeighborhoodResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, get_neighborhood)
+    }
+
+    async fn find_nodes(
+        &self,
+        request: tonic::Request<FindNodesRequestProto>,
+    ) -> Result<tonic::Response<FindNodesResponseProto>, tonic::Status> {
+        execute_rpc!(self, request, find_nodes)
+    }
 }
 
 /**
@@ -83,4 +83,18 @@ impl GraphSchemaManagerClient {
             RpcConfig::default(),
         )
     }
+
+    pub async fn get_property_schema(
+        &mut self,
+        request: native::GetPropertySchemaRequest,
+    ) -> Result<native::GetPropertySchemaResponse, GraphSchemaManagerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            get_property_schema,
+            proto::GetPropertySchemaRequest,
+            native::GetPropertySchemaResponse,
+            RpcConfig::default(),
+        )
+    }
 }
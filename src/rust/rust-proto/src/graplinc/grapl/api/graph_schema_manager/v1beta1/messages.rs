@@ -4,6 +4,7 @@ use crate::{
     graplinc::grapl::common::v1beta1::types::{
         EdgeName,
         NodeType,
+        PropertyName,
     },
     protobufs::graplinc::grapl::api::graph_schema_manager::v1beta1::{
         DeploySchemaRequest as DeploySchemaRequestProto,
@@ -11,6 +12,9 @@ use crate::{
         EdgeCardinality as EdgeCardinalityProto,
         GetEdgeSchemaRequest as GetEdgeSchemaRequestProto,
         GetEdgeSchemaResponse as GetEdgeSchemaResponseProto,
+        GetPropertySchemaRequest as GetPropertySchemaRequestProto,
+        GetPropertySchemaResponse as GetPropertySchemaResponseProto,
+        PropertyType as PropertyTypeProto,
         SchemaType as SchemaTypeProto,
     },
     serde_impl,
@@ -265,3 +269,148 @@ impl type_url::TypeUrl for EdgeCardinality {
     const TYPE_URL: &'static str =
         "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.EdgeCardinality";
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetPropertySchemaRequest {
+    pub tenant_id: uuid::Uuid,
+    pub node_type: NodeType,
+    pub property_name: PropertyName,
+}
+
+impl TryFrom<GetPropertySchemaRequestProto> for GetPropertySchemaRequest {
+    type Error = SerDeError;
+
+    fn try_from(request_proto: GetPropertySchemaRequestProto) -> Result<Self, Self::Error> {
+        let tenant_id = request_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField(
+                "GetPropertySchemaRequest.tenant_id",
+            ))?
+            .into();
+
+        let node_type = request_proto
+            .node_type
+            .ok_or(SerDeError::MissingField(
+                "GetPropertySchemaRequest.node_type",
+            ))?
+            .try_into()?;
+
+        let property_name = request_proto
+            .property_name
+            .ok_or(SerDeError::MissingField(
+                "GetPropertySchemaRequest.property_name",
+            ))?
+            .try_into()?;
+
+        Ok(GetPropertySchemaRequest {
+            tenant_id,
+            node_type,
+            property_name,
+        })
+    }
+}
+
+impl From<GetPropertySchemaRequest> for GetPropertySchemaRequestProto {
+    fn from(request: GetPropertySchemaRequest) -> Self {
+        GetPropertySchemaRequestProto {
+            tenant_id: Some(request.tenant_id.into()),
+            node_type: Some(request.node_type.into()),
+            property_name: Some(request.property_name.into()),
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetPropertySchemaRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetPropertySchemaRequest";
+}
+
+impl serde_impl::ProtobufSerializable for GetPropertySchemaRequest {
+    type ProtobufMessage = GetPropertySchemaRequestProto;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetPropertySchemaResponse {
+    pub property_type: PropertyType,
+}
+
+impl TryFrom<GetPropertySchemaResponseProto> for GetPropertySchemaResponse {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: GetPropertySchemaResponseProto) -> Result<Self, Self::Error> {
+        let property_type = response_proto.property_type().try_into()?;
+
+        Ok(GetPropertySchemaResponse { property_type })
+    }
+}
+
+impl From<GetPropertySchemaResponse> for GetPropertySchemaResponseProto {
+    fn from(value: GetPropertySchemaResponse) -> Self {
+        let property_type: PropertyTypeProto = value.property_type.into();
+        GetPropertySchemaResponseProto {
+            property_type: property_type as i32,
+        }
+    }
+}
+
+impl type_url::TypeUrl for GetPropertySchemaResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.GetPropertySchemaResponse";
+}
+
+impl serde_impl::ProtobufSerializable for GetPropertySchemaResponse {
+    type ProtobufMessage = GetPropertySchemaResponseProto;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    ImmutableString,
+    ImmutableI64,
+    MaxI64,
+    MinI64,
+    ImmutableU64,
+    MaxU64,
+    MinU64,
+    MutableString,
+    MutableF64,
+}
+
+impl TryFrom<PropertyTypeProto> for PropertyType {
+    type Error = SerDeError;
+
+    fn try_from(response_proto: PropertyTypeProto) -> Result<Self, Self::Error> {
+        match response_proto {
+            PropertyTypeProto::ImmutableString => Ok(PropertyType::ImmutableString),
+            PropertyTypeProto::ImmutableI64 => Ok(PropertyType::ImmutableI64),
+            PropertyTypeProto::MaxI64 => Ok(PropertyType::MaxI64),
+            PropertyTypeProto::MinI64 => Ok(PropertyType::MinI64),
+            PropertyTypeProto::ImmutableU64 => Ok(PropertyType::ImmutableU64),
+            PropertyTypeProto::MaxU64 => Ok(PropertyType::MaxU64),
+            PropertyTypeProto::MinU64 => Ok(PropertyType::MinU64),
+            PropertyTypeProto::MutableString => Ok(PropertyType::MutableString),
+            PropertyTypeProto::MutableF64 => Ok(PropertyType::MutableF64),
+            PropertyTypeProto::Unspecified => Err(SerDeError::UnknownVariant("PropertyType")),
+        }
+    }
+}
+
+impl From<PropertyType> for PropertyTypeProto {
+    fn from(response: PropertyType) -> Self {
+        match response {
+            PropertyType::ImmutableString => PropertyTypeProto::ImmutableString,
+            PropertyType::ImmutableI64 => PropertyTypeProto::ImmutableI64,
+            PropertyType::MaxI64 => PropertyTypeProto::MaxI64,
+            PropertyType::MinI64 => PropertyTypeProto::MinI64,
+            PropertyType::ImmutableU64 => PropertyTypeProto::ImmutableU64,
+            PropertyType::MaxU64 => PropertyTypeProto::MaxU64,
+            PropertyType::MinU64 => PropertyTypeProto::MinU64,
+            PropertyType::MutableString => PropertyTypeProto::MutableString,
+            PropertyType::MutableF64 => PropertyTypeProto::MutableF64,
+        }
+    }
+}
+
+impl type_url::TypeUrl for PropertyType {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.graph_schema_manager.v1beta1.PropertyType";
+}
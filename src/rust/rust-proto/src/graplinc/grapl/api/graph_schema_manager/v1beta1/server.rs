@@ -24,6 +24,8 @@ use crate::{
         DeploySchemaResponse,
         GetEdgeSchemaRequest,
         GetEdgeSchemaResponse,
+        GetPropertySchemaRequest,
+        GetPropertySchemaResponse,
     },
     protobufs::graplinc::grapl::api::graph_schema_manager::{
         v1beta1 as proto,
@@ -64,6 +66,11 @@ pub trait GraphSchemaManagerApi {
         &self,
         request: GetEdgeSchemaRequest,
     ) -> Result<GetEdgeSchemaResponse, Self::Error>;
+
+    async fn get_property_schema(
+        &self,
+        request: GetPropertySchemaRequest,
+    ) -> Result<GetPropertySchemaResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -84,6 +91,13 @@ where
     ) -> Result<tonic::Response<proto::GetEdgeSchemaResponse>, tonic::Status> {
         execute_rpc!(self, request, get_edge_schema)
     }
+
+    async fn get_property_schema(
+        &self,
+        request: tonic::Request<proto::GetPropertySchemaRequest>,
+    ) -> Result<tonic::Response<proto::GetPropertySchemaResponse>, tonic::Status> {
+        execute_rpc!(self, request, get_property_schema)
+    }
 }
 
 /**
@@ -1133,6 +1133,87 @@ impl ProtobufSerializable for TearDownPluginResponse {
     type ProtobufMessage = proto::TearDownPluginResponse;
 }
 
+//
+// DeletePluginsForTenantRequest
+//
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletePluginsForTenantRequest {
+    tenant_id: uuid::Uuid,
+}
+
+impl DeletePluginsForTenantRequest {
+    pub fn new(tenant_id: uuid::Uuid) -> Self {
+        Self { tenant_id }
+    }
+
+    pub fn tenant_id(&self) -> uuid::Uuid {
+        self.tenant_id
+    }
+}
+
+impl type_url::TypeUrl for DeletePluginsForTenantRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_registry.v1beta1.DeletePluginsForTenantRequest";
+}
+
+impl TryFrom<proto::DeletePluginsForTenantRequest> for DeletePluginsForTenantRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::DeletePluginsForTenantRequest) -> Result<Self, Self::Error> {
+        let tenant_id = value
+            .tenant_id
+            .ok_or(SerDeError::MissingField(
+                "DeletePluginsForTenantRequest.tenant_id",
+            ))?
+            .into();
+
+        Ok(Self { tenant_id })
+    }
+}
+
+impl From<DeletePluginsForTenantRequest> for proto::DeletePluginsForTenantRequest {
+    fn from(value: DeletePluginsForTenantRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for DeletePluginsForTenantRequest {
+    type ProtobufMessage = proto::DeletePluginsForTenantRequest;
+}
+
+//
+// DeletePluginsForTenantResponse
+//
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletePluginsForTenantResponse {}
+
+impl type_url::TypeUrl for DeletePluginsForTenantResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_registry.v1beta1.DeletePluginsForTenantResponse";
+}
+
+impl TryFrom<proto::DeletePluginsForTenantResponse> for DeletePluginsForTenantResponse {
+    type Error = SerDeError;
+
+    fn try_from(_value: proto::DeletePluginsForTenantResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<DeletePluginsForTenantResponse> for proto::DeletePluginsForTenantResponse {
+    fn from(_: DeletePluginsForTenantResponse) -> Self {
+        Self {}
+    }
+}
+
+impl ProtobufSerializable for DeletePluginsForTenantResponse {
+    type ProtobufMessage = proto::DeletePluginsForTenantResponse;
+}
+
 //
 // GetPluginHealthRequest
 //
@@ -177,6 +177,22 @@ impl PluginRegistryServiceClient {
         )
     }
 
+    /// turn off and delete every plugin belonging to a tenant
+    #[instrument(skip(self, request), err)]
+    pub async fn delete_plugins_for_tenant(
+        &mut self,
+        request: native::DeletePluginsForTenantRequest,
+    ) -> Result<native::DeletePluginsForTenantResponse, GrpcClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            delete_plugins_for_tenant,
+            proto::DeletePluginsForTenantRequest,
+            native::DeletePluginsForTenantResponse,
+            RpcConfig::default(),
+        )
+    }
+
     #[instrument(skip(self, request), err)]
     pub async fn get_plugin_health(
         &mut self,
@@ -28,6 +28,8 @@ use crate::{
     graplinc::grapl::api::plugin_registry::v1beta1::{
         CreatePluginRequest,
         CreatePluginResponse,
+        DeletePluginsForTenantRequest,
+        DeletePluginsForTenantResponse,
         DeployPluginRequest,
         DeployPluginResponse,
         GetAnalyzersForTenantRequest,
@@ -95,6 +97,11 @@ pub trait PluginRegistryApi {
         request: TearDownPluginRequest,
     ) -> Result<TearDownPluginResponse, Self::Error>;
 
+    async fn delete_plugins_for_tenant(
+        &self,
+        request: DeletePluginsForTenantRequest,
+    ) -> Result<DeletePluginsForTenantResponse, Self::Error>;
+
     async fn get_plugin_health(
         &self,
         request: GetPluginHealthRequest,
@@ -199,6 +206,13 @@ where
         execute_rpc!(self, request, tear_down_plugin)
     }
 
+    async fn delete_plugins_for_tenant(
+        &self,
+        request: Request<proto::DeletePluginsForTenantRequest>,
+    ) -> Result<Response<proto::DeletePluginsForTenantResponse>, tonic::Status> {
+        execute_rpc!(self, request, delete_plugins_for_tenant)
+    }
+
     async fn get_generators_for_event_source(
         &self,
         request: Request<proto::GetGeneratorsForEventSourceRequest>,
@@ -35,7 +35,8 @@ pub struct AnalyzerServiceClient {
 #[async_trait::async_trait]
 impl Connectable for AnalyzerServiceClient {
     type Config = AnalyzerClientConfig;
-    const SERVICE_NAME: &'static str = "graplinc.grapl.api.plugin_registry.v1beta1.AnalyzerService";
+    const SERVICE_NAME: &'static str =
+        "graplinc.grapl.api.plugin_sdk.analyzers.v1beta1.AnalyzerService";
 
     #[tracing::instrument(err)]
     async fn connect_with_endpoint(endpoint: Endpoint) -> Result<Self, ConnectError> {
@@ -20,7 +20,10 @@ pub use crate::graplinc::grapl::api::plugin_work_queue::{
     },
 };
 use crate::{
-    graplinc::grapl::api::graph::v1beta1::GraphDescription,
+    graplinc::grapl::api::{
+        graph::v1beta1::GraphDescription,
+        plugin_sdk::analyzers::v1beta1::messages::ExecutionHit,
+    },
     protobufs::graplinc::grapl::api::plugin_work_queue::v1beta1 as proto,
     serde_impl::ProtobufSerializable,
     type_url,
@@ -255,6 +258,7 @@ impl type_url::TypeUrl for AcknowledgeGeneratorResponse {
 pub struct AcknowledgeAnalyzerRequest {
     request_id: i64,
     success: bool,
+    execution_hits: Vec<ExecutionHit>,
     plugin_id: Uuid,
     tenant_id: Uuid,
     trace_id: Uuid,
@@ -265,6 +269,7 @@ impl AcknowledgeAnalyzerRequest {
     pub fn new(
         request_id: i64,
         success: bool,
+        execution_hits: Vec<ExecutionHit>,
         plugin_id: Uuid,
         tenant_id: Uuid,
         trace_id: Uuid,
@@ -273,6 +278,7 @@ impl AcknowledgeAnalyzerRequest {
         Self {
             request_id,
             success,
+            execution_hits,
             plugin_id,
             tenant_id,
             trace_id,
@@ -288,6 +294,10 @@ impl AcknowledgeAnalyzerRequest {
         self.success
     }
 
+    pub fn execution_hits(self) -> Vec<ExecutionHit> {
+        self.execution_hits
+    }
+
     pub fn plugin_id(&self) -> Uuid {
         self.plugin_id
     }
@@ -311,6 +321,11 @@ impl TryFrom<proto::AcknowledgeAnalyzerRequest> for AcknowledgeAnalyzerRequest {
     fn try_from(value: proto::AcknowledgeAnalyzerRequest) -> Result<Self, Self::Error> {
         let request_id = value.request_id;
         let success = value.success;
+        let execution_hits = value
+            .execution_hits
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, SerDeError>>()?;
         let plugin_id = value
             .plugin_id
             .ok_or(Self::Error::MissingField("plugin_id"))?
@@ -331,6 +346,7 @@ impl TryFrom<proto::AcknowledgeAnalyzerRequest> for AcknowledgeAnalyzerRequest {
         Ok(Self {
             request_id,
             success,
+            execution_hits,
             plugin_id,
             tenant_id,
             trace_id,
@@ -344,6 +360,7 @@ impl From<AcknowledgeAnalyzerRequest> for proto::AcknowledgeAnalyzerRequest {
         Self {
             request_id: value.request_id,
             success: value.success,
+            execution_hits: value.execution_hits.into_iter().map(Into::into).collect(),
             plugin_id: Some(value.plugin_id.into()),
             tenant_id: Some(value.tenant_id.into()),
             trace_id: Some(value.trace_id.into()),
@@ -387,6 +404,66 @@ impl type_url::TypeUrl for AcknowledgeAnalyzerResponse {
         "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.AcknowledgeAnalyzerResponse";
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteExecutionsForTenantRequest {
+    pub tenant_id: Uuid,
+}
+
+impl TryFrom<proto::DeleteExecutionsForTenantRequest> for DeleteExecutionsForTenantRequest {
+    type Error = SerDeError;
+
+    fn try_from(value: proto::DeleteExecutionsForTenantRequest) -> Result<Self, Self::Error> {
+        let tenant_id = value
+            .tenant_id
+            .ok_or(Self::Error::MissingField("tenant_id"))?
+            .into();
+        Ok(Self { tenant_id })
+    }
+}
+
+impl From<DeleteExecutionsForTenantRequest> for proto::DeleteExecutionsForTenantRequest {
+    fn from(value: DeleteExecutionsForTenantRequest) -> Self {
+        Self {
+            tenant_id: Some(value.tenant_id.into()),
+        }
+    }
+}
+
+impl ProtobufSerializable for DeleteExecutionsForTenantRequest {
+    type ProtobufMessage = proto::DeleteExecutionsForTenantRequest;
+}
+
+impl type_url::TypeUrl for DeleteExecutionsForTenantRequest {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.DeleteExecutionsForTenantRequest";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteExecutionsForTenantResponse {}
+
+impl TryFrom<proto::DeleteExecutionsForTenantResponse> for DeleteExecutionsForTenantResponse {
+    type Error = SerDeError;
+
+    fn try_from(_value: proto::DeleteExecutionsForTenantResponse) -> Result<Self, Self::Error> {
+        Ok(Self {})
+    }
+}
+
+impl From<DeleteExecutionsForTenantResponse> for proto::DeleteExecutionsForTenantResponse {
+    fn from(_value: DeleteExecutionsForTenantResponse) -> Self {
+        Self {}
+    }
+}
+
+impl ProtobufSerializable for DeleteExecutionsForTenantResponse {
+    type ProtobufMessage = proto::DeleteExecutionsForTenantResponse;
+}
+
+impl type_url::TypeUrl for DeleteExecutionsForTenantResponse {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.api.plugin_work_queue.v1beta1.DeleteExecutionsForTenantResponse";
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetExecuteAnalyzerRequest {
     pub plugin_id: Uuid,
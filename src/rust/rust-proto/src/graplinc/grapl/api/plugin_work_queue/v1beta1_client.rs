@@ -149,4 +149,20 @@ impl PluginWorkQueueServiceClient {
             RpcConfig::default(),
         )
     }
+
+    /// Deletes all execution jobs belonging to a tenant
+    #[tracing::instrument(skip(self, request), err)]
+    pub async fn delete_executions_for_tenant(
+        &mut self,
+        request: native::DeleteExecutionsForTenantRequest,
+    ) -> Result<native::DeleteExecutionsForTenantResponse, PluginWorkQueueServiceClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            delete_executions_for_tenant,
+            proto::DeleteExecutionsForTenantRequest,
+            native::DeleteExecutionsForTenantResponse,
+            RpcConfig::default(),
+        )
+    }
 }
@@ -69,6 +69,11 @@ pub trait PluginWorkQueueApi {
         &self,
         request: native::AcknowledgeAnalyzerRequest,
     ) -> Result<native::AcknowledgeAnalyzerResponse, Self::Error>;
+
+    async fn delete_executions_for_tenant(
+        &self,
+        request: native::DeleteExecutionsForTenantRequest,
+    ) -> Result<native::DeleteExecutionsForTenantResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -117,6 +122,13 @@ where
     ) -> Result<tonic::Response<proto::AcknowledgeAnalyzerResponse>, tonic::Status> {
         execute_rpc!(self, request, acknowledge_analyzer)
     }
+
+    async fn delete_executions_for_tenant(
+        &self,
+        request: tonic::Request<proto::DeleteExecutionsForTenantRequest>,
+    ) -> Result<tonic::Response<proto::DeleteExecutionsForTenantResponse>, tonic::Status> {
+        execute_rpc!(self, request, delete_executions_for_tenant)
+    }
 }
 
 /**
@@ -11,7 +11,9 @@ use crate::{
     client_macros::RpcConfig,
     create_proto_client,
     execute_client_rpc,
-    graplinc::grapl::api::scylla_provisioner::v1beta1::messages::{self as native,},
+    graplinc::grapl::api::scylla_provisioner::v1beta1::messages::{
+        self as native,
+    },
     protobufs::graplinc::grapl::api::scylla_provisioner::v1beta1::{
         self as proto,
         scylla_provisioner_service_client::ScyllaProvisionerServiceClient,
@@ -68,4 +70,32 @@ impl ScyllaProvisionerClient {
             RpcConfig::default(),
         )
     }
+
+    pub async fn deprovision_graph_for_tenant(
+        &mut self,
+        request: native::DeprovisionGraphForTenantRequest,
+    ) -> Result<native::DeprovisionGraphForTenantResponse, ScyllaProvisionerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            deprovision_graph_for_tenant,
+            proto::DeprovisionGraphForTenantRequest,
+            native::DeprovisionGraphForTenantResponse,
+            RpcConfig::default(),
+        )
+    }
+
+    pub async fn alter_replication_for_tenant(
+        &mut self,
+        request: native::AlterReplicationForTenantRequest,
+    ) -> Result<native::AlterReplicationForTenantResponse, ScyllaProvisionerClientError> {
+        execute_client_rpc!(
+            self,
+            request,
+            alter_replication_for_tenant,
+            proto::AlterReplicationForTenantRequest,
+            native::AlterReplicationForTenantResponse,
+            RpcConfig::default(),
+        )
+    }
 }
@@ -1,7 +1,17 @@
+use std::collections::HashMap;
+
 use crate::{
     protobufs::graplinc::grapl::api::scylla_provisioner::v1beta1::{
+        replication_strategy::Strategy as StrategyProto,
+        AlterReplicationForTenantRequest as AlterReplicationForTenantRequestProto,
+        AlterReplicationForTenantResponse as AlterReplicationForTenantResponseProto,
+        DeprovisionGraphForTenantRequest as DeprovisionGraphForTenantRequestProto,
+        DeprovisionGraphForTenantResponse as DeprovisionGraphForTenantResponseProto,
+        NetworkTopologyStrategy as NetworkTopologyStrategyProto,
         ProvisionGraphForTenantRequest as ProvisionGraphForTenantRequestProto,
         ProvisionGraphForTenantResponse as ProvisionGraphForTenantResponseProto,
+        ReplicationStrategy as ReplicationStrategyProto,
+        SimpleStrategy as SimpleStrategyProto,
     },
     SerDeError,
 };
@@ -50,3 +60,146 @@ impl From<ProvisionGraphForTenantResponse> for ProvisionGraphForTenantResponsePr
         Self {}
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct DeprovisionGraphForTenantRequest {
+    pub tenant_id: uuid::Uuid,
+}
+
+impl TryFrom<DeprovisionGraphForTenantRequestProto> for DeprovisionGraphForTenantRequest {
+    type Error = SerDeError;
+
+    fn try_from(request: DeprovisionGraphForTenantRequestProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: request
+                .tenant_id
+                .ok_or_else(|| SerDeError::MissingField("tenant_id"))?
+                .into(),
+        })
+    }
+}
+
+impl From<DeprovisionGraphForTenantRequest> for DeprovisionGraphForTenantRequestProto {
+    fn from(request: DeprovisionGraphForTenantRequest) -> Self {
+        Self {
+            tenant_id: Some(request.tenant_id.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeprovisionGraphForTenantResponse {}
+
+impl TryFrom<DeprovisionGraphForTenantResponseProto> for DeprovisionGraphForTenantResponse {
+    type Error = SerDeError;
+
+    fn try_from(request: DeprovisionGraphForTenantResponseProto) -> Result<Self, Self::Error> {
+        let DeprovisionGraphForTenantResponseProto {} = request;
+        Ok(Self {})
+    }
+}
+
+impl From<DeprovisionGraphForTenantResponse> for DeprovisionGraphForTenantResponseProto {
+    fn from(request: DeprovisionGraphForTenantResponse) -> Self {
+        let DeprovisionGraphForTenantResponse {} = request;
+        Self {}
+    }
+}
+
+/// How a tenant's keyspace is replicated across the Scylla cluster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationStrategy {
+    Simple {
+        replication_factor: u32,
+    },
+    NetworkTopology {
+        datacenter_replication_factors: HashMap<String, u32>,
+    },
+}
+
+impl TryFrom<ReplicationStrategyProto> for ReplicationStrategy {
+    type Error = SerDeError;
+
+    fn try_from(strategy: ReplicationStrategyProto) -> Result<Self, Self::Error> {
+        match strategy.strategy {
+            Some(StrategyProto::SimpleStrategy(SimpleStrategyProto { replication_factor })) => {
+                Ok(Self::Simple { replication_factor })
+            }
+            Some(StrategyProto::NetworkTopologyStrategy(NetworkTopologyStrategyProto {
+                datacenter_replication_factors,
+            })) => Ok(Self::NetworkTopology {
+                datacenter_replication_factors,
+            }),
+            None => Err(SerDeError::MissingField("strategy")),
+        }
+    }
+}
+
+impl From<ReplicationStrategy> for ReplicationStrategyProto {
+    fn from(strategy: ReplicationStrategy) -> Self {
+        let strategy = match strategy {
+            ReplicationStrategy::Simple { replication_factor } => {
+                StrategyProto::SimpleStrategy(SimpleStrategyProto { replication_factor })
+            }
+            ReplicationStrategy::NetworkTopology {
+                datacenter_replication_factors,
+            } => StrategyProto::NetworkTopologyStrategy(NetworkTopologyStrategyProto {
+                datacenter_replication_factors,
+            }),
+        };
+        Self {
+            strategy: Some(strategy),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlterReplicationForTenantRequest {
+    pub tenant_id: uuid::Uuid,
+    pub replication_strategy: ReplicationStrategy,
+}
+
+impl TryFrom<AlterReplicationForTenantRequestProto> for AlterReplicationForTenantRequest {
+    type Error = SerDeError;
+
+    fn try_from(request: AlterReplicationForTenantRequestProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tenant_id: request
+                .tenant_id
+                .ok_or_else(|| SerDeError::MissingField("tenant_id"))?
+                .into(),
+            replication_strategy: request
+                .replication_strategy
+                .ok_or_else(|| SerDeError::MissingField("replication_strategy"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<AlterReplicationForTenantRequest> for AlterReplicationForTenantRequestProto {
+    fn from(request: AlterReplicationForTenantRequest) -> Self {
+        Self {
+            tenant_id: Some(request.tenant_id.into()),
+            replication_strategy: Some(request.replication_strategy.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AlterReplicationForTenantResponse {}
+
+impl TryFrom<AlterReplicationForTenantResponseProto> for AlterReplicationForTenantResponse {
+    type Error = SerDeError;
+
+    fn try_from(request: AlterReplicationForTenantResponseProto) -> Result<Self, Self::Error> {
+        let AlterReplicationForTenantResponseProto {} = request;
+        Ok(Self {})
+    }
+}
+
+impl From<AlterReplicationForTenantResponse> for AlterReplicationForTenantResponseProto {
+    fn from(request: AlterReplicationForTenantResponse) -> Self {
+        let AlterReplicationForTenantResponse {} = request;
+        Self {}
+    }
+}
@@ -52,6 +52,16 @@ pub trait ScyllaProvisionerApi {
         &self,
         request: native::ProvisionGraphForTenantRequest,
     ) -> Result<native::ProvisionGraphForTenantResponse, Self::Error>;
+
+    async fn deprovision_graph_for_tenant(
+        &self,
+        request: native::DeprovisionGraphForTenantRequest,
+    ) -> Result<native::DeprovisionGraphForTenantResponse, Self::Error>;
+
+    async fn alter_replication_for_tenant(
+        &self,
+        request: native::AlterReplicationForTenantRequest,
+    ) -> Result<native::AlterReplicationForTenantResponse, Self::Error>;
 }
 
 #[tonic::async_trait]
@@ -66,6 +76,22 @@ where
     ) -> Result<Response<proto::ProvisionGraphForTenantResponse>, tonic::Status> {
         execute_rpc!(self, request, provision_graph_for_tenant)
     }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn deprovision_graph_for_tenant(
+        &self,
+        request: Request<proto::DeprovisionGraphForTenantRequest>,
+    ) -> Result<Response<proto::DeprovisionGraphForTenantResponse>, tonic::Status> {
+        execute_rpc!(self, request, deprovision_graph_for_tenant)
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
+    async fn alter_replication_for_tenant(
+        &self,
+        request: Request<proto::AlterReplicationForTenantRequest>,
+    ) -> Result<Response<proto::AlterReplicationForTenantResponse>, tonic::Status> {
+        execute_rpc!(self, request, alter_replication_for_tenant)
+    }
 }
 
 /**
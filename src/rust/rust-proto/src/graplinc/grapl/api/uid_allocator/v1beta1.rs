@@ -26,6 +26,8 @@ pub mod server {
             AllocateIdsResponse,
             CreateTenantKeyspaceRequest,
             CreateTenantKeyspaceResponse,
+            DeleteTenantKeyspaceRequest,
+            DeleteTenantKeyspaceResponse,
         },
         protobufs::graplinc::grapl::api::uid_allocator::v1beta1::{
             uid_allocator_service_server::{
@@ -36,6 +38,8 @@ pub mod server {
             AllocateIdsResponse as AllocateIdsResponseProto,
             CreateTenantKeyspaceRequest as CreateTenantKeyspaceRequestProto,
             CreateTenantKeyspaceResponse as CreateTenantKeyspaceResponseProto,
+            DeleteTenantKeyspaceRequest as DeleteTenantKeyspaceRequestProto,
+            DeleteTenantKeyspaceResponse as DeleteTenantKeyspaceResponseProto,
         },
         protocol::{
             error::ServeError,
@@ -65,6 +69,13 @@ pub mod server {
             &self,
             request: CreateTenantKeyspaceRequest,
         ) -> Result<CreateTenantKeyspaceResponse, Self::Error>;
+
+        /// Removes the tenant's allocation state. Succeeds if the tenant was
+        /// never set up or has already been removed.
+        async fn delete_tenant_keyspace(
+            &self,
+            request: DeleteTenantKeyspaceRequest,
+        ) -> Result<DeleteTenantKeyspaceResponse, Self::Error>;
     }
 
     #[async_trait::async_trait]
@@ -85,6 +96,13 @@ pub mod server {
         ) -> Result<Response<CreateTenantKeyspaceResponseProto>, tonic::Status> {
             execute_rpc!(self, request, create_tenant_keyspace)
         }
+
+        async fn delete_tenant_keyspace(
+            &self,
+            request: Request<DeleteTenantKeyspaceRequestProto>,
+        ) -> Result<Response<DeleteTenantKeyspaceResponseProto>, tonic::Status> {
+            execute_rpc!(self, request, delete_tenant_keyspace)
+        }
     }
 
     /**
@@ -270,6 +288,20 @@ pub mod client {
                 RpcConfig::default(),
             )
         }
+
+        pub async fn delete_tenant_keyspace(
+            &mut self,
+            request: native::DeleteTenantKeyspaceRequest,
+        ) -> Result<native::DeleteTenantKeyspaceResponse, UidAllocatorServiceClientError> {
+            execute_client_rpc!(
+                self,
+                request,
+                delete_tenant_keyspace,
+                proto::DeleteTenantKeyspaceRequest,
+                native::DeleteTenantKeyspaceResponse,
+                RpcConfig::default(),
+            )
+        }
     }
 }
 
@@ -281,6 +313,8 @@ pub mod messages {
             Allocation as AllocationProto,
             CreateTenantKeyspaceRequest as CreateTenantKeyspaceRequestProto,
             CreateTenantKeyspaceResponse as CreateTenantKeyspaceResponseProto,
+            DeleteTenantKeyspaceRequest as DeleteTenantKeyspaceRequestProto,
+            DeleteTenantKeyspaceResponse as DeleteTenantKeyspaceResponseProto,
         },
         serde_impl,
         type_url,
@@ -327,6 +361,46 @@ pub mod messages {
         }
     }
 
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct DeleteTenantKeyspaceRequest {
+        pub tenant_id: uuid::Uuid,
+    }
+
+    impl TryFrom<DeleteTenantKeyspaceRequestProto> for DeleteTenantKeyspaceRequest {
+        type Error = SerDeError;
+        fn try_from(value: DeleteTenantKeyspaceRequestProto) -> Result<Self, Self::Error> {
+            Ok(Self {
+                tenant_id: value
+                    .tenant_id
+                    .ok_or_else(|| SerDeError::MissingField("value.tenant_id"))?
+                    .into(),
+            })
+        }
+    }
+
+    impl From<DeleteTenantKeyspaceRequest> for DeleteTenantKeyspaceRequestProto {
+        fn from(value: DeleteTenantKeyspaceRequest) -> Self {
+            Self {
+                tenant_id: Some(value.tenant_id.into()),
+            }
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct DeleteTenantKeyspaceResponse {}
+
+    impl From<DeleteTenantKeyspaceResponseProto> for DeleteTenantKeyspaceResponse {
+        fn from(_value: DeleteTenantKeyspaceResponseProto) -> Self {
+            Self {}
+        }
+    }
+
+    impl From<DeleteTenantKeyspaceResponse> for DeleteTenantKeyspaceResponseProto {
+        fn from(_value: DeleteTenantKeyspaceResponse) -> Self {
+            Self {}
+        }
+    }
+
     #[derive(Copy, Clone, Debug, PartialEq)]
     pub struct Allocation {
         pub start: u64,
@@ -1,4 +1,7 @@
-use bytes::Bytes;
+use bytes::{
+    Buf,
+    Bytes,
+};
 
 use crate::{
     graplinc::common::v1beta1::{
@@ -9,6 +12,7 @@ use crate::{
         google::protobuf::Any as AnyProto,
         graplinc::grapl::pipeline::v1beta1::{
             Envelope as EnvelopeProto,
+            FailedEnvelope as FailedEnvelopeProto,
             RawLog as RawLogProto,
         },
     },
@@ -166,6 +170,181 @@ where
     type ProtobufMessage = EnvelopeProto;
 }
 
+//
+// FailedEnvelope
+//
+
+/// An envelope which could not be processed, along with the reason why and
+/// the topic it was consumed from. Only envelopes which were deserialized can
+/// be dead-lettered, since one is built from an `Envelope<T>`. The envelope
+/// itself is kept serialized so that it can be replayed without knowing its
+/// inner message type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FailedEnvelope {
+    tenant_id: Uuid,
+    trace_id: Uuid,
+    event_source_id: Uuid,
+    retry_count: u32,
+    origin_topic: String,
+    error_reason: String,
+    failed_time: SystemTime,
+    envelope: Bytes,
+}
+
+impl FailedEnvelope {
+    pub fn new<T>(
+        envelope: Envelope<T>,
+        origin_topic: String,
+        error_reason: String,
+    ) -> Result<Self, SerDeError>
+    where
+        T: SerDe,
+    {
+        Ok(FailedEnvelope {
+            tenant_id: envelope.tenant_id(),
+            trace_id: envelope.trace_id(),
+            event_source_id: envelope.event_source_id(),
+            retry_count: envelope.retry_count(),
+            origin_topic,
+            error_reason,
+            failed_time: SystemTime::now(),
+            envelope: envelope.serialize()?,
+        })
+    }
+
+    pub fn tenant_id(&self) -> Uuid {
+        self.tenant_id
+    }
+
+    pub fn trace_id(&self) -> Uuid {
+        self.trace_id
+    }
+
+    pub fn event_source_id(&self) -> Uuid {
+        self.event_source_id
+    }
+
+    pub fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
+    pub fn origin_topic(&self) -> &str {
+        &self.origin_topic
+    }
+
+    pub fn error_reason(&self) -> &str {
+        &self.error_reason
+    }
+
+    pub fn failed_time(&self) -> SystemTime {
+        self.failed_time
+    }
+
+    /// The serialized Envelope which failed
+    pub fn envelope(self) -> Bytes {
+        self.envelope
+    }
+}
+
+impl TryFrom<FailedEnvelopeProto> for FailedEnvelope {
+    type Error = SerDeError;
+
+    fn try_from(failed_envelope_proto: FailedEnvelopeProto) -> Result<Self, Self::Error> {
+        let tenant_id = failed_envelope_proto
+            .tenant_id
+            .ok_or(SerDeError::MissingField("tenant_id"))?;
+
+        let trace_id = failed_envelope_proto
+            .trace_id
+            .ok_or(SerDeError::MissingField("trace_id"))?;
+
+        let event_source_id = failed_envelope_proto
+            .event_source_id
+            .ok_or(SerDeError::MissingField("event_source_id"))?;
+
+        let failed_time = failed_envelope_proto
+            .failed_time
+            .ok_or(SerDeError::MissingField("failed_time"))?;
+
+        Ok(FailedEnvelope {
+            tenant_id: tenant_id.into(),
+            trace_id: trace_id.into(),
+            event_source_id: event_source_id.into(),
+            retry_count: failed_envelope_proto.retry_count,
+            origin_topic: failed_envelope_proto.origin_topic,
+            error_reason: failed_envelope_proto.error_reason,
+            failed_time: failed_time.try_into()?,
+            envelope: failed_envelope_proto.envelope,
+        })
+    }
+}
+
+impl TryFrom<FailedEnvelope> for FailedEnvelopeProto {
+    type Error = SerDeError;
+
+    fn try_from(failed_envelope: FailedEnvelope) -> Result<Self, Self::Error> {
+        Ok(FailedEnvelopeProto {
+            tenant_id: Some(failed_envelope.tenant_id.into()),
+            trace_id: Some(failed_envelope.trace_id.into()),
+            event_source_id: Some(failed_envelope.event_source_id.into()),
+            retry_count: failed_envelope.retry_count,
+            origin_topic: failed_envelope.origin_topic,
+            error_reason: failed_envelope.error_reason,
+            failed_time: Some(failed_envelope.failed_time.try_into()?),
+            envelope: failed_envelope.envelope,
+        })
+    }
+}
+
+impl type_url::TypeUrl for FailedEnvelope {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.pipeline.v1beta1.FailedEnvelope";
+}
+
+impl serde_impl::ProtobufSerializable for FailedEnvelope {
+    type ProtobufMessage = FailedEnvelopeProto;
+}
+
+//
+// OpaqueMessage
+//
+
+/// An envelope's inner message, left serialized so that the envelope can be
+/// decoded without knowing its inner message type, e.g. to inspect the
+/// envelopes on a retry topic. The inner message's type url isn't kept, so an
+/// `Envelope<OpaqueMessage>` should only ever be decoded, never re-serialized.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OpaqueMessage {
+    value: Bytes,
+}
+
+impl OpaqueMessage {
+    pub fn value(self) -> Bytes {
+        self.value
+    }
+}
+
+impl type_url::TypeUrl for OpaqueMessage {
+    const TYPE_URL: &'static str =
+        "graplsecurity.com/graplinc.grapl.pipeline.v1beta1.OpaqueMessage";
+}
+
+impl SerDe for OpaqueMessage {
+    fn serialize(self) -> Result<Bytes, SerDeError> {
+        Ok(self.value)
+    }
+
+    fn deserialize<B>(mut buf: B) -> Result<Self, SerDeError>
+    where
+        B: Buf,
+        Self: Sized,
+    {
+        Ok(OpaqueMessage {
+            value: buf.copy_to_bytes(buf.remaining()),
+        })
+    }
+}
+
 //
 // RawLog
 //
@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use tonic::{
     Code as GrpcCode,
     Status as GrpcStatus,
@@ -28,6 +29,9 @@ pub enum Code {
     /// The caller does not have permission to execute the specified operation.
     PermissionDenied,
 
+    /// Some resource has been exhausted.
+    ResourceExhausted,
+
     /// The system is not in a state required for the operation's execution.
     FailedPrecondition,
 
@@ -67,6 +71,7 @@ impl Code {
             Code::PermissionDenied => {
                 "The caller does not have permission to execute the specified operation"
             }
+            Code::ResourceExhausted => "Some resource has been exhausted",
             Code::FailedPrecondition => {
                 "The system is not in a state required for the operation's execution"
             }
@@ -93,6 +98,7 @@ impl From<Code> for GrpcCode {
             Code::NotFound => GrpcCode::NotFound,
             Code::AlreadyExists => GrpcCode::AlreadyExists,
             Code::PermissionDenied => GrpcCode::PermissionDenied,
+            Code::ResourceExhausted => GrpcCode::ResourceExhausted,
             Code::FailedPrecondition => GrpcCode::FailedPrecondition,
             Code::Unimplemented => GrpcCode::Unimplemented,
             Code::Internal => GrpcCode::Internal,
@@ -113,7 +119,7 @@ impl From<GrpcCode> for Code {
             GrpcCode::NotFound => Code::NotFound,
             GrpcCode::AlreadyExists => Code::AlreadyExists,
             GrpcCode::PermissionDenied => Code::PermissionDenied,
-            GrpcCode::ResourceExhausted => Code::Internal,
+            GrpcCode::ResourceExhausted => Code::ResourceExhausted,
             GrpcCode::FailedPrecondition => Code::FailedPrecondition,
             GrpcCode::Aborted => Code::Internal,
             GrpcCode::OutOfRange => Code::Internal,
@@ -132,6 +138,8 @@ pub struct Status {
     pub code: Code,
     /// A relevant error message, found in the `grpc-message` header.
     message: String,
+    /// Binary opaque details, found in the `grpc-status-details-bin` header.
+    details: Bytes,
 }
 
 impl Status {
@@ -140,6 +148,17 @@ impl Status {
         Status {
             code,
             message: message.into(),
+            details: Bytes::new(),
+        }
+    }
+
+    /// Create a new `Status` with the associated code, message and binary
+    /// details, e.g. a serialized message describing the error.
+    pub fn with_details(code: Code, message: impl Into<String>, details: Bytes) -> Status {
+        Status {
+            code,
+            message: message.into(),
+            details,
         }
     }
 
@@ -184,6 +203,12 @@ impl Status {
         Status::new(Code::PermissionDenied, message)
     }
 
+    /// Some resource has been exhausted, perhaps a per-user quota, or perhaps
+    /// the entire file system is out of space.
+    pub fn resource_exhausted(message: impl Into<String>) -> Status {
+        Status::new(Code::ResourceExhausted, message)
+    }
+
     /// Operation was rejected because the system is not in a state required for
     /// the operation's execution. For example, directory to be deleted may be
     /// non-empty, an rmdir operation is applied to a non-directory, etc.
@@ -237,6 +262,11 @@ impl Status {
     pub fn message(&self) -> &'_ str {
         &self.message
     }
+
+    // Retrieve the binary details, which are empty if none were set.
+    pub fn details(&self) -> &[u8] {
+        &self.details
+    }
 }
 
 impl std::fmt::Display for Status {
@@ -256,18 +286,42 @@ impl std::fmt::Debug for Status {
             builder.field("message", &self.message);
         }
 
+        if !self.details.is_empty() {
+            builder.field("details", &self.details);
+        }
+
         builder.finish()
     }
 }
 
 impl From<Status> for GrpcStatus {
     fn from(status: Status) -> Self {
-        GrpcStatus::new(status.code.into(), status.message)
+        GrpcStatus::with_details(status.code.into(), status.message, status.details)
     }
 }
 
 impl From<GrpcStatus> for Status {
     fn from(status: GrpcStatus) -> Self {
-        Status::new(status.code().into(), status.message())
+        Status::with_details(
+            status.code().into(),
+            status.message(),
+            Bytes::copy_from_slice(status.details()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_details_survive_grpc_round_trip() {
+        let details = Bytes::from_static(b"details");
+        let status = Status::with_details(Code::ResourceExhausted, "message", details.clone());
+
+        let status = Status::from(GrpcStatus::from(status));
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.message(), "message");
+        assert_eq!(status.details(), &details[..]);
     }
 }
@@ -88,6 +88,20 @@ mod event_source {
         ) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_encode_decode_delete_event_sources_for_tenant_request(
+            value in st::delete_event_sources_for_tenant_requests()
+        ) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_encode_decode_delete_event_sources_for_tenant_response(
+            value in st::delete_event_sources_for_tenant_responses()
+        ) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -146,6 +160,20 @@ mod graph {
             check_encode_decode_invariant(increment_only_uint_prop)
         }
 
+        #[test]
+        fn test_mutable_f64_prop_encode_decode(
+            mutable_f64_prop in st::mutable_f64_props()
+        ) {
+            check_encode_decode_invariant(mutable_f64_prop)
+        }
+
+        #[test]
+        fn test_mutable_str_prop_encode_decode(
+            mutable_str_prop in st::mutable_str_props()
+        ) {
+            check_encode_decode_invariant(mutable_str_prop)
+        }
+
         #[test]
         fn test_edge_encode_decode(edge in st::edges()) {
             check_encode_decode_invariant(edge)
@@ -284,6 +312,13 @@ mod pipeline {
         ){
             check_encode_decode_invariant(envelope)
         }
+
+        #[test]
+        fn test_failed_envelope_encode_decode(
+            failed_envelope in st::failed_envelopes()
+        ) {
+            check_encode_decode_invariant(failed_envelope)
+        }
     }
 }
 
@@ -401,6 +436,20 @@ mod plugin_registry {
             check_encode_decode_invariant(value)
         }
 
+        #[test]
+        fn test_serde_delete_plugins_for_tenant_requests(
+            value in pr_strats::delete_plugins_for_tenant_requests()
+        ) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_serde_delete_plugins_for_tenant_responses(
+            value in pr_strats::delete_plugins_for_tenant_responses()
+        ) {
+            check_encode_decode_invariant(value)
+        }
+
         #[test]
         fn test_serde_get_plugin_health_requests(
             value in pr_strats::get_plugin_health_requests()
@@ -512,6 +561,20 @@ mod plugin_work_queue {
         fn test_push_execute_generator_responses(value in pwq_strats::push_execute_generator_responses()) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_delete_executions_for_tenant_requests(
+            value in pwq_strats::delete_executions_for_tenant_requests()
+        ) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_delete_executions_for_tenant_responses(
+            value in pwq_strats::delete_executions_for_tenant_responses()
+        ) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -530,6 +593,16 @@ mod graph_schema_manager {
         fn test_get_edge_schema_responses(value in sm_strats::get_edge_schema_responses()) {
             check_encode_decode_invariant(value)
         }
+
+        #[test]
+        fn test_get_property_schema_requests(value in sm_strats::get_property_schema_requests()) {
+            check_encode_decode_invariant(value)
+        }
+
+        #[test]
+        fn test_get_property_schema_responses(value in sm_strats::get_property_schema_responses()) {
+            check_encode_decode_invariant(value)
+        }
     }
 }
 
@@ -546,3 +619,16 @@ mod analyzer_sdk {
         }
     }
 }
+
+mod graph_query_service {
+    use strategies::graph_query_service as gqs_strats;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_graph_view(value in gqs_strats::graph_views()) {
+            check_encode_decode_invariant(value)
+        }
+    }
+}
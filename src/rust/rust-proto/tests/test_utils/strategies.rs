@@ -38,6 +38,7 @@ pub mod pipeline {
     use rust_proto::{
         graplinc::grapl::pipeline::v1beta1::{
             Envelope,
+            FailedEnvelope,
             RawLog,
         },
         SerDe,
@@ -73,6 +74,21 @@ pub mod pipeline {
             },
         )
     }
+
+    //
+    // FailedEnvelope
+    //
+
+    prop_compose! {
+        pub fn failed_envelopes()(
+            envelope in envelopes(raw_logs()),
+            origin_topic in string_not_empty(),
+            error_reason in string_not_empty(),
+        ) -> FailedEnvelope {
+            FailedEnvelope::new(envelope, origin_topic, error_reason)
+                .expect("failed to serialize envelope")
+        }
+    }
 }
 
 pub mod common {
@@ -144,6 +160,8 @@ pub mod graph {
         MergedEdgeList,
         MergedGraph,
         MergedNode,
+        MutableF64Prop,
+        MutableStrProp,
         NodeDescription,
         NodeProperty,
         Property,
@@ -252,6 +270,34 @@ pub mod graph {
         }
     }
 
+    //
+    // MutableF64Prop
+    //
+
+    prop_compose! {
+        pub fn mutable_f64_props()(
+            prop in any::<f64>(),
+        ) -> MutableF64Prop {
+            MutableF64Prop {
+                prop
+            }
+        }
+    }
+
+    //
+    // MutableStrProp
+    //
+
+    prop_compose! {
+        pub fn mutable_str_props()(
+            prop in any::<String>(),
+        ) -> MutableStrProp {
+            MutableStrProp {
+                prop
+            }
+        }
+    }
+
     //
     // Lens
     //
@@ -402,6 +448,8 @@ pub mod graph {
             immutable_uint_props().prop_map(Property::ImmutableUintProp),
             increment_only_int_props().prop_map(Property::IncrementOnlyIntProp),
             increment_only_uint_props().prop_map(Property::IncrementOnlyUintProp),
+            mutable_f64_props().prop_map(Property::MutableF64Prop),
+            mutable_str_props().prop_map(Property::MutableStrProp),
         ]
     }
 
@@ -699,6 +747,21 @@ pub mod event_source {
             }
         }
     }
+
+    prop_compose! {
+        pub fn delete_event_sources_for_tenant_requests()(
+            tenant_id in uuids(),
+        ) -> native::DeleteEventSourcesForTenantRequest {
+            native::DeleteEventSourcesForTenantRequest {
+                tenant_id,
+            }
+        }
+    }
+
+    pub fn delete_event_sources_for_tenant_responses(
+    ) -> impl Strategy<Value = native::DeleteEventSourcesForTenantResponse> {
+        Just(native::DeleteEventSourcesForTenantResponse {})
+    }
 }
 
 pub mod plugin_registry {
@@ -706,6 +769,8 @@ pub mod plugin_registry {
     use rust_proto::graplinc::grapl::api::plugin_registry::v1beta1::{
         CreatePluginRequest,
         CreatePluginResponse,
+        DeletePluginsForTenantRequest,
+        DeletePluginsForTenantResponse,
         DeployPluginRequest,
         DeployPluginResponse,
         GetAnalyzersForTenantRequest,
@@ -904,6 +969,19 @@ pub mod plugin_registry {
         Just(TearDownPluginResponse {})
     }
 
+    prop_compose! {
+        pub fn delete_plugins_for_tenant_requests()(
+            tenant_id in uuids()
+        ) -> DeletePluginsForTenantRequest {
+            DeletePluginsForTenantRequest::new(tenant_id)
+        }
+    }
+
+    pub fn delete_plugins_for_tenant_responses(
+    ) -> impl Strategy<Value = DeletePluginsForTenantResponse> {
+        Just(DeletePluginsForTenantResponse {})
+    }
+
     prop_compose! {
         pub fn get_plugin_health_requests()(
             plugin_id in uuids()
@@ -1013,6 +1091,7 @@ pub mod plugin_work_queue {
         pub fn acknowledge_analyzer_requests()(
             request_id in any::<i64>(),
             success in any::<bool>(),
+            execution_hits in proptest::collection::vec(analyzer_sdk::execution_hits(), 0..3),
             plugin_id in uuids(),
             tenant_id in uuids(),
             trace_id in uuids(),
@@ -1021,6 +1100,7 @@ pub mod plugin_work_queue {
             native::AcknowledgeAnalyzerRequest::new(
                 request_id,
                 success,
+                execution_hits,
                 plugin_id,
                 tenant_id,
                 trace_id,
@@ -1115,6 +1195,21 @@ pub mod plugin_work_queue {
     ) -> impl Strategy<Value = native::PushExecuteGeneratorResponse> {
         Just(native::PushExecuteGeneratorResponse {})
     }
+
+    prop_compose! {
+        pub fn delete_executions_for_tenant_requests()(
+            tenant_id in uuids(),
+        ) -> native::DeleteExecutionsForTenantRequest {
+            native::DeleteExecutionsForTenantRequest {
+                tenant_id,
+            }
+        }
+    }
+
+    pub fn delete_executions_for_tenant_responses(
+    ) -> impl Strategy<Value = native::DeleteExecutionsForTenantResponse> {
+        Just(native::DeleteExecutionsForTenantResponse {})
+    }
 }
 
 pub mod graph_schema_manager {
@@ -1155,6 +1250,44 @@ pub mod graph_schema_manager {
         }
     }
 
+    pub fn property_types() -> BoxedStrategy<native::PropertyType> {
+        prop_oneof![
+            // For cases without data, `Just` is all you need
+            Just(native::PropertyType::ImmutableString),
+            Just(native::PropertyType::ImmutableI64),
+            Just(native::PropertyType::MaxI64),
+            Just(native::PropertyType::MinI64),
+            Just(native::PropertyType::ImmutableU64),
+            Just(native::PropertyType::MaxU64),
+            Just(native::PropertyType::MinU64),
+            Just(native::PropertyType::MutableString),
+            Just(native::PropertyType::MutableF64),
+        ]
+        .boxed()
+    }
+
+    prop_compose! {
+        pub fn get_property_schema_requests()(
+            tenant_id in uuids(),
+            node_type in common::node_types(),
+            property_name in common::property_names(),
+        ) -> native::GetPropertySchemaRequest {
+            native::GetPropertySchemaRequest {
+                tenant_id,
+                node_type,
+                property_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn get_property_schema_responses()(
+            property_type in property_types(),
+        ) -> native::GetPropertySchemaResponse {
+            native::GetPropertySchemaResponse { property_type }
+        }
+    }
+
     prop_compose! {
         pub fn deploy_schema_requests()(
             tenant_id in uuids(),
@@ -1245,6 +1378,40 @@ pub mod analyzer_sdk {
         ]
     }
 
+    prop_compose! {
+        pub fn lens_refs()(
+            lens_namespace in any::<String>(),
+            lens_name in any::<String>(),
+        ) -> native::LensRef {
+            native::LensRef {
+                lens_namespace,
+                lens_name,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn execution_hits()(
+            graph_view in graph_query_service::graph_views(),
+            lens_refs in proptest::collection::vec(lens_refs(), 0..3),
+            analyzer_name in any::<String>(),
+            time_of_match in any::<SystemTime>(),
+            idempotency_key in any::<u64>(),
+            score in any::<i32>(),
+        ) -> native::ExecutionHit {
+            native::ExecutionHit {
+                graph_view,
+                lens_refs,
+                analyzer_name: native::AnalyzerName {
+                    value: analyzer_name,
+                },
+                time_of_match,
+                idempotency_key,
+                score,
+            }
+        }
+    }
+
     prop_compose! {
         pub fn run_analyzer_requests()(
             tenant_id in uuids(),
@@ -1257,3 +1424,99 @@ pub mod analyzer_sdk {
         }
     }
 }
+
+pub mod graph_query_service {
+    use proptest::collection;
+    use rust_proto::graplinc::grapl::api::graph_query_service::v1beta1::messages::{
+        self as native,
+    };
+
+    use super::{
+        common::{
+            edge_names,
+            node_types,
+            property_names,
+            uids,
+        },
+        *,
+    };
+
+    prop_compose! {
+        pub fn string_properties()(
+            prop_map in collection::hash_map(property_names(), any::<String>(), 0..5),
+        ) -> native::StringProperties {
+            native::StringProperties {
+                prop_map: prop_map.into_iter().collect(),
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn int64_properties()(
+            prop_map in collection::hash_map(property_names(), any::<i64>(), 0..5),
+        ) -> native::Int64Properties {
+            native::Int64Properties {
+                prop_map: prop_map.into_iter().collect(),
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn uint64_properties()(
+            prop_map in collection::hash_map(property_names(), any::<u64>(), 0..5),
+        ) -> native::UInt64Properties {
+            native::UInt64Properties {
+                prop_map: prop_map.into_iter().collect(),
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn float64_properties()(
+            prop_map in collection::hash_map(property_names(), any::<f64>(), 0..5),
+        ) -> native::Float64Properties {
+            native::Float64Properties {
+                prop_map: prop_map.into_iter().collect(),
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn node_properties_views()(
+            uid in uids(),
+            node_type in node_types(),
+            string_properties in string_properties(),
+            int64_properties in int64_properties(),
+            uint64_properties in uint64_properties(),
+            float64_properties in float64_properties(),
+        ) -> native::NodePropertiesView {
+            native::NodePropertiesView {
+                uid,
+                node_type,
+                string_properties,
+                int64_properties,
+                uint64_properties,
+                float64_properties,
+            }
+        }
+    }
+
+    prop_compose! {
+        pub fn graph_views()(
+            nodes in collection::vec(node_properties_views(), 0..5),
+            edges in collection::hash_map(
+                (uids(), edge_names()),
+                collection::hash_set(uids(), 1..3),
+                0..5,
+            ),
+        ) -> native::GraphView {
+            native::GraphView {
+                nodes: nodes.into_iter().map(|node| (node.uid, node)).collect(),
+                edges: edges
+                    .into_iter()
+                    .map(|(key, neighbors)| (key, neighbors.into_iter().collect()))
+                    .collect(),
+            }
+        }
+    }
+}
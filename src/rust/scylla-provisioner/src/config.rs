@@ -1,7 +1,10 @@
 use std::net::SocketAddr;
 
+use rust_proto::graplinc::grapl::api::scylla_provisioner::v1beta1::messages::ReplicationStrategy;
 use secrecy::ExposeSecret;
 
+use crate::replication::parse_replication_strategy;
+
 #[derive(clap::Parser, Clone, Debug)]
 pub struct GraphDbConfig {
     #[clap(long, env, value_delimiter = ',')]
@@ -32,6 +35,16 @@ pub struct ScyllaProvisionerServiceConfig {
     /// The address to bind the graph query service to
     pub scylla_provisioner_bind_address: SocketAddr,
 
+    #[clap(
+        long,
+        env = "SCYLLA_PROVISIONER_REPLICATION_STRATEGY",
+        default_value = "SimpleStrategy:1",
+        parse(try_from_str = parse_replication_strategy)
+    )]
+    /// The replication strategy for newly provisioned tenant keyspaces, either
+    /// `SimpleStrategy:<rf>` or `NetworkTopologyStrategy:<dc>=<rf>[,<dc>=<rf>...]`
+    pub replication_strategy: ReplicationStrategy,
+
     #[clap(flatten)]
     pub graph_db_config: GraphDbConfig,
 }
@@ -1,3 +1,3 @@
 pub mod config;
+pub mod replication;
 pub mod server;
-pub mod table_names;
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use rust_proto::graplinc::grapl::api::scylla_provisioner::v1beta1::messages::ReplicationStrategy;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ReplicationStrategyError {
+    #[error("replication factor must be greater than 0")]
+    ZeroReplicationFactor,
+    #[error("NetworkTopologyStrategy requires at least one datacenter")]
+    NoDatacenters,
+    #[error("invalid datacenter name {0:?}")]
+    InvalidDatacenterName(String),
+    #[error("invalid replication strategy {0:?}, expected `SimpleStrategy:<rf>` or `NetworkTopologyStrategy:<dc>=<rf>[,<dc>=<rf>...]`")]
+    Unparseable(String),
+}
+
+/// Parses a replication strategy from the form used by the service's
+/// configuration, either `SimpleStrategy:<rf>` or
+/// `NetworkTopologyStrategy:<dc>=<rf>[,<dc>=<rf>...]`.
+pub fn parse_replication_strategy(
+    value: &str,
+) -> Result<ReplicationStrategy, ReplicationStrategyError> {
+    let unparseable = || ReplicationStrategyError::Unparseable(value.to_owned());
+
+    let (class, options) = value.split_once(':').ok_or_else(unparseable)?;
+    let strategy = match class.trim() {
+        "SimpleStrategy" => ReplicationStrategy::Simple {
+            replication_factor: options.trim().parse().map_err(|_| unparseable())?,
+        },
+        "NetworkTopologyStrategy" => {
+            let mut datacenter_replication_factors = HashMap::new();
+            for datacenter in options.split(',') {
+                let (name, replication_factor) =
+                    datacenter.split_once('=').ok_or_else(unparseable)?;
+                datacenter_replication_factors.insert(
+                    name.trim().to_owned(),
+                    replication_factor
+                        .trim()
+                        .parse()
+                        .map_err(|_| unparseable())?,
+                );
+            }
+            ReplicationStrategy::NetworkTopology {
+                datacenter_replication_factors,
+            }
+        }
+        _ => return Err(unparseable()),
+    };
+
+    validate_replication_strategy(&strategy)?;
+    Ok(strategy)
+}
+
+/// Rejects replication factors of 0 and suspicious datacenter names. Datacenter
+/// names are interpolated into CQL, so they're restricted to a conservative set
+/// of characters.
+pub fn validate_replication_strategy(
+    strategy: &ReplicationStrategy,
+) -> Result<(), ReplicationStrategyError> {
+    match strategy {
+        ReplicationStrategy::Simple { replication_factor } => {
+            if *replication_factor == 0 {
+                return Err(ReplicationStrategyError::ZeroReplicationFactor);
+            }
+        }
+        ReplicationStrategy::NetworkTopology {
+            datacenter_replication_factors,
+        } => {
+            if datacenter_replication_factors.is_empty() {
+                return Err(ReplicationStrategyError::NoDatacenters);
+            }
+            for (name, replication_factor) in datacenter_replication_factors {
+                let valid_name = !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+                if !valid_name {
+                    return Err(ReplicationStrategyError::InvalidDatacenterName(
+                        name.clone(),
+                    ));
+                }
+                if *replication_factor == 0 {
+                    return Err(ReplicationStrategyError::ZeroReplicationFactor);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Renders the strategy as the map literal that follows `WITH REPLICATION =`
+/// in a keyspace statement. The strategy must already have been validated.
+pub fn replication_options(strategy: &ReplicationStrategy) -> String {
+    match strategy {
+        ReplicationStrategy::Simple { replication_factor } => {
+            format!("{{'class' : 'SimpleStrategy', 'replication_factor' : {replication_factor}}}")
+        }
+        ReplicationStrategy::NetworkTopology {
+            datacenter_replication_factors,
+        } => {
+            let mut datacenters: Vec<_> = datacenter_replication_factors.iter().collect();
+            datacenters.sort();
+            let datacenters: Vec<String> = datacenters
+                .into_iter()
+                .map(|(name, replication_factor)| format!("'{name}' : {replication_factor}"))
+                .collect();
+            format!(
+                "{{'class' : 'NetworkTopologyStrategy', {}}}",
+                datacenters.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_strategy() {
+        let strategy = parse_replication_strategy("SimpleStrategy:3").unwrap();
+        assert_eq!(
+            strategy,
+            ReplicationStrategy::Simple {
+                replication_factor: 3
+            }
+        );
+        assert_eq!(
+            replication_options(&strategy),
+            "{'class' : 'SimpleStrategy', 'replication_factor' : 3}"
+        );
+    }
+
+    #[test]
+    fn test_parse_network_topology_strategy() {
+        let strategy =
+            parse_replication_strategy("NetworkTopologyStrategy:us-west=3, us-east=2").unwrap();
+        assert_eq!(
+            replication_options(&strategy),
+            "{'class' : 'NetworkTopologyStrategy', 'us-east' : 2, 'us-west' : 3}"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_strategies() {
+        assert_eq!(
+            parse_replication_strategy("SimpleStrategy:0"),
+            Err(ReplicationStrategyError::ZeroReplicationFactor)
+        );
+        assert_eq!(
+            parse_replication_strategy("NetworkTopologyStrategy:dc1'=3"),
+            Err(ReplicationStrategyError::InvalidDatacenterName(
+                "dc1'".to_owned()
+            ))
+        );
+        assert!(parse_replication_strategy("LocalStrategy:1").is_err());
+        assert!(parse_replication_strategy("SimpleStrategy").is_err());
+    }
+}
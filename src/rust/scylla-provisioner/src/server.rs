@@ -4,6 +4,7 @@ use std::{
 };
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use rust_proto::{
     graplinc::grapl::api::scylla_provisioner::v1beta1::{
         messages as native,
@@ -18,29 +19,70 @@ use rust_proto::{
     },
 };
 use scylla::{
-    transport::errors::QueryError,
+    cql_to_rust::{
+        FromRow,
+        FromRowError,
+    },
+    frame::value::Value,
+    transport::{
+        errors::QueryError,
+        query_result::MaybeFirstRowTypedError,
+    },
     Session,
 };
+use scylla_table_names::{
+    tenant_keyspace_name,
+    EDGES_TABLE_NAME,
+    IMM_I_64_INDEX_TABLE_NAME,
+    IMM_I_64_TABLE_NAME,
+    IMM_STRING_INDEX_TABLE_NAME,
+    IMM_STRING_TABLE_NAME,
+    IMM_U_64_INDEX_TABLE_NAME,
+    IMM_U_64_TABLE_NAME,
+    NODE_TYPE_TABLE_NAME,
+    PROPERTY_INDEX_TABLES,
+    PROPERTY_TABLES,
+    PROVISIONING_MARKERS_TABLE_NAME,
+    TENANT_KEYSPACE_PREFIX,
+};
 use tokio::net::TcpListener;
 
 use crate::{
     config::ScyllaProvisionerServiceConfig,
-    table_names::{
-        tenant_keyspace_name,
-        IMM_STRING_TABLE_NAME,
+    replication::{
+        replication_options,
+        validate_replication_strategy,
+        ReplicationStrategyError,
     },
 };
 
+/// Written to a keyspace's provisioning markers once its property indexes hold
+/// an entry for every immutable property written before they existed.
+const INDEX_BACKFILL_COMPLETE_MARKER: &str = "index_backfill_complete";
+
 #[derive(thiserror::Error, Debug)]
 pub enum ScyllaProvisionerError {
     #[error("Scylla Error {0}")]
     ScyllaError(#[from] QueryError),
+    #[error("Invalid replication strategy: {0}")]
+    InvalidReplicationStrategy(#[from] ReplicationStrategyError),
+    #[error("Invalid row {0}")]
+    FromRowError(#[from] FromRowError),
+    #[error("Invalid row {0}")]
+    MaybeFirstRowTypedError(#[from] MaybeFirstRowTypedError),
 }
 
 impl From<ScyllaProvisionerError> for Status {
     fn from(error: ScyllaProvisionerError) -> Self {
         match error {
             ScyllaProvisionerError::ScyllaError(error) => Status::unknown(error.to_string()),
+            ScyllaProvisionerError::InvalidReplicationStrategy(error) => {
+                Status::invalid_argument(error.to_string())
+            }
+            ScyllaProvisionerError::FromRowError(error) => Status::unknown(error.to_string()),
+            ScyllaProvisionerError::MaybeFirstRowTypedError(error) => {
+                Status::unknown(error.to_string())
+            }
         }
     }
 }
@@ -48,6 +90,7 @@ impl From<ScyllaProvisionerError> for Status {
 #[derive(Clone)]
 pub struct ScyllaProvisioner {
     scylla_client: Arc<Session>,
+    replication_strategy: native::ReplicationStrategy,
 }
 
 #[async_trait]
@@ -63,62 +106,396 @@ impl ScyllaProvisionerApi for ScyllaProvisioner {
 
         let tenant_ks = tenant_keyspace_name(tenant_id);
 
-        session.query(
-            format!(
-                r"CREATE KEYSPACE IF NOT EXISTS {tenant_ks} WITH REPLICATION = {{'class' : 'SimpleStrategy', 'replication_factor' : 1}};"
-            ),
-            &[]
-        ).await?;
-
-        let property_table_names = [(IMM_STRING_TABLE_NAME, "text")];
-
-        for (table_name, value_type) in property_table_names.into_iter() {
-            session
-                .query(
-                    format!(
-                        r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{table_name} (
-                            uid bigint,
-                            populated_field text,
-                            value {value_type},
-                            PRIMARY KEY (uid, populated_field)
-                        )"
-                    ),
-                    &(),
-                )
-                .await?;
+        let replication = replication_options(&self.replication_strategy);
+
+        // A new keyspace has nothing to backfill, but an existing one may be
+        // partway through its backfill, which mustn't be marked complete
+        let is_new_keyspace = !keyspace_exists(session, &tenant_ks).await?;
+
+        session
+            .query(
+                format!(
+                    r"CREATE KEYSPACE IF NOT EXISTS {tenant_ks} WITH REPLICATION = {replication};"
+                ),
+                &[],
+            )
+            .await?;
+
+        provision_tenant_tables(session, &tenant_ks).await?;
+
+        session.await_schema_agreement().await?;
+
+        if is_new_keyspace {
+            mark_index_backfill_complete(session, &tenant_ks).await?;
         }
 
+        Ok(native::ProvisionGraphForTenantResponse {})
+    }
+
+    async fn deprovision_graph_for_tenant(
+        &self,
+        request: native::DeprovisionGraphForTenantRequest,
+    ) -> Result<native::DeprovisionGraphForTenantResponse, Self::Error> {
+        let native::DeprovisionGraphForTenantRequest { tenant_id } = request;
+        let session = self.scylla_client.as_ref();
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+
+        session
+            .query(format!(r"DROP KEYSPACE IF EXISTS {tenant_ks};"), &[])
+            .await?;
+
+        session.await_schema_agreement().await?;
+
+        Ok(native::DeprovisionGraphForTenantResponse {})
+    }
+
+    async fn alter_replication_for_tenant(
+        &self,
+        request: native::AlterReplicationForTenantRequest,
+    ) -> Result<native::AlterReplicationForTenantResponse, Self::Error> {
+        let native::AlterReplicationForTenantRequest {
+            tenant_id,
+            replication_strategy,
+        } = request;
+        validate_replication_strategy(&replication_strategy)?;
+        let session = self.scylla_client.as_ref();
+
+        let tenant_ks = tenant_keyspace_name(tenant_id);
+        let replication = replication_options(&replication_strategy);
+
+        session
+            .query(
+                format!(r"ALTER KEYSPACE {tenant_ks} WITH REPLICATION = {replication};"),
+                &[],
+            )
+            .await?;
+
+        session.await_schema_agreement().await?;
+
+        tracing::info!(
+            message = "altered tenant keyspace replication",
+            tenant_id =% tenant_id,
+            replication =% replication,
+        );
+
+        Ok(native::AlterReplicationForTenantResponse {})
+    }
+}
+
+/// Creates any of the tenant's tables that don't exist yet. Every statement is
+/// idempotent, so this also brings a keyspace provisioned before a table was
+/// added up to date.
+async fn provision_tenant_tables(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    for (table_name, value_type) in PROPERTY_TABLES.into_iter() {
         session
             .query(
                 format!(
-                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.node_type (
+                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{table_name} (
                         uid bigint,
-                        node_type text,
-                        PRIMARY KEY (uid, node_type)
+                        populated_field text,
+                        value {value_type},
+                        PRIMARY KEY (uid, populated_field)
                     )"
                 ),
                 &(),
             )
             .await?;
+    }
+
+    for (table_name, value_type) in PROPERTY_INDEX_TABLES.into_iter() {
         session
             .query(
                 format!(
-                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.edges (
-                        source_uid bigint,
-                        destination_uid bigint,
-                        f_edge_name text,
-                        r_edge_name text,
-                        PRIMARY KEY (source_uid, f_edge_name, destination_uid)
+                    r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{table_name} (
+                        node_type text,
+                        populated_field text,
+                        value {value_type},
+                        uid bigint,
+                        PRIMARY KEY ((node_type, populated_field), value, uid)
                     )"
                 ),
                 &(),
             )
             .await?;
+    }
 
-        session.await_schema_agreement().await?;
+    session
+        .query(
+            format!(
+                r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{NODE_TYPE_TABLE_NAME} (
+                    uid bigint,
+                    node_type text,
+                    PRIMARY KEY (uid, node_type)
+                )"
+            ),
+            &(),
+        )
+        .await?;
+    session
+        .query(
+            format!(
+                r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{EDGES_TABLE_NAME} (
+                    source_uid bigint,
+                    destination_uid bigint,
+                    f_edge_name text,
+                    r_edge_name text,
+                    PRIMARY KEY (source_uid, f_edge_name, destination_uid)
+                )"
+            ),
+            &(),
+        )
+        .await?;
+    session
+        .query(
+            format!(
+                r"CREATE TABLE IF NOT EXISTS {tenant_ks}.{PROVISIONING_MARKERS_TABLE_NAME} (
+                    marker text PRIMARY KEY
+                )"
+            ),
+            &(),
+        )
+        .await?;
 
-        Ok(native::ProvisionGraphForTenantResponse {})
+    Ok(())
+}
+
+async fn keyspace_exists(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<bool, ScyllaProvisionerError> {
+    let keyspace = session
+        .query(
+            "SELECT keyspace_name FROM system_schema.keyspaces WHERE keyspace_name = ?",
+            (tenant_ks,),
+        )
+        .await?
+        .maybe_first_row_typed::<(String,)>()?;
+    Ok(keyspace.is_some())
+}
+
+/// Provisions any tables added since the existing tenant keyspaces were
+/// provisioned, e.g. the property index tables, and returns the keyspaces.
+#[tracing::instrument(skip(session), err)]
+async fn reprovision_tenant_keyspaces(
+    session: &Session,
+) -> Result<Vec<String>, ScyllaProvisionerError> {
+    let keyspaces = session
+        .query("SELECT keyspace_name FROM system_schema.keyspaces", &[])
+        .await?
+        .rows_typed_or_empty::<(String,)>()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let tenant_keyspaces: Vec<String> = keyspaces
+        .into_iter()
+        .map(|(keyspace,)| keyspace)
+        .filter(|keyspace| keyspace.starts_with(TENANT_KEYSPACE_PREFIX))
+        .collect();
+    for tenant_ks in tenant_keyspaces.iter() {
+        provision_tenant_tables(session, tenant_ks).await?;
+    }
+
+    session.await_schema_agreement().await?;
+
+    tracing::info!(
+        message = "reprovisioned existing tenant keyspaces",
+        count = tenant_keyspaces.len(),
+    );
+
+    Ok(tenant_keyspaces)
+}
+
+/// Resumes the property index backfill of every keyspace that hasn't completed
+/// it. This can take a while for a large tenant, so it runs in the background
+/// while the service is serving; a keyspace whose backfill fails is retried
+/// the next time the service starts.
+async fn resume_property_index_backfills(session: Arc<Session>, tenant_keyspaces: Vec<String>) {
+    for tenant_ks in tenant_keyspaces {
+        if let Err(e) = resume_property_index_backfill(session.as_ref(), &tenant_ks).await {
+            tracing::error!(
+                message = "failed to backfill property indexes",
+                tenant_ks =% tenant_ks,
+                error =% e,
+            );
+        }
+    }
+}
+
+/// Provisions any tables the tenant keyspace doesn't have yet, then resumes
+/// its property index backfill if it hasn't completed.
+#[tracing::instrument(skip(session), err)]
+pub async fn reprovision_tenant_keyspace(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    provision_tenant_tables(session, tenant_ks).await?;
+    session.await_schema_agreement().await?;
+    resume_property_index_backfill(session, tenant_ks).await
+}
+
+/// Backfills the property indexes of a keyspace provisioned before they
+/// existed, so that FindNodes can find nodes written before then. Completion
+/// is recorded with a marker rather than inferred from the index tables
+/// existing, so that a backfill which fails partway is started over rather
+/// than leaving the indexes incomplete for good.
+async fn resume_property_index_backfill(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    if is_index_backfill_complete(session, tenant_ks).await? {
+        return Ok(());
     }
+    backfill_property_indexes(session, tenant_ks).await?;
+    mark_index_backfill_complete(session, tenant_ks).await
+}
+
+async fn is_index_backfill_complete(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<bool, ScyllaProvisionerError> {
+    let marker = session
+        .query(
+            format!(
+                r"
+                SELECT marker
+                FROM {tenant_ks}.{PROVISIONING_MARKERS_TABLE_NAME}
+                WHERE marker = ?
+                "
+            ),
+            (INDEX_BACKFILL_COMPLETE_MARKER,),
+        )
+        .await?
+        .maybe_first_row_typed::<(String,)>()?;
+    Ok(marker.is_some())
+}
+
+async fn mark_index_backfill_complete(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    session
+        .query(
+            format!(
+                r"
+                INSERT INTO {tenant_ks}.{PROVISIONING_MARKERS_TABLE_NAME} (marker)
+                VALUES (?)
+                "
+            ),
+            (INDEX_BACKFILL_COMPLETE_MARKER,),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Writes the index entry of every immutable property in the keyspace.
+///
+/// Each entry is written at its property's WRITETIME rather than now, so that
+/// a property cleared while the backfill runs doesn't get an index entry which
+/// outlives it. Properties of nodes with no node type can't be indexed, and
+/// are skipped.
+#[tracing::instrument(skip(session), err)]
+async fn backfill_property_indexes(
+    session: &Session,
+    tenant_ks: &str,
+) -> Result<(), ScyllaProvisionerError> {
+    let mut backfilled = backfill_property_index::<String>(
+        session,
+        tenant_ks,
+        IMM_STRING_TABLE_NAME,
+        IMM_STRING_INDEX_TABLE_NAME,
+    )
+    .await?;
+    backfilled += backfill_property_index::<i64>(
+        session,
+        tenant_ks,
+        IMM_I_64_TABLE_NAME,
+        IMM_I_64_INDEX_TABLE_NAME,
+    )
+    .await?;
+    // u64 values are stored as bigint
+    backfilled += backfill_property_index::<i64>(
+        session,
+        tenant_ks,
+        IMM_U_64_TABLE_NAME,
+        IMM_U_64_INDEX_TABLE_NAME,
+    )
+    .await?;
+
+    tracing::info!(
+        message = "backfilled property indexes",
+        tenant_ks =% tenant_ks,
+        count = backfilled,
+    );
+
+    Ok(())
+}
+
+async fn backfill_property_index<V>(
+    session: &Session,
+    tenant_ks: &str,
+    table_name: &str,
+    index_table_name: &str,
+) -> Result<usize, ScyllaProvisionerError>
+where
+    (i64, String, V, i64): FromRow,
+    V: Value,
+{
+    let mut rows = session
+        .query_iter(
+            format!(
+                r"
+                SELECT uid, populated_field, value, WRITETIME(value)
+                FROM {tenant_ks}.{table_name}
+                "
+            ),
+            &[],
+        )
+        .await?;
+
+    let mut backfilled = 0;
+    while let Some(row) = rows.next().await {
+        let (uid, populated_field, value, writetime) =
+            row?.into_typed::<(i64, String, V, i64)>()?;
+
+        let node_type = session
+            .query(
+                format!(
+                    r"
+                    SELECT node_type
+                    FROM {tenant_ks}.{NODE_TYPE_TABLE_NAME}
+                    WHERE uid = ?
+                    LIMIT 1
+                    "
+                ),
+                (uid,),
+            )
+            .await?
+            .maybe_first_row_typed::<(String,)>()?;
+        let (node_type,) = match node_type {
+            Some(node_type) => node_type,
+            None => continue,
+        };
+
+        session
+            .query(
+                format!(
+                    r"
+                    INSERT INTO {tenant_ks}.{index_table_name}
+                    (node_type, populated_field, value, uid)
+                    VALUES (?, ?, ?, ?)
+                    USING TIMESTAMP ?
+                    "
+                ),
+                (node_type, populated_field, value, uid, writetime),
+            )
+            .await?;
+        backfilled += 1;
+    }
+
+    Ok(backfilled)
 }
 
 #[tracing::instrument(skip(config), err)]
@@ -132,10 +509,22 @@ pub async fn exec_service(
         message="Starting Db Schema Manager Service",
         addr=?addr,
         graph_db_addresses=?graph_db_config.graph_db_addresses,
+        replication_strategy=?config.replication_strategy,
     );
 
+    let scylla_client = Arc::new(graph_db_config.connect().await?);
+
+    // Bring keyspaces provisioned by an older version of this service up to
+    // date before anything can use the new tables
+    let tenant_keyspaces = reprovision_tenant_keyspaces(scylla_client.as_ref()).await?;
+    tokio::spawn(resume_property_index_backfills(
+        scylla_client.clone(),
+        tenant_keyspaces,
+    ));
+
     let plugin_registry = ScyllaProvisioner {
-        scylla_client: Arc::new(graph_db_config.connect().await?),
+        scylla_client,
+        replication_strategy: config.replication_strategy,
     };
 
     let healthcheck_polling_interval_ms = 5000; // TODO: un-hardcode
@@ -0,0 +1,185 @@
+#![cfg(feature = "integration_tests")]
+
+use clap::Parser;
+use rust_proto::{
+    client_factory::services::ScyllaProvisionerClientConfig,
+    graplinc::grapl::api::scylla_provisioner::v1beta1::{
+        client::ScyllaProvisionerClient,
+        messages as scylla_provisioner_msgs,
+    },
+    protocol::service_client::ConnectWithConfig,
+};
+use scylla_provisioner::{
+    config::GraphDbConfig,
+    server::reprovision_tenant_keyspace,
+};
+use scylla_table_names::{
+    tenant_keyspace_name,
+    IMM_STRING_INDEX_TABLE_NAME,
+    IMM_STRING_TABLE_NAME,
+    NODE_TYPE_TABLE_NAME,
+    PROPERTY_INDEX_TABLES,
+    PROVISIONING_MARKERS_TABLE_NAME,
+};
+
+#[test_log::test(tokio::test)]
+async fn test_alter_replication_and_deprovision() -> eyre::Result<()> {
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(provisioner_client_config).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    // The integration environment runs a single Scylla node, so the only
+    // replication factor it can satisfy is 1.
+    provisioner_client
+        .alter_replication_for_tenant(scylla_provisioner_msgs::AlterReplicationForTenantRequest {
+            tenant_id,
+            replication_strategy: scylla_provisioner_msgs::ReplicationStrategy::Simple {
+                replication_factor: 1,
+            },
+        })
+        .await?;
+
+    let invalid_alter = provisioner_client
+        .alter_replication_for_tenant(scylla_provisioner_msgs::AlterReplicationForTenantRequest {
+            tenant_id,
+            replication_strategy: scylla_provisioner_msgs::ReplicationStrategy::Simple {
+                replication_factor: 0,
+            },
+        })
+        .await;
+    assert!(invalid_alter.is_err());
+
+    // Deprovisioning is idempotent
+    for _ in 0..2 {
+        provisioner_client
+            .deprovision_graph_for_tenant(
+                scylla_provisioner_msgs::DeprovisionGraphForTenantRequest { tenant_id },
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_reprovision_backfills_property_indexes() -> eyre::Result<()> {
+    let provisioner_client_config = ScyllaProvisionerClientConfig::parse();
+    let mut provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(provisioner_client_config).await?;
+    let session = GraphDbConfig::parse()
+        .connect()
+        .await
+        .map_err(|e| eyre::eyre!("failed to connect to the graph db: {e}"))?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+    let tenant_ks = tenant_keyspace_name(tenant_id);
+
+    provisioner_client
+        .provision_graph_for_tenant(scylla_provisioner_msgs::ProvisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    // Make the keyspace look like it was provisioned before the property
+    // indexes existed, with one node whose property was never indexed and one
+    // property of a node with no type
+    for (index_table_name, _) in PROPERTY_INDEX_TABLES {
+        session
+            .query(format!("DROP TABLE {tenant_ks}.{index_table_name}"), &[])
+            .await?;
+    }
+    session
+        .query(
+            format!("DROP TABLE {tenant_ks}.{PROVISIONING_MARKERS_TABLE_NAME}"),
+            &[],
+        )
+        .await?;
+    session.await_schema_agreement().await?;
+
+    let insert_process_name = |uid: i64, node_type: Option<&'static str>| {
+        let session = &session;
+        let tenant_ks = &tenant_ks;
+        async move {
+            if let Some(node_type) = node_type {
+                session
+                    .query(
+                        format!(
+                            "INSERT INTO {tenant_ks}.{NODE_TYPE_TABLE_NAME} (uid, node_type) VALUES (?, ?)"
+                        ),
+                        (uid, node_type),
+                    )
+                    .await?;
+            }
+            session
+                .query(
+                    format!(
+                        "INSERT INTO {tenant_ks}.{IMM_STRING_TABLE_NAME} (uid, populated_field, value) VALUES (?, ?, ?)"
+                    ),
+                    (uid, "process_name", "chrome.exe"),
+                )
+                .await?;
+            eyre::Result::<()>::Ok(())
+        }
+    };
+    let indexed_uids = || {
+        let session = &session;
+        let tenant_ks = &tenant_ks;
+        async move {
+            let mut uids = session
+            .query(
+                format!(
+                    "SELECT uid FROM {tenant_ks}.{IMM_STRING_INDEX_TABLE_NAME} WHERE node_type = ? AND populated_field = ?"
+                ),
+                ("Process", "process_name"),
+            )
+            .await?
+            .rows_typed_or_empty::<(i64,)>()
+            .map(|row| row.map(|(uid,)| uid))
+            .collect::<Result<Vec<_>, _>>()?;
+            uids.sort();
+            eyre::Result::<_>::Ok(uids)
+        }
+    };
+
+    let (typed_uid, untyped_uid) = (1_i64, 2_i64);
+    insert_process_name(typed_uid, Some("Process")).await?;
+    insert_process_name(untyped_uid, None).await?;
+
+    reprovision_tenant_keyspace(&session, &tenant_ks).await?;
+    assert_eq!(indexed_uids().await?, vec![typed_uid]);
+
+    // A backfill which failed partway leaves the index tables in place but
+    // not its completion marker, and is resumed
+    let unindexed_uid = 3_i64;
+    insert_process_name(unindexed_uid, Some("Process")).await?;
+    session
+        .query(
+            format!("TRUNCATE {tenant_ks}.{PROVISIONING_MARKERS_TABLE_NAME}"),
+            &[],
+        )
+        .await?;
+
+    reprovision_tenant_keyspace(&session, &tenant_ks).await?;
+    assert_eq!(indexed_uids().await?, vec![typed_uid, unindexed_uid]);
+
+    // Reprovisioning a keyspace whose backfill has completed leaves it alone
+    insert_process_name(4, Some("Process")).await?;
+    reprovision_tenant_keyspace(&session, &tenant_ks).await?;
+    assert_eq!(indexed_uids().await?, vec![typed_uid, unindexed_uid]);
+
+    provisioner_client
+        .deprovision_graph_for_tenant(scylla_provisioner_msgs::DeprovisionGraphForTenantRequest {
+            tenant_id,
+        })
+        .await?;
+
+    Ok(())
+}
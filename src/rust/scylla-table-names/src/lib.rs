@@ -0,0 +1,59 @@
+//! The tables provisioned for each tenant. scylla-provisioner, graph-mutation
+//! and graph-query-service all depend on this crate rather than keeping their
+//! own copies, so that the three can't drift apart.
+
+pub const MAX_I_64_TABLE_NAME: &str = "max_i64";
+pub const MIN_I_64_TABLE_NAME: &str = "min_i64";
+pub const IMM_I_64_TABLE_NAME: &str = "imm_i64";
+pub const MAX_U_64_TABLE_NAME: &str = "max_u64";
+pub const MIN_U_64_TABLE_NAME: &str = "min_u64";
+pub const IMM_U_64_TABLE_NAME: &str = "imm_u64";
+pub const IMM_STRING_TABLE_NAME: &str = "imm_string";
+pub const MUT_STRING_TABLE_NAME: &str = "mut_string";
+pub const MUT_F_64_TABLE_NAME: &str = "mut_f64";
+pub const NODE_TYPE_TABLE_NAME: &str = "node_type";
+pub const EDGES_TABLE_NAME: &str = "edges";
+
+/// Every property table, along with the CQL type of its `value` column.
+/// Unsigned values are stored as `bigint` and cast on the way in and out.
+pub const PROPERTY_TABLES: [(&str, &str); 9] = [
+    (MAX_I_64_TABLE_NAME, "bigint"),
+    (MIN_I_64_TABLE_NAME, "bigint"),
+    (IMM_I_64_TABLE_NAME, "bigint"),
+    (MAX_U_64_TABLE_NAME, "bigint"),
+    (MIN_U_64_TABLE_NAME, "bigint"),
+    (IMM_U_64_TABLE_NAME, "bigint"),
+    (IMM_STRING_TABLE_NAME, "text"),
+    (MUT_STRING_TABLE_NAME, "text"),
+    (MUT_F_64_TABLE_NAME, "double"),
+];
+
+pub const IMM_I_64_INDEX_TABLE_NAME: &str = "imm_i64_index";
+pub const IMM_U_64_INDEX_TABLE_NAME: &str = "imm_u64_index";
+pub const IMM_STRING_INDEX_TABLE_NAME: &str = "imm_string_index";
+
+/// Every property index table, along with the CQL type of its `value` column.
+/// These map a property's value back to the uids of the nodes that have it,
+/// and are partitioned by `(node_type, populated_field)` so that string values
+/// can be range scanned by prefix.
+///
+/// Only immutable properties are indexed, since an index entry for a mutable
+/// property would have to be removed every time its value changed.
+pub const PROPERTY_INDEX_TABLES: [(&str, &str); 3] = [
+    (IMM_I_64_INDEX_TABLE_NAME, "bigint"),
+    (IMM_U_64_INDEX_TABLE_NAME, "bigint"),
+    (IMM_STRING_INDEX_TABLE_NAME, "text"),
+];
+
+/// Records which of scylla-provisioner's one-off provisioning steps, like
+/// backfilling the property indexes, have completed for the keyspace.
+pub const PROVISIONING_MARKERS_TABLE_NAME: &str = "provisioning_markers";
+
+/// Every tenant's keyspace name starts with this.
+pub const TENANT_KEYSPACE_PREFIX: &str = "tenant_keyspace_";
+
+pub fn tenant_keyspace_name(tenant_id: uuid::Uuid) -> String {
+    // scylla keyspace names must be alphanumeric + underscores, and max out at 48.
+    // fun fact: the result of this is exactly 48
+    format!("{TENANT_KEYSPACE_PREFIX}{}", tenant_id.simple())
+}
@@ -0,0 +1,177 @@
+use rust_proto::{
+    client_factory::services::{
+        EventSourceClientConfig,
+        PluginRegistryClientConfig,
+        PluginWorkQueueClientConfig,
+        ScyllaProvisionerClientConfig,
+        UidAllocatorClientConfig,
+    },
+    graplinc::grapl::api::{
+        event_source::v1beta1::{
+            client::EventSourceServiceClient,
+            DeleteEventSourcesForTenantRequest,
+        },
+        plugin_registry::v1beta1::{
+            DeletePluginsForTenantRequest,
+            PluginRegistryServiceClient,
+        },
+        plugin_work_queue::v1beta1::{
+            DeleteExecutionsForTenantRequest,
+            PluginWorkQueueServiceClient,
+        },
+        scylla_provisioner::v1beta1::{
+            client::ScyllaProvisionerClient,
+            messages::DeprovisionGraphForTenantRequest,
+        },
+        uid_allocator::v1beta1::{
+            client::UidAllocatorServiceClient,
+            messages::DeleteTenantKeyspaceRequest,
+        },
+    },
+    protocol::{
+        error::GrpcClientError,
+        service_client::{
+            ConnectError,
+            ConnectWithConfig,
+        },
+    },
+};
+
+#[derive(clap::Parser, Debug)]
+pub struct TenantPurgeConfig {
+    #[clap(long, env)]
+    pub tenant_id: uuid::Uuid,
+
+    #[clap(flatten)]
+    pub plugin_registry_client_config: PluginRegistryClientConfig,
+
+    #[clap(flatten)]
+    pub plugin_work_queue_client_config: PluginWorkQueueClientConfig,
+
+    #[clap(flatten)]
+    pub event_source_client_config: EventSourceClientConfig,
+
+    #[clap(flatten)]
+    pub scylla_provisioner_client_config: ScyllaProvisionerClientConfig,
+
+    #[clap(flatten)]
+    pub uid_allocator_client_config: UidAllocatorClientConfig,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TenantPurgeError {
+    #[error("failed to connect: {0}")]
+    ConnectError(#[from] ConnectError),
+    #[error("failed to {step}: {source}")]
+    StepFailed {
+        step: &'static str,
+        source: GrpcClientError,
+    },
+}
+
+/// Removes every trace of a tenant from Grapl by calling each service's
+/// tenant deletion RPC in turn.
+///
+/// Everything that feeds the plugin work queue is torn down before the
+/// tenant's queued work is deleted, so that no new work is enqueued behind the
+/// purge: event sources first, then plugins. pipeline-ingress doesn't check
+/// event sources, so it's deleting the plugins that actually stops the
+/// dispatchers, which find no generators or analyzers for the tenant's logs
+/// and graphs from then on. The uid allocator is cleared last since nothing
+/// can write to the tenant's graph once its keyspace is gone.
+///
+/// Every step is idempotent, so a purge that failed part way through can
+/// simply be run again. Running it again also clears out any work that a
+/// dispatch already in flight during the purge managed to enqueue.
+pub struct TenantPurger {
+    plugin_registry_client: PluginRegistryServiceClient,
+    plugin_work_queue_client: PluginWorkQueueServiceClient,
+    event_source_client: EventSourceServiceClient,
+    scylla_provisioner_client: ScyllaProvisionerClient,
+    uid_allocator_client: UidAllocatorServiceClient,
+}
+
+impl TenantPurger {
+    pub fn new(
+        plugin_registry_client: PluginRegistryServiceClient,
+        plugin_work_queue_client: PluginWorkQueueServiceClient,
+        event_source_client: EventSourceServiceClient,
+        scylla_provisioner_client: ScyllaProvisionerClient,
+        uid_allocator_client: UidAllocatorServiceClient,
+    ) -> Self {
+        Self {
+            plugin_registry_client,
+            plugin_work_queue_client,
+            event_source_client,
+            scylla_provisioner_client,
+            uid_allocator_client,
+        }
+    }
+
+    pub async fn connect(config: TenantPurgeConfig) -> Result<Self, TenantPurgeError> {
+        Ok(Self::new(
+            PluginRegistryServiceClient::connect_with_config(config.plugin_registry_client_config)
+                .await?,
+            PluginWorkQueueServiceClient::connect_with_config(
+                config.plugin_work_queue_client_config,
+            )
+            .await?,
+            EventSourceServiceClient::connect_with_config(config.event_source_client_config)
+                .await?,
+            ScyllaProvisionerClient::connect_with_config(config.scylla_provisioner_client_config)
+                .await?,
+            UidAllocatorServiceClient::connect_with_config(config.uid_allocator_client_config)
+                .await?,
+        ))
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn purge(&mut self, tenant_id: uuid::Uuid) -> Result<(), TenantPurgeError> {
+        self.event_source_client
+            .delete_event_sources_for_tenant(DeleteEventSourcesForTenantRequest { tenant_id })
+            .await
+            .map_err(|source| TenantPurgeError::StepFailed {
+                step: "delete event sources",
+                source,
+            })?;
+        tracing::info!(message = "deleted event sources", tenant_id =% tenant_id);
+
+        self.plugin_registry_client
+            .delete_plugins_for_tenant(DeletePluginsForTenantRequest::new(tenant_id))
+            .await
+            .map_err(|source| TenantPurgeError::StepFailed {
+                step: "delete plugins",
+                source,
+            })?;
+        tracing::info!(message = "deleted plugins", tenant_id =% tenant_id);
+
+        self.plugin_work_queue_client
+            .delete_executions_for_tenant(DeleteExecutionsForTenantRequest { tenant_id })
+            .await
+            .map_err(|source| TenantPurgeError::StepFailed {
+                step: "delete plugin executions",
+                source,
+            })?;
+        tracing::info!(message = "deleted plugin executions", tenant_id =% tenant_id);
+
+        self.scylla_provisioner_client
+            .deprovision_graph_for_tenant(DeprovisionGraphForTenantRequest { tenant_id })
+            .await
+            .map_err(|source| TenantPurgeError::StepFailed {
+                step: "deprovision graph",
+                source,
+            })?;
+        tracing::info!(message = "deprovisioned graph", tenant_id =% tenant_id);
+
+        self.uid_allocator_client
+            .delete_tenant_keyspace(DeleteTenantKeyspaceRequest { tenant_id })
+            .await
+            .map_err(|source| TenantPurgeError::StepFailed {
+                step: "delete uid allocator keyspace",
+                source,
+            })?;
+        tracing::info!(message = "deleted uid allocator keyspace", tenant_id =% tenant_id);
+
+        Ok(())
+    }
+}
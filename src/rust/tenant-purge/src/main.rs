@@ -0,0 +1,22 @@
+use clap::Parser;
+use grapl_tracing::setup_tracing;
+use tenant_purge::{
+    TenantPurgeConfig,
+    TenantPurger,
+};
+
+const SERVICE_NAME: &'static str = "tenant-purge";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = setup_tracing(SERVICE_NAME)?;
+    let config = TenantPurgeConfig::parse();
+    let tenant_id = config.tenant_id;
+    tracing::info!(message = "purging tenant", tenant_id =% tenant_id);
+
+    let mut purger = TenantPurger::connect(config).await?;
+    purger.purge(tenant_id).await?;
+
+    tracing::info!(message = "purged tenant", tenant_id =% tenant_id);
+    Ok(())
+}
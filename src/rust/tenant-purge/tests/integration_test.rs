@@ -0,0 +1,156 @@
+#![cfg(feature = "integration_tests")]
+
+use bytes::Bytes;
+use clap::Parser;
+use rust_proto::{
+    client_factory::services::{
+        EventSourceClientConfig,
+        PluginRegistryClientConfig,
+        PluginWorkQueueClientConfig,
+        ScyllaProvisionerClientConfig,
+        UidAllocatorClientConfig,
+    },
+    graplinc::grapl::api::{
+        event_source::v1beta1::{
+            client::EventSourceServiceClient,
+            CreateEventSourceRequest,
+            GetEventSourceRequest,
+        },
+        plugin_registry::v1beta1::{
+            GetPluginRequest,
+            PluginMetadata,
+            PluginRegistryServiceClient,
+            PluginType,
+        },
+        plugin_work_queue::v1beta1::{
+            ExecutionJob,
+            GetExecuteGeneratorRequest,
+            PluginWorkQueueServiceClient,
+            PushExecuteGeneratorRequest,
+        },
+        scylla_provisioner::v1beta1::{
+            client::ScyllaProvisionerClient,
+            messages::ProvisionGraphForTenantRequest,
+        },
+        uid_allocator::v1beta1::{
+            client::UidAllocatorServiceClient,
+            messages::{
+                AllocateIdsRequest,
+                CreateTenantKeyspaceRequest,
+            },
+        },
+    },
+    protocol::{
+        error::GrpcClientError,
+        service_client::ConnectWithConfig,
+        status::Code,
+    },
+};
+use tenant_purge::TenantPurger;
+
+#[test_log::test(tokio::test)]
+async fn test_purge_tenant() -> eyre::Result<()> {
+    let mut event_source_client =
+        EventSourceServiceClient::connect_with_config(EventSourceClientConfig::parse()).await?;
+    let mut plugin_registry_client =
+        PluginRegistryServiceClient::connect_with_config(PluginRegistryClientConfig::parse())
+            .await?;
+    let mut plugin_work_queue_client =
+        PluginWorkQueueServiceClient::connect_with_config(PluginWorkQueueClientConfig::parse())
+            .await?;
+    let mut scylla_provisioner_client =
+        ScyllaProvisionerClient::connect_with_config(ScyllaProvisionerClientConfig::parse())
+            .await?;
+    let mut uid_allocator_client =
+        UidAllocatorServiceClient::connect_with_config(UidAllocatorClientConfig::parse()).await?;
+
+    let tenant_id = uuid::Uuid::new_v4();
+
+    // Set up a tenant with something in every service the purge touches
+    let event_source_id = event_source_client
+        .create_event_source(CreateEventSourceRequest {
+            display_name: "purged".to_owned(),
+            description: "purged".to_owned(),
+            tenant_id,
+        })
+        .await?
+        .event_source_id;
+
+    let artifact = Bytes::from("fake");
+    let plugin_id = plugin_registry_client
+        .create_plugin(
+            PluginMetadata::new(
+                tenant_id,
+                "purged".to_owned(),
+                PluginType::Generator,
+                Some(event_source_id),
+            ),
+            futures::stream::once(async move { artifact.clone() }),
+        )
+        .await?
+        .plugin_id();
+
+    plugin_work_queue_client
+        .push_execute_generator(PushExecuteGeneratorRequest::new(
+            ExecutionJob::new(
+                "purged".into(),
+                tenant_id,
+                uuid::Uuid::new_v4(),
+                event_source_id,
+            ),
+            plugin_id,
+        ))
+        .await?;
+
+    scylla_provisioner_client
+        .provision_graph_for_tenant(ProvisionGraphForTenantRequest { tenant_id })
+        .await?;
+    uid_allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    let mut purger = TenantPurger::new(
+        plugin_registry_client.clone(),
+        plugin_work_queue_client.clone(),
+        event_source_client.clone(),
+        scylla_provisioner_client,
+        uid_allocator_client.clone(),
+    );
+    purger.purge(tenant_id).await?;
+
+    let event_source = event_source_client
+        .get_event_source(GetEventSourceRequest { event_source_id })
+        .await;
+    assert!(event_source.is_err(), "{event_source:?}");
+
+    match plugin_registry_client
+        .get_plugin(GetPluginRequest::new(plugin_id, tenant_id))
+        .await
+    {
+        Err(GrpcClientError::ErrorStatus(status)) => {
+            assert_eq!(status.code(), Code::NotFound, "{status}")
+        }
+        other => panic!("expected NotFound, got {other:?}"),
+    }
+
+    let execution_job = plugin_work_queue_client
+        .get_execute_generator(GetExecuteGeneratorRequest::new(plugin_id))
+        .await?
+        .execution_job();
+    assert_eq!(execution_job, None);
+
+    // Nothing was allocated for the tenant before the purge, so no uid
+    // allocator holds on to a range for it
+    let allocation = uid_allocator_client
+        .allocate_ids(AllocateIdsRequest {
+            count: 1,
+            tenant_id,
+        })
+        .await;
+    assert!(allocation.is_err(), "{allocation:?}");
+
+    // Purging a tenant that's already gone is a no-op
+    purger.purge(tenant_id).await?;
+
+    Ok(())
+}
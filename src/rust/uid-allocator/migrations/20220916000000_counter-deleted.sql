@@ -0,0 +1,5 @@
+-- A deleted tenant's counter is kept rather than removed, so that the uids
+-- allocated before it was deleted are never handed out again if the tenant is
+-- created again. Other uid-allocator instances, and clients, may still hold
+-- ranges below the counter.
+ALTER TABLE counters ADD COLUMN IF NOT EXISTS deleted boolean NOT NULL DEFAULT false;
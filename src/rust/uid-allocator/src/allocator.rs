@@ -176,9 +176,35 @@ impl UidAllocator {
         &self,
         tenant_id: uuid::Uuid,
     ) -> Result<(), UidAllocatorServiceError> {
+        // A tenant that was deleted carries on from its old counter
         sqlx::query!(
             r"INSERT INTO counters (tenant_id, counter) VALUES ($1, 1)
-            ON CONFLICT DO NOTHING;",
+            ON CONFLICT (tenant_id) DO UPDATE SET deleted = false;",
+            tenant_id
+        )
+        .execute(&self.db.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Marks the tenant's counter as deleted and drops this instance's
+    /// preallocated range for the tenant, so that no new range can be
+    /// preallocated for it until its keyspace is created again.
+    ///
+    /// Other instances, and `CachingUidAllocatorServiceClient`s, may still hand
+    /// out uids from ranges they already hold until those run out. The counter
+    /// itself is kept, so those uids are never handed out a second time if the
+    /// tenant is created again.
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_tenant_keyspace(
+        &self,
+        tenant_id: uuid::Uuid,
+    ) -> Result<(), UidAllocatorServiceError> {
+        self.allocated_ranges.remove(&tenant_id);
+
+        sqlx::query!(
+            r"UPDATE counters SET deleted = true WHERE tenant_id = $1;",
             tenant_id
         )
         .execute(&self.db.pool)
@@ -42,7 +42,8 @@ impl CounterDb {
         size: u32,
     ) -> Result<PreAllocation, UidAllocatorServiceError> {
         let mut conn = self.pool.acquire().await?;
-        // Increments the tenant's allocation counter by `size`, and returns the new value as well as the previous value
+        // Increments the tenant's allocation counter by `size`, and returns the new value as well as the previous value.
+        // A deleted tenant has no counter to allocate from.
         let count = sqlx::query_as!(
             Count,
             "
@@ -51,7 +52,7 @@ impl CounterDb {
             FROM (
                      SELECT counter as prev
                      FROM counters
-                     WHERE counters.tenant_id = $2
+                     WHERE counters.tenant_id = $2 AND NOT counters.deleted
                      LIMIT 1
                      FOR UPDATE
                  ) as c
@@ -8,6 +8,8 @@ use rust_proto::{
             AllocateIdsResponse,
             CreateTenantKeyspaceRequest,
             CreateTenantKeyspaceResponse,
+            DeleteTenantKeyspaceRequest,
+            DeleteTenantKeyspaceResponse,
         },
         server::{
             UidAllocatorApi,
@@ -81,6 +83,17 @@ impl UidAllocatorApi for UidAllocatorService {
 
         Ok(CreateTenantKeyspaceResponse {})
     }
+
+    #[tracing::instrument(err, skip(self))]
+    async fn delete_tenant_keyspace(
+        &self,
+        request: DeleteTenantKeyspaceRequest,
+    ) -> Result<DeleteTenantKeyspaceResponse, Self::Error> {
+        let tenant_id = request.tenant_id;
+        self.allocator.delete_tenant_keyspace(tenant_id).await?;
+
+        Ok(DeleteTenantKeyspaceResponse {})
+    }
 }
 
 pub async fn exec_service(
@@ -3,9 +3,17 @@
 use clap::Parser;
 use rust_proto::{
     client_factory::services::UidAllocatorClientConfig,
-    graplinc::grapl::api::uid_allocator::v1beta1::messages::CreateTenantKeyspaceRequest,
+    graplinc::grapl::api::uid_allocator::v1beta1::messages::{
+        AllocateIdsRequest,
+        CreateTenantKeyspaceRequest,
+        DeleteTenantKeyspaceRequest,
+    },
+    protocol::service_client::ConnectWithConfig,
+};
+use uid_allocator::client::{
+    CachingUidAllocatorServiceClient,
+    UidAllocatorServiceClient,
 };
-use uid_allocator::client::CachingUidAllocatorServiceClient;
 
 #[tokio::test]
 async fn test_uid_allocator() -> eyre::Result<()> {
@@ -61,3 +69,70 @@ async fn test_uid_allocator() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delete_tenant_keyspace() -> eyre::Result<()> {
+    let client_config = UidAllocatorClientConfig::parse();
+    let mut allocator_client = UidAllocatorServiceClient::connect_with_config(client_config).await?;
+
+    // Nothing has been allocated for this tenant yet, so no instance holds a
+    // preallocated range for it, and allocating fails once it's deleted
+    let tenant_id = uuid::Uuid::new_v4();
+    allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+    allocator_client
+        .delete_tenant_keyspace(DeleteTenantKeyspaceRequest { tenant_id })
+        .await?;
+    let allocation = allocator_client
+        .allocate_ids(AllocateIdsRequest {
+            count: 100,
+            tenant_id,
+        })
+        .await;
+    assert!(allocation.is_err(), "{allocation:?}");
+
+    // Uids allocated before a tenant is deleted are never handed out again
+    // once it's created again, even though an instance may still be holding
+    // on to a range preallocated before the delete
+    let tenant_id = uuid::Uuid::new_v4();
+    allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    let mut uids = std::collections::HashSet::new();
+    for _ in 0..10 {
+        let allocation = allocator_client
+            .allocate_ids(AllocateIdsRequest {
+                count: 100,
+                tenant_id,
+            })
+            .await?
+            .allocation;
+        for uid in allocation {
+            assert!(uids.insert(uid), "uid {uid} was not unique");
+        }
+    }
+
+    allocator_client
+        .delete_tenant_keyspace(DeleteTenantKeyspaceRequest { tenant_id })
+        .await?;
+    allocator_client
+        .create_tenant_keyspace(CreateTenantKeyspaceRequest { tenant_id })
+        .await?;
+
+    for _ in 0..10 {
+        let allocation = allocator_client
+            .allocate_ids(AllocateIdsRequest {
+                count: 100,
+                tenant_id,
+            })
+            .await?
+            .allocation;
+        for uid in allocation {
+            assert!(uids.insert(uid), "uid {uid} was reused after the delete");
+        }
+    }
+
+    Ok(())
+}